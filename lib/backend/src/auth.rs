@@ -0,0 +1,85 @@
+//! Authentication of gRPC callers.
+//!
+//! Frontends and peers present the shared cluster token in the
+//! `x-lycaon-token` metadata header on every call. Alternatively the
+//! cluster can rely on client certificates alone, see `tls`.
+
+use futures::Future;
+use grpcio::{CallOption, MetadataBuilder, RpcContext, RpcStatus, RpcStatusCode,
+             ServerStreamingSink, UnarySink};
+
+use trace;
+
+pub const TOKEN_HEADER: &'static str = "x-lycaon-token";
+
+/// Whether _token_ can be sent as call metadata, which only takes
+/// printable ASCII. Checked when the configuration is loaded.
+pub fn valid_token(token: &str) -> bool {
+    MetadataBuilder::with_capacity(1)
+        .add_str(TOKEN_HEADER, token)
+        .is_ok()
+}
+
+/// The cluster token, when none is configured every call is accepted.
+#[derive(Clone, Debug, Default)]
+pub struct ClusterAuth {
+    token: Option<String>,
+}
+
+impl ClusterAuth {
+    pub fn new(token: Option<String>) -> ClusterAuth {
+        ClusterAuth { token }
+    }
+
+    /// Options for outgoing calls, carrying the token if there is one
+    /// and the request ID and trace of the current span.
+    pub fn call_option(&self) -> CallOption {
+        let mut headers = MetadataBuilder::with_capacity(3);
+        if let Some(ref token) = self.token {
+            if headers.add_str(TOKEN_HEADER, token).is_err() {
+                warn!("Cluster token is not valid metadata, calling without it");
+            }
+        }
+        trace::inject(&mut headers);
+        CallOption::default().headers(headers.build())
+    }
+
+    /// Checks the token sent along with an incoming call.
+    pub fn verify(&self, ctx: &RpcContext) -> bool {
+        let expected = match self.token {
+            Some(ref token) => token,
+            None => return true,
+        };
+        ctx.request_headers()
+            .iter()
+            .any(|(key, value)| key == TOKEN_HEADER && constant_time_eq(value, expected.as_bytes()))
+    }
+}
+
+/// Replies `UNAUTHENTICATED` to a call that failed `ClusterAuth::verify`.
+pub fn reject<T>(ctx: RpcContext, sink: UnarySink<T>) {
+    warn!("Rejecting unauthenticated call to {}", String::from_utf8_lossy(ctx.method()));
+    let status = RpcStatus::new(
+        RpcStatusCode::Unauthenticated,
+        Some("missing or invalid cluster token".to_owned()),
+    );
+    let f = sink.fail(status)
+        .map_err(move |e| warn!("failed to reply! {:?}", e));
+    ctx.spawn(f);
+}
+
+/// Like `reject`, for calls streaming their response.
+pub fn reject_stream<T>(ctx: RpcContext, sink: ServerStreamingSink<T>) {
+    warn!("Rejecting unauthenticated call to {}", String::from_utf8_lossy(ctx.method()));
+    let status = RpcStatus::new(
+        RpcStatusCode::Unauthenticated,
+        Some("missing or invalid cluster token".to_owned()),
+    );
+    let f = sink.fail(status)
+        .map_err(move |e| warn!("failed to reply! {:?}", e));
+    ctx.spawn(f);
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,40 +1,134 @@
 use std;
-use std::path::Path;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use grpcio;
 use grpc;
 
 use failure::Error;
 use std::error::Error as ErrorTrait;
-use futures::Future;
+use futures::{future, stream, Future, Sink, Stream};
+use futures::future::Either;
+use grpc::backend::EventKind;
+use grpc::peer_grpc::PeerClient;
+use serde_json;
 use uuid::Uuid;
 
+use auth::{self, ClusterAuth};
+use digest::Digest;
+use events::{self, EventBus};
+use health::HealthService;
+use metrics;
+use peer::{Deltas, Peers};
+use referrers::Referrers;
+use trace;
 use util;
 
 /// Struct implementing callbacks for the Frontend
 ///
 /// _uploads_: a HashSet of all uuids that are currently being tracked
+/// _auth_: checks callers present the cluster token
+/// _events_: where changes are published for `watch` subscribers
+/// _referrers_: manifests referring to others through their subject
+/// _peers_: where copies of blobs are fetched from
+/// _deltas_: where committed blobs are queued for the peers
+/// _health_: whether this backend is ready for calls
+/// _data_: the data directory layers and uploads are kept in
 #[derive(Clone)]
 pub struct BackendService {
-    uploads: Arc<Mutex<std::collections::HashSet<Layer>>>,
+    uploads: Arc<Mutex<HashSet<Layer>>>,
+    auth: ClusterAuth,
+    events: EventBus,
+    referrers: Referrers,
+    peers: Peers,
+    deltas: Deltas,
+    health: HealthService,
+    data: PathBuf,
 }
 
+/// Upload sessions saved on shutdown, so uploads survive a restart
+const UPLOADS_FILE: &'static str = "uploads.json";
+
 impl BackendService {
-    pub fn new() -> Self {
-        BackendService { uploads: Arc::new(Mutex::new(std::collections::HashSet::new())) }
+    pub fn new(
+        auth: ClusterAuth,
+        events: EventBus,
+        referrers: Referrers,
+        peers: Peers,
+        deltas: Deltas,
+        health: HealthService,
+        data: &Path,
+    ) -> Self {
+        let uploads = load_uploads(&data.join(UPLOADS_FILE));
+        metrics::UPLOAD_SESSIONS.set(uploads.len() as f64);
+        BackendService {
+            uploads: Arc::new(Mutex::new(uploads)),
+            auth,
+            events,
+            referrers,
+            peers,
+            deltas,
+            health,
+            data: data.to_owned(),
+        }
+    }
+
+    pub fn health(&self) -> &HealthService {
+        &self.health
     }
+
+    /// Saves the open upload sessions for the next start.
+    pub fn persist(&self) -> Result<(), Error> {
+        let uploads = self.uploads.lock().unwrap();
+        let uploads: Vec<&Layer> = uploads.iter().collect();
+        let path = self.data.join(UPLOADS_FILE);
+        fs::create_dir_all(&self.data)?;
+        let tmp = path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&serde_json::to_vec(&uploads)?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, path).map_err(|e| e.into())
+    }
+}
+
+/// The upload sessions saved by the last shutdown at _path_, which are
+/// forgotten once loaded so a crash doesn't bring back finished ones.
+fn load_uploads(path: &Path) -> HashSet<Layer> {
+    let mut contents = Vec::new();
+    if File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut contents))
+        .is_err()
+    {
+        return HashSet::new();
+    }
+    let _ = fs::remove_file(path);
+    let uploads: Vec<Layer> = serde_json::from_slice(&contents).unwrap_or_else(|e| {
+        warn!("Ignoring unreadable {:?}: {}", path, e);
+        Vec::new()
+    });
+    info!("Resuming {} upload sessions", uploads.len());
+    uploads.into_iter().collect()
 }
 
-#[derive(Eq, PartialEq, Hash, Debug, Clone)]
+/// _name_ is the full repository name, of any depth
+#[derive(Eq, PartialEq, Hash, Debug, Clone, Serialize, Deserialize)]
 pub struct Layer {
     pub name: String,
-    pub repo: String,
     pub digest: String,
 }
 
-fn process(layer: Layer) -> Result<u64, Error> {
-    let path = construct_absolute_path(layer)?;
+/// The size of the blob of _layer_ in _data_, failing unless it's
+/// linked into the repository of _layer_.
+fn process(data: &Path, layer: Layer) -> Result<u64, Error> {
+    let digest: Digest = layer.digest.parse()?;
+    std::fs::metadata(util::link_path(data, &layer.name, &digest))?;
+    let path = construct_path(data, layer)?;
     std::fs::metadata(path.as_os_str())?;
     debug!("File {:?} Exists", path.as_os_str());
     let file = std::fs::File::open(path)?;
@@ -42,28 +136,21 @@ fn process(layer: Layer) -> Result<u64, Error> {
 
 }
 
-/// Delete a file if we want
-pub fn delete_blob_by_uuid(layer: &Layer) -> bool {
+/// Removes the scratch file of the upload _layer_, whose digest is the
+/// upload uuid.
+pub fn delete_blob_by_uuid(data: &Path, layer: &Layer) -> bool {
     use std::fs;
-    let path = format!(
-        "data/scratch/{}/{}/{}",
-        layer.name,
-        layer.repo,
-        layer.digest
-    );
+    let path = util::scratch_path(data, &layer.digest);
 
     fs::remove_file(path).map(|_| true).unwrap_or(false)
 }
 
-/// Takes the digest, and constructs an absolute pathstring to the digest.
-fn construct_absolute_path(layer: Layer) -> Result<Box<Path>, Error> {
-    std::env::current_dir()
-        .map(|cwd| {
-            let absolute_dir = cwd.join(format!("data/layers/{}", layer.digest));
-            debug!("Absolute Path: {:?}", absolute_dir);
-            absolute_dir.into_boxed_path()
-        })
-        .map_err(|e| e.into())
+/// Takes the digest, and constructs the path to the layer in _data_.
+fn construct_path(data: &Path, layer: Layer) -> Result<PathBuf, Error> {
+    let digest: Digest = layer.digest.parse()?;
+    let path = util::layer_path(data, &digest);
+    debug!("Layer Path: {:?}", path);
+    Ok(path)
 }
 
 fn file_length(file: std::fs::File) -> Result<u64, Error> {
@@ -72,21 +159,29 @@ fn file_length(file: std::fs::File) -> Result<u64, Error> {
         .map_err(|e| e.into())
 }
 
-impl grpc::backend_grpc::Backend for BackendService {
-    fn layer_exists(
+/// Chunks of a blob as they're fetched from a peer
+pub type BlobStream = Box<Stream<Item = grpc::backend::BlobChunk, Error = grpcio::Error> + Send>;
+
+/// The blob of the first peer which has it
+pub type BlobFetch = Box<Future<Item = BlobStream, Error = grpcio::RpcStatus> + Send>;
+
+/// Seconds a peer has to say whether it has a blob
+const PROBE_TIMEOUT: u64 = 5;
+
+/// The calls of the `Backend` service without the gRPC plumbing, so a
+/// frontend in the same process can make them directly.
+impl BackendService {
+    pub fn layer_exists(
         &self,
-        ctx: grpcio::RpcContext,
-        req: grpc::backend::Layer,
-        sink: grpcio::UnarySink<grpc::backend::LayerExistsResult>,
-    ) {
+        req: &grpc::backend::Layer,
+    ) -> Result<grpc::backend::LayerExistsResult, grpcio::RpcStatus> {
         let layer = Layer {
             name: req.get_name().to_owned(),
-            repo: req.get_repo().to_owned(),
             digest: req.get_digest().to_owned(),
         };
 
         let mut resp = grpc::backend::LayerExistsResult::new();
-        let _ = process(layer)
+        let _ = process(&self.data, layer)
             .map(|length| {
                 debug!("Success, building return object");
                 resp.set_success(true);
@@ -96,86 +191,187 @@ impl grpc::backend_grpc::Backend for BackendService {
                 debug!("Failure, building return object");
                 resp.set_success(false);
             });
-
-        let req = req.clone();
-        let f = sink.success(resp).map_err(move |e| {
-            warn!("failed to reply! {:?}, {:?}", req, e)
-        });
-        ctx.spawn(f);
+        Ok(resp)
     }
 
-    fn gen_uuid(
+    pub fn gen_uuid(
         &self,
-        ctx: grpcio::RpcContext,
-        req: grpc::backend::Layer,
-        sink: grpcio::UnarySink<grpc::backend::GenUuidResult>,
-    ) {
+        req: &grpc::backend::Layer,
+    ) -> Result<grpc::backend::GenUuidResult, grpcio::RpcStatus> {
         let mut resp = grpc::backend::GenUuidResult::new();
         let layer = Layer {
             name: req.get_name().to_owned(),
-            repo: req.get_repo().to_owned(),
             digest: gen_uuid().to_string(),
         };
         {
-            self.uploads.lock().unwrap().insert(layer.clone());
-            debug!("Hash Table: {:?}", self.uploads);
+            let mut uploads = self.uploads.lock().unwrap();
+            uploads.insert(layer.clone());
+            metrics::UPLOAD_SESSIONS.set(uploads.len() as f64);
+            debug!("Hash Table: {:?}", uploads);
         }
+        let mut event = events::event(EventKind::UPLOAD_STARTED, &layer.name);
+        event.set_digest(layer.digest.clone());
+        self.events.publish(event);
         resp.set_uuid(layer.digest.to_owned());
-        let f = sink.success(resp).map_err(
-            move |e| warn!("failed to reply! {:?}", e),
-        );
-        ctx.spawn(f);
+        Ok(resp)
     }
 
-    fn uuid_exists(
+    pub fn uuid_exists(
         &self,
-        ctx: grpcio::RpcContext,
-        req: grpc::backend::Layer,
-        sink: grpcio::UnarySink<grpc::backend::Result>,
-    ) {
+        req: &grpc::backend::Layer,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
         let mut resp = grpc::backend::Result::new();
         let set = self.uploads.lock().unwrap();
         let layer = Layer {
             name: req.get_name().to_owned(),
-            repo: req.get_repo().to_owned(),
             digest: req.get_digest().to_owned(),
         };
         resp.set_success(set.contains(&layer));
-
-        let f = sink.success(resp).map_err(
-            move |e| warn!("failed to reply! {:?}", e),
-        );
-        ctx.spawn(f);
+        Ok(resp)
     }
 
-    fn cancel_upload(
+    pub fn cancel_upload(
         &self,
-        ctx: grpcio::RpcContext,
-        req: grpc::backend::Layer,
-        sink: grpcio::UnarySink<grpc::backend::Result>,
-    ) {
+        req: &grpc::backend::Layer,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
         let mut resp = grpc::backend::Result::new();
         let mut set = self.uploads.lock().unwrap();
         let layer = Layer {
             name: req.get_name().to_owned(),
-            repo: req.get_repo().to_owned(),
             digest: req.get_digest().to_owned(),
         };
-        let _ = delete_blob_by_uuid(&layer);
+        // only the scratch file of a known upload, the uuid comes from a client
         resp.set_success(set.remove(&layer));
+        if resp.get_success() {
+            let _ = delete_blob_by_uuid(&self.data, &layer);
+        }
+        metrics::UPLOAD_SESSIONS.set(set.len() as f64);
+        if resp.get_success() {
+            let mut event = events::event(EventKind::UPLOAD_CANCELLED, &layer.name);
+            event.set_digest(layer.digest.clone());
+            self.events.publish(event);
+        }
+        Ok(resp)
+    }
+
+    pub fn commit_upload(
+        &self,
+        req: &grpc::backend::CommittedBlob,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
+        let mut resp = grpc::backend::Result::new();
+        if let Err(e) = req.get_digest().parse::<Digest>() {
+            resp.set_text(e.to_string());
+            return Ok(resp);
+        }
+        {
+            let mut set = self.uploads.lock().unwrap();
+            let before = set.len();
+            set.retain(|layer| layer.digest != req.get_uuid());
+            resp.set_success(set.len() < before);
+            metrics::UPLOAD_SESSIONS.set(set.len() as f64);
+        }
+
+        if resp.get_success() {
+            self.deltas.add(req.get_digest());
+            let mut event = events::event(EventKind::BLOB_COMMITTED, req.get_name());
+            event.set_digest(req.get_digest().to_owned());
+            self.events.publish(event);
+        } else {
+            resp.set_text(format!("unknown upload {}", req.get_uuid()));
+        }
+        Ok(resp)
+    }
 
-        let f = sink.success(resp).map_err(
-            move |e| warn!("failed to reply! {:?}", e),
+    pub fn add_referrer(
+        &self,
+        req: &grpc::backend::Referrer,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
+        let mut resp = grpc::backend::Result::new();
+        match self.referrers.add(req) {
+            Ok(()) => resp.set_success(true),
+            Err(e) => {
+                warn!("Storing referrer {}: {}", req.get_digest(), e);
+                resp.set_text(e.to_string());
+            }
+        }
+        Ok(resp)
+    }
+
+    pub fn list_referrers(
+        &self,
+        req: &grpc::backend::ReferrersRequest,
+    ) -> Result<grpc::backend::ReferrerList, grpcio::RpcStatus> {
+        let referrers = self.referrers.list(
+            req.get_repository(),
+            req.get_subject(),
+            req.get_artifact_type(),
         );
-        ctx.spawn(f);
+        let mut resp = grpc::backend::ReferrerList::new();
+        resp.set_referrers(referrers.into());
+        Ok(resp)
     }
 
-    fn get_uuids(
+    pub fn remove_referrers(
         &self,
-        ctx: grpcio::RpcContext,
-        req: grpc::backend::Empty,
-        sink: grpcio::UnarySink<grpc::backend::UuidList>,
-    ) {
+        req: &grpc::backend::ReferrersRequest,
+    ) -> Result<grpc::backend::ReferrerList, grpcio::RpcStatus> {
+        match self.referrers.remove(req.get_repository(), req.get_subject()) {
+            Ok(referrers) => {
+                let mut resp = grpc::backend::ReferrerList::new();
+                resp.set_referrers(referrers.into());
+                Ok(resp)
+            }
+            Err(e) => {
+                warn!("Removing referrers of {}: {}", req.get_subject(), e);
+                Err(grpcio::RpcStatus::new(
+                    grpcio::RpcStatusCode::Internal,
+                    Some(e.to_string()),
+                ))
+            }
+        }
+    }
+
+    /// Asks the peers one after the other for a blob, without blocking:
+    /// the returned future resolves once one of them started sending it.
+    pub fn fetch_blob(&self, req: &grpc::backend::Layer) -> BlobFetch {
+        let mut request = grpc::peer::BlobRequest::new();
+        request.set_digest(req.get_digest().to_owned());
+        let digest = req.get_digest().to_owned();
+        let peers = self.peers.clone();
+        let auth = self.auth.clone();
+
+        let found = stream::iter_ok::<_, ()>(0..peers.len())
+            .and_then(move |i| {
+                let (ref address, ref client) = peers[i];
+                probe(address, client, &request, auth.call_option())
+            })
+            .filter_map(|found| found)
+            .into_future()
+            .then(move |found| match found {
+                Ok((Some(chunks), _)) => Ok(chunks),
+                _ => Err(grpcio::RpcStatus::new(
+                    grpcio::RpcStatusCode::NotFound,
+                    Some(format!("no peer has {}", digest)),
+                )),
+            });
+        Box::new(found)
+    }
+
+    pub fn publish(
+        &self,
+        req: &grpc::backend::Event,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
+        self.events.publish(req.clone());
+
+        let mut resp = grpc::backend::Result::new();
+        resp.set_success(true);
+        Ok(resp)
+    }
+
+    pub fn get_uuids(
+        &self,
+        _req: &grpc::backend::Empty,
+    ) -> Result<grpc::backend::UuidList, grpcio::RpcStatus> {
         let mut resp = grpc::backend::UuidList::new();
         {
             use protobuf;
@@ -188,11 +384,301 @@ impl grpc::backend_grpc::Backend for BackendService {
             });
             resp.set_uuids(protobuf::RepeatedField::from_iter(set));
         }
-        let f = sink.success(resp).map_err(
-            move |e| warn!("failed to reply! {:?}", e),
-        );
+        Ok(resp)
+    }
+
+    /// The id of the data directory, which frontends compare with theirs.
+    pub fn storage(
+        &self,
+        _req: &grpc::backend::Empty,
+    ) -> Result<grpc::backend::Result, grpcio::RpcStatus> {
+        let id = util::storage_id(&self.data).map_err(|e| {
+            grpcio::RpcStatus::new(grpcio::RpcStatusCode::Internal, Some(e.to_string()))
+        })?;
+        let mut resp = grpc::backend::Result::new();
+        resp.set_success(true);
+        resp.set_text(id);
+        Ok(resp)
+    }
+}
+
+/// Asks the peer at _address_ for a blob. A peer without it fails
+/// straight away, so the first message tells whether it has a copy; one
+/// not answering within `PROBE_TIMEOUT` is given up on.
+fn probe(
+    address: &str,
+    client: &PeerClient,
+    request: &grpc::peer::BlobRequest,
+    option: grpcio::CallOption,
+) -> Box<Future<Item = Option<BlobStream>, Error = ()> + Send> {
+    let address = address.to_owned();
+    let digest = request.get_digest().to_owned();
+    let chunks = match client.fetch_blob_opt(request, option) {
+        Ok(chunks) => chunks,
+        Err(e) => {
+            debug!("{} can't provide {}: {:?}", address, digest, e);
+            return Box::new(future::ok(None));
+        }
+    };
+    let timeout = util::timeout(Duration::from_secs(PROBE_TIMEOUT));
+    let probed = chunks.into_future().select2(timeout).then(move |probed| {
+        let found = match probed {
+            Ok(Either::A(((first, rest), _))) => {
+                debug!("Fetching {} from {}", digest, address);
+                let chunks = stream::iter_ok(first).chain(rest).map(|mut data| {
+                    let mut chunk = grpc::backend::BlobChunk::new();
+                    chunk.set_data(data.take_data());
+                    chunk
+                });
+                Some(Box::new(chunks) as BlobStream)
+            }
+            Ok(Either::B(_)) | Err(Either::B(_)) => {
+                debug!("{} didn't answer for {} in time", address, digest);
+                None
+            }
+            Err(Either::A(((e, _), _))) => {
+                debug!("{} can't provide {}: {:?}", address, digest, e);
+                None
+            }
+        };
+        Ok(found)
+    });
+    Box::new(probed)
+}
+
+/// Replies to a unary call with _result_.
+fn reply<T>(ctx: grpcio::RpcContext, sink: grpcio::UnarySink<T>, result: Result<T, grpcio::RpcStatus>) {
+    let f = match result {
+        Ok(resp) => sink.success(resp),
+        Err(status) => sink.fail(status),
+    };
+    ctx.spawn(f.map_err(move |e| warn!("failed to reply! {:?}", e)));
+}
+
+impl grpc::backend_grpc::Backend for BackendService {
+    fn layer_exists(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Layer,
+        sink: grpcio::UnarySink<grpc::backend::LayerExistsResult>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "layerExists");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["layerExists"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::layer_exists(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn gen_uuid(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Layer,
+        sink: grpcio::UnarySink<grpc::backend::GenUuidResult>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "GenUuid");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["GenUuid"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::gen_uuid(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn uuid_exists(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Layer,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "UuidExists");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["UuidExists"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::uuid_exists(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn cancel_upload(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Layer,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "cancelUpload");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["cancelUpload"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::cancel_upload(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn commit_upload(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::CommittedBlob,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "commitUpload");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["commitUpload"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::commit_upload(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn add_referrer(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Referrer,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "addReferrer");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["addReferrer"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::add_referrer(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn list_referrers(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::ReferrersRequest,
+        sink: grpcio::UnarySink<grpc::backend::ReferrerList>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "listReferrers");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["listReferrers"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::list_referrers(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn remove_referrers(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::ReferrersRequest,
+        sink: grpcio::UnarySink<grpc::backend::ReferrerList>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "removeReferrers");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["removeReferrers"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::remove_referrers(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn watch(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::WatchRequest,
+        sink: grpcio::ServerStreamingSink<grpc::backend::Event>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "watch");
+        if !self.auth.verify(&ctx) {
+            return auth::reject_stream(ctx, sink);
+        }
+        let repositories = req.get_repositories().to_vec();
+        let (backlog, receiver) = self.events.subscribe(req.get_cursor());
+        debug!("Watch from cursor {} for {:?}", req.get_cursor(), repositories);
+
+        let events = stream::iter_ok(backlog)
+            .chain(receiver)
+            .filter(move |event| {
+                // peer events concern the whole cluster
+                event.get_repository().is_empty() || repositories.is_empty()
+                    || repositories.iter().any(|r| r == event.get_repository())
+            })
+            .map(|event| (event, grpcio::WriteFlags::default()))
+            .map_err(|()| grpcio::Error::RemoteStopped);
+
+        let f = sink.send_all(events)
+            .map(|_| ())
+            .map_err(|e| debug!("watch ended: {:?}", e));
+        ctx.spawn(f);
+    }
+
+    fn fetch_blob(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Layer,
+        sink: grpcio::ServerStreamingSink<grpc::backend::BlobChunk>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "fetchBlob");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["fetchBlob"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject_stream(ctx, sink);
+        }
+        // the peers are probed as the completion queue polls, not by
+        // blocking it
+        let f = BackendService::fetch_blob(self, &req).then(move |found| match found {
+            Ok(chunks) => {
+                let chunks = chunks.map(|chunk| (chunk, grpcio::WriteFlags::default()));
+                let sent = sink.send_all(chunks)
+                    .map(|_| ())
+                    .map_err(|e| warn!("fetchBlob failed: {:?}", e));
+                Either::A(sent)
+            }
+            Err(status) => Either::B(
+                sink.fail(status)
+                    .map_err(move |e| warn!("failed to reply! {:?}", e)),
+            ),
+        });
         ctx.spawn(f);
     }
+
+    fn publish(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Event,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "publish");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["publish"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::publish(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn get_uuids(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Empty,
+        sink: grpcio::UnarySink<grpc::backend::UuidList>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "getUuids");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["getUuids"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::get_uuids(self, &req);
+        reply(ctx, sink, result);
+    }
+
+    fn storage(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: grpc::backend::Empty,
+        sink: grpcio::UnarySink<grpc::backend::Result>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "storage");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["storage"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
+        let result = BackendService::storage(self, &req);
+        reply(ctx, sink, result);
+    }
 }
 
 fn gen_uuid() -> Uuid {
@@ -2,6 +2,8 @@
 pub struct LycaonBackendConfig {
     pub listen: Service,
     pub bootstrap: Service,
+    pub tls: Option<TlsConfig>,
+    pub token: Option<String>,
 }
 
 impl LycaonBackendConfig {
@@ -12,6 +14,29 @@ impl LycaonBackendConfig {
     pub fn bootstrap(&self) -> Service {
         self.listen.clone()
     }
+
+    pub fn tls(&self) -> Option<TlsConfig> {
+        self.tls.clone()
+    }
+
+    /// The shared secret frontends and peers authenticate with.
+    pub fn token(&self) -> Option<String> {
+        self.token.clone()
+    }
+}
+
+/// PEM files used for mutual TLS between frontends, backends and peers.
+///
+/// _ca_: the CA every node's certificate must be signed by
+/// _cert_, _key_: this node's certificate and private key
+/// _server_name_: the name the other nodes' certificates are checked
+/// against, if not the host connected to, e.g. when that's an IP
+#[derive(Clone, Debug, Deserialize)]
+pub struct TlsConfig {
+    pub ca: String,
+    pub cert: String,
+    pub key: String,
+    pub server_name: Option<String>,
 }
 
 // DUPLICATED
@@ -0,0 +1,185 @@
+//! Content digests, `<algorithm>:<hex>`, shared by the frontend and
+//! the backend.
+
+use std::error::Error as ErrorTrait;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+use ring::digest;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Algorithm {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Length of the hex encoded hash
+    fn hex_len(&self) -> usize {
+        self.ring().output_len * 2
+    }
+
+    fn ring(&self) -> &'static digest::Algorithm {
+        match *self {
+            Algorithm::Sha256 => &digest::SHA256,
+            Algorithm::Sha512 => &digest::SHA512,
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = InvalidDigest;
+
+    fn from_str(s: &str) -> Result<Algorithm, InvalidDigest> {
+        match s {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            _ => Err(InvalidDigest(s.to_owned())),
+        }
+    }
+}
+
+/// A digest of a supported algorithm with a hash of the right length
+/// in lowercase hex.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Digest {
+    algorithm: Algorithm,
+    hex: String,
+}
+
+impl Digest {
+    /// The digest of _content_.
+    pub fn of(algorithm: Algorithm, content: &[u8]) -> Digest {
+        let mut hasher = Hasher::new(algorithm);
+        hasher.update(content);
+        hasher.finish()
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    pub fn hex(&self) -> &str {
+        &self.hex
+    }
+
+    /// A hasher of the same algorithm, to check content against this
+    /// digest.
+    pub fn hasher(&self) -> Hasher {
+        Hasher::new(self.algorithm)
+    }
+}
+
+impl FromStr for Digest {
+    type Err = InvalidDigest;
+
+    fn from_str(s: &str) -> Result<Digest, InvalidDigest> {
+        let invalid = || InvalidDigest(s.to_owned());
+        let mut parts = s.splitn(2, ':');
+        let algorithm: Algorithm = parts
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| invalid())?;
+        let hex = parts.next().ok_or_else(invalid)?;
+        let lower_hex = hex.bytes().all(|b| match b {
+            b'0'...b'9' | b'a'...b'f' => true,
+            _ => false,
+        });
+        if hex.len() != algorithm.hex_len() || !lower_hex {
+            return Err(invalid());
+        }
+        Ok(Digest {
+            algorithm,
+            hex: hex.to_owned(),
+        })
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm.name(), self.hex)
+    }
+}
+
+impl Serialize for Digest {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Digest, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Incrementally hashes content as it arrives.
+#[derive(Clone)]
+pub struct Hasher {
+    algorithm: Algorithm,
+    context: digest::Context,
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Hasher {
+        Hasher {
+            algorithm,
+            context: digest::Context::new(algorithm.ring()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.context.update(data)
+    }
+
+    pub fn finish(self) -> Digest {
+        let hex: Vec<String> = self.context
+            .finish()
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        Digest {
+            algorithm: self.algorithm,
+            hex: hex.concat(),
+        }
+    }
+}
+
+impl io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidDigest(String);
+
+impl fmt::Display for InvalidDigest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid digest {:?}", self.0)
+    }
+}
+
+impl ErrorTrait for InvalidDigest {
+    fn description(&self) -> &str {
+        "invalid digest"
+    }
+}
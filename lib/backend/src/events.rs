@@ -0,0 +1,87 @@
+//! In-process bus of registry events, streamed to `watch` subscribers.
+//!
+//! Every event gets an increasing cursor. The most recent events are
+//! kept around so a subscriber that lost its connection can resume
+//! where it left off.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use grpc::backend::{Event, EventKind};
+
+const HISTORY: usize = 1024;
+
+struct Inner {
+    cursor: u64,
+    history: VecDeque<Event>,
+    subscribers: Vec<UnboundedSender<Event>>,
+}
+
+#[derive(Clone)]
+pub struct EventBus {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl EventBus {
+    pub fn new() -> EventBus {
+        EventBus {
+            inner: Arc::new(Mutex::new(Inner {
+                cursor: 0,
+                history: VecDeque::with_capacity(HISTORY),
+                subscribers: Vec::new(),
+            })),
+        }
+    }
+
+    /// Assigns the next cursor to _event_ and sends it to all subscribers.
+    pub fn publish(&self, mut event: Event) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.cursor += 1;
+        event.set_cursor(inner.cursor);
+        event.set_timestamp(unix_time());
+        debug!("Event {}: {:?}", inner.cursor, event.get_kind());
+
+        if inner.history.len() == HISTORY {
+            inner.history.pop_front();
+        }
+        inner.history.push_back(event.clone());
+        inner
+            .subscribers
+            .retain(|subscriber| subscriber.unbounded_send(event.clone()).is_ok());
+    }
+
+    /// Subscribes to new events, returning the retained events after
+    /// _cursor_ first. A cursor of 0 only subscribes to new events.
+    pub fn subscribe(&self, cursor: u64) -> (Vec<Event>, UnboundedReceiver<Event>) {
+        let mut inner = self.inner.lock().unwrap();
+        let backlog = match cursor {
+            0 => Vec::new(),
+            cursor => inner
+                .history
+                .iter()
+                .filter(|event| event.get_cursor() > cursor)
+                .cloned()
+                .collect(),
+        };
+        let (sender, receiver) = mpsc::unbounded();
+        inner.subscribers.push(sender);
+        (backlog, receiver)
+    }
+}
+
+/// Builds an event of the given _kind_ about _repository_.
+pub fn event(kind: EventKind, repository: &str) -> Event {
+    let mut event = Event::new();
+    event.set_kind(kind);
+    event.set_repository(repository.to_owned());
+    event
+}
+
+fn unix_time() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,210 @@
+//! The standard `grpc.health.v1` service, so gRPC-aware load balancers,
+//! frontends included, only send calls to backends ready for them.
+//!
+//! A backend is serving once it synced the blobs of every peer, and while
+//! its storage is writable. Health checks don't need the cluster token,
+//! probes usually don't have it.
+//!
+//! Storage is probed at most every `PROBE_TTL` seconds, however often
+//! health is checked. A single thread looks for changes to send to
+//! `watch` subscribers, started by the first one and stopping once the
+//! last one is gone.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use futures::{future, stream, Future, Sink, Stream};
+use futures::sync::mpsc::{self, UnboundedSender};
+use grpcio;
+use grpc::health::{HealthCheckRequest, HealthCheckResponse,
+                   HealthCheckResponse_ServingStatus as ServingStatus};
+use grpc::health_grpc::Health;
+
+use metrics;
+use trace;
+
+/// The services health is reported for, "" being the server as a whole
+const SERVICES: &'static [&'static str] = &["", "lycaon.Backend", "lycaon.Peer"];
+
+/// Seconds between checks for `watch` subscribers
+const WATCH_INTERVAL: u64 = 1;
+
+/// Seconds the result of probing storage is reused for
+const PROBE_TTL: u64 = 5;
+
+/// Checks _dir_ can be written to by creating a file in it.
+pub fn writable(dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let probe = dir.join(".writable");
+    File::create(&probe)?.write_all(b"ok")?;
+    fs::remove_file(&probe)
+}
+
+/// The `watch` subscribers, and whether the thread sending them changes
+/// is running.
+struct Watchers {
+    senders: Vec<UnboundedSender<ServingStatus>>,
+    running: bool,
+}
+
+#[derive(Clone)]
+pub struct HealthService {
+    synced: Arc<AtomicBool>,
+    storage: PathBuf,
+    probed: Arc<Mutex<Option<(Instant, Result<(), String>)>>>,
+    watchers: Arc<Mutex<Watchers>>,
+}
+
+impl HealthService {
+    /// Serving once _synced_ is set, when the blobs of every peer were
+    /// synced, and while the data directory _storage_ is writable.
+    pub fn new(synced: Arc<AtomicBool>, storage: &Path) -> HealthService {
+        HealthService {
+            synced,
+            storage: storage.to_owned(),
+            probed: Arc::new(Mutex::new(None)),
+            watchers: Arc::new(Mutex::new(Watchers {
+                senders: Vec::new(),
+                running: false,
+            })),
+        }
+    }
+
+    /// Why this backend isn't ready for calls, if it isn't.
+    pub fn problem(&self) -> Option<String> {
+        if let Err(e) = self.writable() {
+            return Some(format!("storage isn't writable: {}", e));
+        }
+        if !self.synced.load(Ordering::SeqCst) {
+            return Some(String::from("not synced with every peer yet"));
+        }
+        None
+    }
+
+    /// Whether storage was writable when last probed, probing it again
+    /// once that's `PROBE_TTL` ago.
+    fn writable(&self) -> Result<(), String> {
+        let mut probed = self.probed.lock().unwrap();
+        match *probed {
+            Some((at, ref result)) if at.elapsed() < Duration::from_secs(PROBE_TTL) => {
+                return result.clone()
+            }
+            _ => {}
+        }
+        let result = writable(&self.storage).map_err(|e| e.to_string());
+        *probed = Some((Instant::now(), result.clone()));
+        result
+    }
+
+    /// Sends _sender_ the current status and every change of it, starting
+    /// the thread looking for changes unless it's running.
+    fn subscribe(&self, sender: UnboundedSender<ServingStatus>) {
+        let status = self.status();
+        let mut watchers = self.watchers.lock().unwrap();
+        if sender.unbounded_send(status).is_err() {
+            return;
+        }
+        watchers.senders.push(sender);
+        if watchers.running {
+            return;
+        }
+        let watched = self.clone();
+        let spawned = thread::Builder::new()
+            .name(String::from("health-watch"))
+            .spawn(move || watched.send_changes(status));
+        match spawned {
+            Ok(_) => watchers.running = true,
+            Err(e) => warn!("Failed to start health watch: {}", e),
+        }
+    }
+
+    /// Sends every status differing from _last_ to the subscribers, until
+    /// none is left.
+    fn send_changes(&self, mut last: ServingStatus) {
+        loop {
+            thread::sleep(Duration::from_secs(WATCH_INTERVAL));
+            let status = self.status();
+            let mut watchers = self.watchers.lock().unwrap();
+            if status != last {
+                info!("Backend health changed to {:?}", status);
+                watchers
+                    .senders
+                    .retain(|watcher| watcher.unbounded_send(status).is_ok());
+                last = status;
+            }
+            if watchers.senders.is_empty() {
+                watchers.running = false;
+                return;
+            }
+        }
+    }
+
+    fn status(&self) -> ServingStatus {
+        match self.problem() {
+            None => ServingStatus::SERVING,
+            Some(_) => ServingStatus::NOT_SERVING,
+        }
+    }
+}
+
+fn response(status: ServingStatus) -> HealthCheckResponse {
+    let mut resp = HealthCheckResponse::new();
+    resp.set_status(status);
+    resp
+}
+
+impl Health for HealthService {
+    fn check(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: HealthCheckRequest,
+        sink: grpcio::UnarySink<HealthCheckResponse>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "healthCheck");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["healthCheck"]).start_timer();
+        let f = match SERVICES.contains(&req.get_service()) {
+            true => sink.success(response(self.status())),
+            false => sink.fail(grpcio::RpcStatus::new(
+                grpcio::RpcStatusCode::NotFound,
+                Some(format!("unknown service {}", req.get_service())),
+            )),
+        };
+        ctx.spawn(f.map_err(move |e| warn!("failed to reply! {:?}", e)));
+    }
+
+    fn watch(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: HealthCheckRequest,
+        sink: grpcio::ServerStreamingSink<HealthCheckResponse>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "healthWatch");
+        let statuses: Box<Stream<Item = ServingStatus, Error = ()> + Send> =
+            match SERVICES.contains(&req.get_service()) {
+                true => {
+                    let (sender, receiver) = mpsc::unbounded();
+                    self.subscribe(sender);
+                    Box::new(receiver)
+                }
+                // no service comes up later, but the call stays open as the
+                // health checking protocol asks
+                false => Box::new(
+                    stream::once(Ok(ServingStatus::SERVICE_UNKNOWN))
+                        .chain(future::empty().into_stream()),
+                ),
+            };
+
+        let statuses = statuses
+            .map(|status| (response(status), grpcio::WriteFlags::default()))
+            .map_err(|()| grpcio::Error::RemoteStopped);
+        let f = sink.send_all(statuses)
+            .map(|_| ())
+            .map_err(|e| debug!("health watch ended: {:?}", e));
+        ctx.spawn(f);
+    }
+}
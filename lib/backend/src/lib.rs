@@ -3,6 +3,9 @@ extern crate futures;
 extern crate failure;
 extern crate uuid;
 extern crate protobuf;
+extern crate ring;
+extern crate serde;
+extern crate serde_json;
 
 extern crate lycaon_protobuf as grpc;
 #[macro_use]
@@ -10,38 +13,165 @@ extern crate serde_derive;
 #[macro_use(log, warn, info, debug)]
 extern crate log;
 extern crate env_logger;
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate prometheus;
 
+pub mod auth;
 pub mod config;
+pub mod digest;
+pub mod events;
+pub mod health;
+pub mod metrics;
+mod referrers;
+pub mod tls;
+pub mod trace;
 mod peer;
 mod backend;
 mod util;
 
-use std::thread;
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use failure::Error;
 use peer::PeerService;
-use backend::BackendService;
+pub use peer::probe;
+pub use util::storage_id;
+pub use backend::{BackendService, BlobStream};
 use futures::Future;
+use grpcio::{Environment, ServerBuilder};
 
-pub fn server(config: config::LycaonBackendConfig) {
-    use std::sync::Arc;
-    use grpcio::{Environment, ServerBuilder};
+/// Seconds the peers get on shutdown to take the deltas still queued
+const FLUSH_TIMEOUT: u64 = 5;
 
-    let listen = config.listen();
+/// The services of a backend node. Built before the server, so a
+/// frontend embedding the backend can call the same `BackendService`.
+pub struct Node {
+    backend: BackendService,
+    peers: PeerService,
+}
+
+impl Node {
+    /// Keeps its data in _data_. Fails if the TLS certificates can't be
+    /// loaded.
+    pub fn new(config: &config::LycaonBackendConfig, data: &Path) -> Result<Node, Error> {
+        let cluster_auth = auth::ClusterAuth::new(config.token());
+        let events = events::EventBus::new();
+        let peers = PeerService::new(
+            config.bootstrap.clone(),
+            config.tls().as_ref(),
+            cluster_auth.clone(),
+            events.clone(),
+            data,
+        )?;
+        let backend = BackendService::new(
+            cluster_auth,
+            events,
+            referrers::Referrers::open(data),
+            peers.peers(),
+            peers.deltas(),
+            health::HealthService::new(peers.synced(), data),
+            data,
+        );
+        Ok(Node { backend, peers })
+    }
+
+    /// A node without peers keeping its data in _data_, e.g. to test
+    /// against.
+    pub fn standalone(data: &Path) -> Node {
+        let peers = PeerService::empty(data);
+        let backend = BackendService::new(
+            auth::ClusterAuth::default(),
+            events::EventBus::new(),
+            referrers::Referrers::open(data),
+            peers.peers(),
+            peers.deltas(),
+            health::HealthService::new(peers.synced(), data),
+            data,
+        );
+        Node { backend, peers }
+    }
+
+    pub fn backend(&self) -> BackendService {
+        self.backend.clone()
+    }
+
+    /// Sends the peers what they haven't got yet and saves the open
+    /// upload sessions for the next start.
+    fn shutdown(&self) {
+        let left = self.peers.flush(Duration::from_secs(FLUSH_TIMEOUT));
+        if left > 0 {
+            warn!("{} deltas not sent to every peer", left);
+        }
+        if let Err(e) = self.backend.persist() {
+            warn!("Failed to save upload sessions: {}", e);
+        }
+    }
+}
 
+/// Tells `serve` to shut its server down.
+#[derive(Clone)]
+pub struct Stop {
+    stopped: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Stop {
+    pub fn new() -> Stop {
+        Stop { stopped: Arc::new((Mutex::new(false), Condvar::new())) }
+    }
+
+    pub fn stop(&self) {
+        let &(ref stopped, ref changed) = &*self.stopped;
+        *stopped.lock().unwrap() = true;
+        changed.notify_all();
+    }
+
+    fn wait(&self) {
+        let &(ref stopped, ref changed) = &*self.stopped;
+        let mut stopped = stopped.lock().unwrap();
+        while !*stopped {
+            stopped = changed.wait(stopped).unwrap();
+        }
+    }
+}
+
+pub fn server(config: config::LycaonBackendConfig, data: &Path) -> Result<(), Error> {
     debug!("Setting up backend server");
+    let node = Node::new(&config, data)?;
+    serve(config, node, Stop::new())
+}
+
+/// Serves _node_ until _stop_ is stopped, then shuts it down. Fails if
+/// the server can't be started.
+pub fn serve(config: config::LycaonBackendConfig, node: Node, stop: Stop) -> Result<(), Error> {
+    let listen = config.listen();
+
     let env = Arc::new(Environment::new(1));
-    let backend_service = grpc::backend_grpc::create_backend(BackendService::new());
-    let peer_service = grpc::peer_grpc::create_peer(PeerService::new(config.bootstrap));
-    let mut server = ServerBuilder::new(env)
+    let backend_service = grpc::backend_grpc::create_backend(node.backend.clone());
+    let peer_service = grpc::peer_grpc::create_peer(node.peers.clone());
+    let health_service = grpc::health_grpc::create_health(node.backend.health().clone());
+    let builder = ServerBuilder::new(env)
         .register_service(peer_service)
         .register_service(backend_service)
-        .bind(listen.host(), listen.port())
-        .build()
-        .unwrap();
+        .register_service(health_service);
+    let builder = match config.tls() {
+        Some(ref tls) => {
+            debug!("Enabling mutual TLS");
+            let credentials = tls::server_credentials(tls)?;
+            builder.bind_secure(listen.host(), listen.port(), credentials)
+        }
+        None => builder.bind(listen.host(), listen.port()),
+    };
+    let mut server = builder.build()?;
     server.start();
     for &(ref host, port) in server.bind_addrs() {
         info!("listening on {}:{}", host, port);
     }
-    thread::park();
+    stop.wait();
+    node.shutdown();
     let _ = server.shutdown().wait();
     warn!("GRPC Server shutdown!");
+    Ok(())
 }
@@ -0,0 +1,35 @@
+//! Prometheus collectors for the backend.
+//!
+//! These are registered in the default registry, the frontend's
+//! `/metrics` endpoint exposes them together with its own.
+
+use prometheus::{Gauge, Histogram, HistogramVec};
+
+lazy_static! {
+    /// Time spent handling each RPC, labelled with the method name.
+    pub static ref RPC_DURATION: HistogramVec = register_histogram_vec!(
+        "lycaon_grpc_request_duration_seconds",
+        "Time spent handling gRPC calls",
+        &["method"]
+    ).unwrap();
+
+    /// Number of uploads currently tracked by the backend.
+    pub static ref UPLOAD_SESSIONS: Gauge = register_gauge!(
+        "lycaon_upload_sessions",
+        "Number of active upload sessions"
+    ).unwrap();
+
+    pub static ref PEERS: Gauge = register_gauge!(
+        "lycaon_peers",
+        "Number of peers answering heartbeats"
+    ).unwrap();
+
+    /// From a change being made on a peer to this node receiving its
+    /// delta, including the time it spent queued while this node was
+    /// unreachable. Relies on the clocks of the nodes agreeing.
+    pub static ref REPLICATION_LAG: Histogram = register_histogram!(
+        "lycaon_peer_replication_lag_seconds",
+        "Time from a change on a peer to its CRDT delta arriving here",
+        vec![0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 60.0, 300.0]
+    ).unwrap();
+}
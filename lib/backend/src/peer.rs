@@ -1,43 +1,330 @@
 use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use futures::Future;
+use failure::{err_msg, Error};
+use futures::{stream, Future, Sink, Stream};
 use grpcio;
-use grpc::peer;
+use grpc::backend::EventKind;
+use grpc::peer::{self, DeltaType};
 use grpc::peer_grpc::{Peer, PeerClient};
-use grpcio::{ChannelBuilder, EnvBuilder};
+use grpcio::EnvBuilder;
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
 
+use auth::{self, ClusterAuth};
 use config;
+use digest::Digest;
+use events::{self, EventBus};
+use metrics;
+use tls;
+use trace;
+use util;
+
+/// Blobs are sent in messages of at most this size
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Seconds between rounds of sending queued deltas, and the deadline
+/// of each
+const DELTA_INTERVAL: u64 = 1;
+const DELTA_TIMEOUT: u64 = 5;
+
+/// Deltas kept while a peer can't be reached, the oldest are dropped
+/// beyond this
+const MAX_PENDING_DELTAS: usize = 10_000;
+
+/// All known peers and their addresses
+pub type Peers = Arc<Vec<(String, PeerClient)>>;
 
 /// Struct implementing callbacks for Peers
 ///
-/// _peers_: a Vector of all known clients, will be populated from
-/// dns records in the K8s cluster
+/// _peers_: a Vector of all known clients and their addresses, will be
+/// populated from dns records in the K8s cluster
+/// _data_: the data directory blobs are served to peers from
+/// _deltas_: changes to the blob set not yet sent to every peer
 #[derive(Clone)]
 pub struct PeerService {
     counter: Cell<u64>,
-    peers: Arc<Vec<PeerClient>>
+    peers: Peers,
+    auth: ClusterAuth,
+    synced: Arc<AtomicBool>,
+    data: PathBuf,
+    deltas: Deltas,
 }
 impl PeerService {
-    pub fn empty() -> PeerService {
+    pub fn empty(data: &Path) -> PeerService {
         PeerService {
             counter: Cell::new(0),
             peers: Arc::new(vec![]),
+            auth: ClusterAuth::default(),
+            synced: Arc::new(AtomicBool::new(true)),
+            data: data.to_owned(),
+            deltas: Deltas::none(),
         }
     }
 
-    pub fn new(service: config::Service) -> PeerService {
+    pub fn new(
+        service: config::Service,
+        tls: Option<&config::TlsConfig>,
+        auth: ClusterAuth,
+        events: EventBus,
+        data: &Path,
+    ) -> Result<PeerService, Error> {
 
         let env = Arc::new(EnvBuilder::new().build());
-        let ch = ChannelBuilder::new(env).connect(&service.address());
-        let client = PeerClient::new(ch);
+        let ch = tls::connect(env, &service.address(), tls)?;
+        let peers = Arc::new(vec![(service.address(), PeerClient::new(ch))]);
+        let synced = Arc::new(AtomicBool::new(false));
+        monitor(peers.clone(), auth.clone(), events, synced.clone(), data.to_owned());
+        let deltas = Deltas::new();
+        send_deltas(deltas.clone(), peers.clone(), auth.clone());
 
-        PeerService {
+        Ok(PeerService {
             counter: Cell::new(0),
-            peers: Arc::new(vec![client]),
+            peers,
+            auth,
+            synced,
+            data: data.to_owned(),
+            deltas,
+        })
+    }
+
+    pub fn peers(&self) -> Peers {
+        self.peers.clone()
+    }
+
+    pub fn deltas(&self) -> Deltas {
+        self.deltas.clone()
+    }
+
+    /// Sends the queued deltas, waiting up to _timeout_ for the peers to
+    /// take them. Returns how many are left.
+    pub fn flush(&self, timeout: Duration) -> usize {
+        let started = Instant::now();
+        loop {
+            let left = self.deltas.send(&self.peers, &self.auth);
+            if left == 0 || started.elapsed() >= timeout {
+                return left;
+            }
+            thread::sleep(Duration::from_millis(100));
         }
     }
+
+    /// Set once this node has the blobs of every peer, see `monitor`.
+    pub fn synced(&self) -> Arc<AtomicBool> {
+        self.synced.clone()
+    }
+}
+
+/// Sends a heartbeat to every configured peer, returning whether each
+/// answered within _timeout_.
+pub fn probe(config: &config::LycaonBackendConfig, timeout: Duration) -> Result<Vec<(String, bool)>, Error> {
+    let env = Arc::new(EnvBuilder::new().build());
+    let auth = ClusterAuth::new(config.token());
+    // the bootstrap service is the only peer configured so far
+    let address = config.bootstrap.address();
+    let ch = tls::connect(env, &address, config.tls().as_ref())?;
+    let up = PeerClient::new(ch)
+        .heartbeat_opt(&peer::Heartbeat::new(), auth.call_option().timeout(timeout))
+        .is_ok();
+    Ok(vec![(address, up)])
+}
+
+/// Sends heartbeats to every peer, publishing an event whenever one
+/// becomes reachable or stops responding.
+///
+/// Once a peer answers the blobs it has are synced into _data_, and
+/// _synced_ is set when that's done for every peer. So a peer gone for
+/// good has to be removed from the configuration.
+fn monitor(peers: Peers, auth: ClusterAuth, events: EventBus, synced: Arc<AtomicBool>, data: PathBuf) {
+    let spawned = thread::Builder::new()
+        .name(String::from("peer-monitor"))
+        .spawn(move || {
+            let mut alive = vec![false; peers.len()];
+            let mut synced_with = vec![false; peers.len()];
+            loop {
+                for (i, &(ref address, ref client)) in peers.iter().enumerate() {
+                    let up = client
+                        .heartbeat_opt(&peer::Heartbeat::new(), auth.call_option())
+                        .is_ok();
+                    if up != alive[i] {
+                        let kind = match up {
+                            true => EventKind::PEER_JOINED,
+                            false => EventKind::PEER_LEFT,
+                        };
+                        info!("Peer {} {}", address, if up { "joined" } else { "left" });
+                        let mut event = events::event(kind, "");
+                        event.set_peer(address.clone());
+                        events.publish(event);
+                        alive[i] = up;
+                    }
+                    if up && !synced_with[i] {
+                        match sync_with(client, &auth, &data) {
+                            Ok(fetched) => {
+                                info!("Synced with {}, fetched {} blobs", address, fetched);
+                                synced_with[i] = true;
+                            }
+                            Err(e) => warn!("Sync with {} failed, retrying: {}", address, e),
+                        }
+                    }
+                }
+                metrics::PEERS.set(alive.iter().filter(|up| **up).count() as f64);
+                if synced_with.iter().all(|synced| *synced) && !synced.swap(true, Ordering::SeqCst) {
+                    info!("Synced with every peer, ready for calls");
+                }
+                thread::sleep(Duration::from_secs(5));
+            }
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start peer monitor: {}", e);
+    }
+}
+
+/// Fetches the blobs _client_ has and _data_ hasn't, returns how many.
+fn sync_with(client: &PeerClient, auth: &ClusterAuth, data: &Path) -> Result<usize, Error> {
+    let elements = client.full_sync_opt(&peer::ORSetFullSync::new(), auth.call_option())?;
+    let mut fetched = 0;
+    for delta in elements.wait() {
+        let digest: Digest = delta?.get_element().parse()?;
+        if !util::layer_path(data, &digest).is_file() {
+            fetch(client, auth, data, &digest)?;
+            fetched += 1;
+        }
+    }
+    Ok(fetched)
+}
+
+/// Stores the blob _digest_ of _client_ in _data_ once it's verified.
+fn fetch(client: &PeerClient, auth: &ClusterAuth, data: &Path, digest: &Digest) -> Result<(), Error> {
+    let mut request = peer::BlobRequest::new();
+    request.set_digest(digest.to_string());
+    let chunks = client.fetch_blob_opt(&request, auth.call_option())?;
+
+    let path = util::layer_path(data, digest);
+    let tmp = path.with_file_name(format!(".{}.sync", digest));
+    let mut hasher = digest.hasher();
+    {
+        let mut file = File::create(&tmp)?;
+        for chunk in chunks.wait() {
+            let chunk = chunk?;
+            hasher.update(chunk.get_data());
+            file.write_all(chunk.get_data())?;
+        }
+    }
+    if hasher.finish() != *digest {
+        let _ = fs::remove_file(&tmp);
+        return Err(err_msg(format!("{} doesn't match its digest", digest)));
+    }
+    fs::rename(&tmp, &path).map_err(|e| e.into())
+}
+
+struct Outbox {
+    counter: u64,
+    pending: VecDeque<peer::ORSetDelta>,
+}
+
+/// Changes to the set of stored blobs, waiting to be sent to the peers.
+///
+/// Deltas are queued as blobs are committed and sent in the background.
+/// One a peer didn't take is sent to every peer again later, which is
+/// safe as adding an element twice leaves the set as it is.
+#[derive(Clone)]
+pub struct Deltas {
+    /// Names this node in the dots of its deltas, made up on every start
+    /// so dots never repeat
+    actor: String,
+    outbox: Option<Arc<Mutex<Outbox>>>,
+}
+
+impl Deltas {
+    fn new() -> Deltas {
+        Deltas {
+            actor: Uuid::new_v4().to_string(),
+            outbox: Some(Arc::new(Mutex::new(Outbox {
+                counter: 0,
+                pending: VecDeque::new(),
+            }))),
+        }
+    }
+
+    /// For a node without peers, dropping every delta.
+    fn none() -> Deltas {
+        Deltas {
+            actor: String::new(),
+            outbox: None,
+        }
+    }
+
+    /// Queues the addition of the blob _digest_.
+    pub fn add(&self, digest: &str) {
+        let outbox = match self.outbox {
+            Some(ref outbox) => outbox,
+            None => return,
+        };
+        let mut outbox = outbox.lock().unwrap();
+        outbox.counter += 1;
+        let mut dot = peer::Dot::new();
+        dot.set_actor(self.actor.clone());
+        dot.set_counter(outbox.counter);
+        let mut delta = peer::ORSetDelta::new();
+        delta.set_deltatype(DeltaType::ADD);
+        delta.set_element(digest.to_owned());
+        delta.mut_dots().push(dot);
+        delta.set_produced(unix_millis());
+        if outbox.pending.len() >= MAX_PENDING_DELTAS {
+            warn!("Too many deltas pending, dropping the oldest");
+            outbox.pending.pop_front();
+        }
+        outbox.pending.push_back(delta);
+    }
+
+    /// Sends the queued deltas to every peer in order, up to the first
+    /// one a peer didn't take. Returns how many are left.
+    fn send(&self, peers: &Peers, auth: &ClusterAuth) -> usize {
+        let outbox = match self.outbox {
+            Some(ref outbox) => outbox,
+            None => return 0,
+        };
+        loop {
+            let delta = match outbox.lock().unwrap().pending.front() {
+                Some(delta) => delta.clone(),
+                None => return 0,
+            };
+            // not holding the lock while waiting for the peers
+            let options = || auth.call_option().timeout(Duration::from_secs(DELTA_TIMEOUT));
+            let sent = peers
+                .iter()
+                .all(|&(_, ref client)| client.delta_sync_opt(&delta, options()).is_ok());
+            let mut outbox = outbox.lock().unwrap();
+            if !sent {
+                return outbox.pending.len();
+            }
+            // unless another sender or the limit got rid of it meanwhile
+            if outbox.pending.front() == Some(&delta) {
+                outbox.pending.pop_front();
+            }
+        }
+    }
+}
+
+/// Keeps sending _deltas_ to _peers_ in the background.
+fn send_deltas(deltas: Deltas, peers: Peers, auth: ClusterAuth) {
+    let spawned = thread::Builder::new()
+        .name(String::from("peer-deltas"))
+        .spawn(move || loop {
+            deltas.send(&peers, &auth);
+            thread::sleep(Duration::from_secs(DELTA_INTERVAL));
+        });
+    if let Err(e) = spawned {
+        warn!("Failed to start sending deltas: {}", e);
+    }
 }
 
 impl Peer for PeerService {
@@ -47,6 +334,11 @@ impl Peer for PeerService {
         req: peer::Heartbeat,
         sink: grpcio::UnarySink<peer::Heartbeat>,
     ) {
+        let _span = trace::Span::rpc(&ctx, "heartbeat");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["heartbeat"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
         {
             for peer in self.peers.iter() {
                 print!("Hello there!");
@@ -65,7 +357,17 @@ impl Peer for PeerService {
         req: peer::ORSetDelta,
         sink: grpcio::UnarySink<peer::ORSetDeltaReply>,
     ) {
+        let _span = trace::Span::rpc(&ctx, "deltaSync");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["deltaSync"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject(ctx, sink);
+        }
         self.counter.set(self.counter.get() + 1);
+        // deltas of peers predating the timestamp don't say
+        if req.get_produced() > 0 {
+            let lag = (unix_millis() - req.get_produced()).max(0);
+            metrics::REPLICATION_LAG.observe(lag as f64 / 1000.0);
+        }
         debug!("Counter: {:?}", self.counter);
         let mut resp = peer::ORSetDeltaReply::new();
         let deltatype = req.get_deltatype();
@@ -76,4 +378,106 @@ impl Peer for PeerService {
         });
         ctx.spawn(f);
     }
+
+    fn full_sync(
+        &self,
+        ctx: grpcio::RpcContext,
+        _req: peer::ORSetFullSync,
+        sink: grpcio::ServerStreamingSink<peer::ORSetDelta>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "fullSync");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["fullSync"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject_stream(ctx, sink);
+        }
+        let stored = match util::stored(&self.data) {
+            Ok(stored) => stored,
+            Err(e) => {
+                let status = grpcio::RpcStatus::new(grpcio::RpcStatusCode::Internal, Some(e.to_string()));
+                let f = sink.fail(status)
+                    .map_err(move |e| warn!("failed to reply! {:?}", e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let elements = stored.into_iter().map(|digest| {
+            let mut delta = peer::ORSetDelta::new();
+            delta.set_deltatype(DeltaType::ADD);
+            delta.set_element(digest.to_string());
+            (delta, grpcio::WriteFlags::default())
+        });
+        let f = sink.send_all(stream::iter_ok::<_, grpcio::Error>(elements))
+            .map(|_| ())
+            .map_err(|e| warn!("fullSync failed: {:?}", e));
+        ctx.spawn(f);
+    }
+
+    fn fetch_blob(
+        &self,
+        ctx: grpcio::RpcContext,
+        req: peer::BlobRequest,
+        sink: grpcio::ServerStreamingSink<peer::BlobData>,
+    ) {
+        let _span = trace::Span::rpc(&ctx, "fetchBlob");
+        let _timer = metrics::RPC_DURATION.with_label_values(&["fetchBlob"]).start_timer();
+        if !self.auth.verify(&ctx) {
+            return auth::reject_stream(ctx, sink);
+        }
+        let file = req.get_digest()
+            .parse::<Digest>()
+            .map_err(|e| e.to_string())
+            .and_then(|digest| {
+                File::open(util::layer_path(&self.data, &digest)).map_err(|e| e.to_string())
+            });
+        let file = match file {
+            Ok(file) => file,
+            Err(e) => {
+                debug!("Can't provide blob {}: {}", req.get_digest(), e);
+                let status = grpcio::RpcStatus::new(grpcio::RpcStatusCode::NotFound, Some(e));
+                let f = sink.fail(status)
+                    .map_err(move |e| warn!("failed to reply! {:?}", e));
+                return ctx.spawn(f);
+            }
+        };
+
+        let chunks = stream::iter_result(Chunks(file)).map(|data| {
+            let mut message = peer::BlobData::new();
+            message.set_data(data);
+            (message, grpcio::WriteFlags::default())
+        });
+        let f = sink.send_all(chunks)
+            .map(|_| ())
+            .map_err(|e| warn!("fetchBlob failed: {:?}", e));
+        ctx.spawn(f);
+    }
+}
+
+/// Reads a blob in pieces small enough for a single message.
+struct Chunks(File);
+
+impl Iterator for Chunks {
+    type Item = Result<Vec<u8>, grpcio::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buffer = vec![0; CHUNK_SIZE];
+        match self.0.read(&mut buffer) {
+            Ok(0) => None,
+            Ok(read) => {
+                buffer.truncate(read);
+                Some(Ok(buffer))
+            }
+            Err(e) => Some(Err(grpcio::Error::RpcFailure(grpcio::RpcStatus::new(
+                grpcio::RpcStatusCode::Internal,
+                Some(e.to_string()),
+            )))),
+        }
+    }
+}
+
+fn unix_millis() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64 * 1000 + d.subsec_nanos() as i64 / 1_000_000)
+        .unwrap_or(0)
 }
@@ -0,0 +1,135 @@
+//! Links from manifests to the manifests referring to them through
+//! their OCI `subject` field, such as signatures and SBOMs.
+//!
+//! The links are kept in `referrers.json` in the data directory so
+//! they survive a restart; the file is rewritten whenever they change.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use failure::Error;
+use grpc::backend::Referrer;
+use serde_json;
+
+use digest::Digest;
+
+const REFERRERS_FILE: &'static str = "referrers.json";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Link {
+    repository: String,
+    subject: String,
+    digest: String,
+    media_type: String,
+    artifact_type: String,
+    size: u64,
+    annotations: String,
+}
+
+impl<'a> From<&'a Referrer> for Link {
+    fn from(referrer: &Referrer) -> Link {
+        Link {
+            repository: referrer.get_repository().to_owned(),
+            subject: referrer.get_subject().to_owned(),
+            digest: referrer.get_digest().to_owned(),
+            media_type: referrer.get_media_type().to_owned(),
+            artifact_type: referrer.get_artifact_type().to_owned(),
+            size: referrer.get_size(),
+            annotations: referrer.get_annotations().to_owned(),
+        }
+    }
+}
+
+impl<'a> From<&'a Link> for Referrer {
+    fn from(link: &Link) -> Referrer {
+        let mut referrer = Referrer::new();
+        referrer.set_repository(link.repository.clone());
+        referrer.set_subject(link.subject.clone());
+        referrer.set_digest(link.digest.clone());
+        referrer.set_media_type(link.media_type.clone());
+        referrer.set_artifact_type(link.artifact_type.clone());
+        referrer.set_size(link.size);
+        referrer.set_annotations(link.annotations.clone());
+        referrer
+    }
+}
+
+#[derive(Clone)]
+pub struct Referrers {
+    path: PathBuf,
+    links: Arc<Mutex<Vec<Link>>>,
+}
+
+impl Referrers {
+    /// Loads the links stored in _data_, starting empty if there are
+    /// none.
+    pub fn open(data: &Path) -> Referrers {
+        let path = data.join(REFERRERS_FILE);
+        let mut contents = Vec::new();
+        let read = File::open(&path).and_then(|mut file| file.read_to_end(&mut contents));
+        let links = match read {
+            Ok(_) => serde_json::from_slice(&contents).unwrap_or_else(|e| {
+                warn!("Ignoring unreadable {:?}: {}", path, e);
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Referrers {
+            path,
+            links: Arc::new(Mutex::new(links)),
+        }
+    }
+
+    /// Records _referrer_, replacing an earlier push of the same manifest.
+    pub fn add(&self, referrer: &Referrer) -> Result<(), Error> {
+        referrer.get_subject().parse::<Digest>()?;
+        referrer.get_digest().parse::<Digest>()?;
+        let mut links = self.links.lock().unwrap();
+        links.retain(|link| {
+            !(link.repository == referrer.get_repository() && link.digest == referrer.get_digest())
+        });
+        links.push(Link::from(referrer));
+        self.save(&links)
+    }
+
+    /// The referrers of _subject_, only of _artifact_type_ unless empty.
+    pub fn list(&self, repository: &str, subject: &str, artifact_type: &str) -> Vec<Referrer> {
+        self.links
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|link| link.repository == repository && link.subject == subject)
+            .filter(|link| artifact_type.is_empty() || link.artifact_type == artifact_type)
+            .map(Referrer::from)
+            .collect()
+    }
+
+    /// Forgets the deleted manifest _digest_ and returns its referrers,
+    /// which are forgotten as links but still stored as manifests.
+    pub fn remove(&self, repository: &str, digest: &str) -> Result<Vec<Referrer>, Error> {
+        let mut links = self.links.lock().unwrap();
+        let (removed, kept): (Vec<Link>, Vec<Link>) = links
+            .drain(..)
+            .partition(|link| link.repository == repository && link.subject == digest);
+        *links = kept;
+        links.retain(|link| !(link.repository == repository && link.digest == digest));
+        self.save(&links)?;
+        Ok(removed.iter().map(Referrer::from).collect())
+    }
+
+    fn save(&self, links: &[Link]) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&serde_json::to_vec(links)?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &self.path).map_err(|e| e.into())
+    }
+}
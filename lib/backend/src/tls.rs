@@ -0,0 +1,48 @@
+//! Helpers for setting up (mutually) authenticated gRPC channels.
+//!
+//! Every node of the cluster presents a certificate signed by the
+//! shared CA, and only accepts peers which do the same.
+
+use std::fs::File;
+use std::io::Read;
+use std::sync::Arc;
+
+use failure::Error;
+use grpcio::{Channel, ChannelBuilder, ChannelCredentialsBuilder, Environment,
+             ServerCredentials, ServerCredentialsBuilder};
+
+use config::TlsConfig;
+
+fn read_pem(path: &str) -> Result<Vec<u8>, Error> {
+    let mut contents = Vec::new();
+    File::open(path)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// Credentials for the gRPC server, requiring clients to present a
+/// certificate signed by the cluster CA.
+pub fn server_credentials(tls: &TlsConfig) -> Result<ServerCredentials, Error> {
+    Ok(ServerCredentialsBuilder::new()
+        .root_cert(read_pem(&tls.ca)?, true)
+        .add_cert(read_pem(&tls.cert)?, read_pem(&tls.key)?)
+        .build())
+}
+
+/// Opens a channel to _address_, using mTLS if _tls_ is configured.
+pub fn connect(env: Arc<Environment>, address: &str, tls: Option<&TlsConfig>) -> Result<Channel, Error> {
+    let builder = ChannelBuilder::new(env);
+    match tls {
+        Some(tls) => {
+            let credentials = ChannelCredentialsBuilder::new()
+                .root_cert(read_pem(&tls.ca)?)
+                .cert(read_pem(&tls.cert)?, read_pem(&tls.key)?)
+                .build();
+            let builder = match tls.server_name {
+                Some(ref name) => builder.override_ssl_target(name.clone()),
+                None => builder,
+            };
+            Ok(builder.secure_connect(address, credentials))
+        }
+        None => Ok(builder.connect(address)),
+    }
+}
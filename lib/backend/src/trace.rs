@@ -0,0 +1,258 @@
+//! Request IDs and tracing spans.
+//!
+//! Every HTTP request gets a request ID and a trace, both of which are
+//! forwarded on gRPC calls as metadata (`x-request-id` and a W3C
+//! `traceparent`). The span being handled on the current thread is
+//! kept in a thread-local so log lines and outgoing calls can pick it
+//! up.
+//!
+//! Finished spans are written as JSON lines using the OpenTelemetry
+//! (OTLP/JSON) field names, either to the file set with `export_to` or
+//! to the debug log.
+
+use std::cell::RefCell;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::mem;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use grpcio::{MetadataBuilder, RpcContext};
+use serde_json;
+use uuid::Uuid;
+
+pub const REQUEST_ID_HEADER: &'static str = "x-request-id";
+pub const TRACEPARENT_HEADER: &'static str = "traceparent";
+
+lazy_static! {
+    static ref EXPORT: Mutex<Option<File>> = Mutex::new(None);
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Context>> = RefCell::new(None);
+}
+
+/// Identifies the span being handled.
+#[derive(Clone, Debug)]
+pub struct Context {
+    pub request_id: String,
+    pub trace_id: String,
+    pub span_id: String,
+}
+
+impl Context {
+    /// The `traceparent` value for calls made as children of this span.
+    pub fn traceparent(&self) -> String {
+        format!("00-{}-{}-01", self.trace_id, self.span_id)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SpanKind {
+    Server = 2,
+    Client = 3,
+}
+
+/// A unit of work within a trace, exported and popped off the
+/// thread-local context when dropped.
+pub struct Span {
+    name: String,
+    kind: SpanKind,
+    context: Context,
+    parent_span_id: Option<String>,
+    start: SystemTime,
+    attributes: Vec<(String, String)>,
+    previous: Option<Context>,
+}
+
+impl Span {
+    /// Starts a span for an incoming request, continuing the caller's
+    /// trace if a valid _traceparent_ was sent.
+    pub fn server(name: &str, request_id: Option<String>, traceparent: Option<&str>) -> Span {
+        let parent = traceparent.and_then(parse_traceparent);
+        let trace_id = parent
+            .as_ref()
+            .map(|&(ref trace_id, _)| trace_id.clone())
+            .unwrap_or_else(new_trace_id);
+        let context = Context {
+            request_id: request_id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            trace_id,
+            span_id: new_span_id(),
+        };
+        Span::start(name, SpanKind::Server, context, parent.map(|(_, span_id)| span_id))
+    }
+
+    /// Starts a span for an incoming RPC from the metadata sent along.
+    pub fn rpc(ctx: &RpcContext, name: &str) -> Span {
+        let mut request_id = None;
+        let mut traceparent = None;
+        for (key, value) in ctx.request_headers().iter() {
+            let value = String::from_utf8_lossy(value).into_owned();
+            match key {
+                REQUEST_ID_HEADER => request_id = Some(value),
+                TRACEPARENT_HEADER => traceparent = Some(value),
+                _ => {}
+            }
+        }
+        Span::server(name, request_id, traceparent.as_ref().map(|t| t.as_str()))
+    }
+
+    /// Starts a child of the current span, or a new trace if there is none.
+    pub fn child(name: &str, kind: SpanKind) -> Span {
+        match current() {
+            Some(parent) => {
+                let context = Context {
+                    span_id: new_span_id(),
+                    ..parent.clone()
+                };
+                Span::start(name, kind, context, Some(parent.span_id))
+            }
+            None => Span::server(name, None, None),
+        }
+    }
+
+    fn start(name: &str, kind: SpanKind, context: Context, parent_span_id: Option<String>) -> Span {
+        let previous = CURRENT.with(|current| {
+            mem::replace(&mut *current.borrow_mut(), Some(context.clone()))
+        });
+        Span {
+            name: name.to_owned(),
+            kind,
+            context,
+            parent_span_id,
+            start: SystemTime::now(),
+            attributes: Vec::new(),
+            previous,
+        }
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn set_attribute(&mut self, key: &str, value: &str) {
+        self.attributes.push((key.to_owned(), value.to_owned()));
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+        export(self, SystemTime::now());
+    }
+}
+
+/// The context of the span currently being handled on this thread.
+pub fn current() -> Option<Context> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+pub fn current_request_id() -> Option<String> {
+    CURRENT.with(|current| current.borrow().as_ref().map(|c| c.request_id.clone()))
+}
+
+/// Adds the current request ID and trace context to outgoing metadata.
+pub fn inject(headers: &mut MetadataBuilder) {
+    if let Some(context) = current() {
+        let _ = headers.add_str(REQUEST_ID_HEADER, &context.request_id);
+        let _ = headers.add_str(TRACEPARENT_HEADER, &context.traceparent());
+    }
+}
+
+/// Appends finished spans to the file at _path_.
+pub fn export_to(path: &str) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *EXPORT.lock().unwrap() = Some(file);
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SpanRecord<'a> {
+    trace_id: &'a str,
+    span_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_span_id: Option<&'a str>,
+    name: &'a str,
+    kind: u8,
+    start_time_unix_nano: String,
+    end_time_unix_nano: String,
+    attributes: Vec<Attribute<'a>>,
+}
+
+#[derive(Serialize)]
+struct Attribute<'a> {
+    key: &'a str,
+    value: AttributeValue<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AttributeValue<'a> {
+    string_value: &'a str,
+}
+
+fn export(span: &Span, end: SystemTime) {
+    let request_id = Attribute {
+        key: "lycaon.request_id",
+        value: AttributeValue { string_value: &span.context.request_id },
+    };
+    let record = SpanRecord {
+        trace_id: &span.context.trace_id,
+        span_id: &span.context.span_id,
+        parent_span_id: span.parent_span_id.as_ref().map(|id| id.as_str()),
+        name: &span.name,
+        kind: span.kind as u8,
+        start_time_unix_nano: unix_nanos(span.start).to_string(),
+        end_time_unix_nano: unix_nanos(end).to_string(),
+        attributes: Some(request_id)
+            .into_iter()
+            .chain(span.attributes.iter().map(|&(ref key, ref value)| Attribute {
+                key: key.as_str(),
+                value: AttributeValue { string_value: value.as_str() },
+            }))
+            .collect(),
+    };
+    let line = match serde_json::to_string(&record) {
+        Ok(line) => line,
+        Err(e) => return warn!("Failed to serialize span: {}", e),
+    };
+
+    match *EXPORT.lock().unwrap() {
+        Some(ref mut file) => {
+            if let Err(e) = writeln!(file, "{}", line) {
+                warn!("Failed to export span: {}", e);
+            }
+        }
+        None => debug!(target: "lycaon::trace", "{}", line),
+    }
+}
+
+/// Parses `00-<trace-id>-<parent-id>-<flags>` into the trace and parent IDs.
+fn parse_traceparent(value: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    let is_hex = |s: &str| s.chars().all(|c| c.is_digit(16));
+    if parts.len() != 4 || parts[0].len() != 2 || parts[3].len() != 2 {
+        return None;
+    }
+    let (trace_id, span_id) = (parts[1], parts[2]);
+    match trace_id.len() == 32 && span_id.len() == 16 && is_hex(trace_id) && is_hex(span_id) {
+        true => Some((trace_id.to_lowercase(), span_id.to_lowercase())),
+        false => None,
+    }
+}
+
+fn new_trace_id() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+fn new_span_id() -> String {
+    new_trace_id()[..16].to_owned()
+}
+
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() * 1_000_000_000 + d.subsec_nanos() as u64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,81 @@
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use futures::sync::oneshot;
+use uuid::Uuid;
+
+use digest::Digest;
+
+/// Resolves once _after_ elapsed, to race other futures against. A
+/// thread sleeps in the meantime, so keep these for calls that are rare.
+pub fn timeout(after: Duration) -> oneshot::Receiver<()> {
+    let (fired, timeout) = oneshot::channel();
+    thread::spawn(move || {
+        thread::sleep(after);
+        let _ = fired.send(());
+    });
+    timeout
+}
+
+/// The layer store of the data directory _data_.
+pub fn layers(data: &Path) -> PathBuf {
+    data.join("layers")
+}
+
+/// Where the blob _digest_ is stored in the data directory _data_.
+pub fn layer_path(data: &Path, digest: &Digest) -> PathBuf {
+    layers(data).join(digest.to_string())
+}
+
+/// The blobs stored in _data_. Anything else in the layer store, e.g.
+/// a temporary file, is left out.
+pub fn stored(data: &Path) -> io::Result<Vec<Digest>> {
+    let mut digests = Vec::new();
+    for entry in fs::read_dir(layers(data))? {
+        let name = entry?.file_name();
+        if let Some(Ok(digest)) = name.to_str().map(|name| name.parse::<Digest>()) {
+            digests.push(digest);
+        }
+    }
+    Ok(digests)
+}
+
+/// The link making the blob _digest_ part of _repository_ in _data_.
+pub fn link_path(data: &Path, repository: &str, digest: &Digest) -> PathBuf {
+    data.join("repositories")
+        .join(repository)
+        .join("_layers")
+        .join(digest.to_string())
+}
+
+/// The scratch file the upload _uuid_ is streamed to in _data_.
+pub fn scratch_path(data: &Path, uuid: &str) -> PathBuf {
+    data.join("scratch").join(uuid)
+}
+
+/// Names the data directory, created by the first node storing in it
+const STORAGE_ID_FILE: &'static str = "storage-id";
+
+/// The id of the data directory _data_, the same for every node sharing
+/// it. Made up and saved by the first one asking.
+pub fn storage_id(data: &Path) -> io::Result<String> {
+    let path = data.join(STORAGE_ID_FILE);
+    if !path.is_file() {
+        let tmp = data.join(format!(".{}.{}", STORAGE_ID_FILE, Uuid::new_v4()));
+        File::create(&tmp)?.write_all(Uuid::new_v4().to_string().as_bytes())?;
+        // fails if another node got there first, then theirs counts
+        let linked = fs::hard_link(&tmp, &path);
+        fs::remove_file(&tmp)?;
+        if let Err(e) = linked {
+            if e.kind() != ErrorKind::AlreadyExists {
+                return Err(e);
+            }
+        }
+    }
+    let mut id = String::new();
+    File::open(&path)?.read_to_string(&mut id)?;
+    Ok(id)
+}
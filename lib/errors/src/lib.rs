@@ -7,9 +7,12 @@ extern crate rocket;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
+
+use std::io::Cursor;
 
 use failure::Error;
-use rocket::http::{Header, Status};
+use rocket::http::{ContentType, Header, Status};
 use rocket::response::{Responder, Response};
 use rocket::request::Request;
 
@@ -29,6 +32,7 @@ pub enum Server {
     #[fail(display = "ConfigError: {}", _0)] ConfigError(Error),
     #[fail(display = "GenericError: {}", _0)] GenericError(String),
     #[fail(display = "Mismatched Digests")] DigestMismatch,
+    #[fail(display = "TokenError: {}", _0)] TokenError(String),
 
     #[fail(display = "Mismatched Digests")] TestError(()),
 }
@@ -40,6 +44,11 @@ pub enum Client {
     #[fail(display = "blob unknown to registry")] BLOB_UNKNOWN,
     #[fail(display = "blob upload invalid")] BLOB_UPLOAD_INVALID,
     #[fail(display = "IMPLEMENT ME")] BLOB_UPLOAD_UNKNOWN,
+    /// A chunk that doesn't continue the upload, sent with the upload's
+    /// error code but as 416
+    #[serde(rename = "BLOB_UPLOAD_INVALID")]
+    #[fail(display = "blob upload range invalid")]
+    RANGE_INVALID,
     #[fail(display = "IMPLEMENT ME")] DIGEST_INVALID,
     #[fail(display = "IMPLEMENT ME")] MANIFEST_BLOB_UNKNOWN,
     #[fail(display = "IMPLEMENT ME")] MANIFEST_INVALID,
@@ -52,16 +61,56 @@ pub enum Client {
     #[fail(display = "IMPLEMENT ME")] UNAUTHORIZED,
     #[fail(display = "IMPLEMENT ME")] DENIED,
     #[fail(display = "IMPLEMENT ME")] UNSUPPORTED,
+    #[fail(display = "service unavailable")] UNAVAILABLE,
+    #[fail(display = "unknown error")] UNKNOWN,
 }
 
 impl Trait for Client {}
 
+/// A single entry of the error body returned to clients
+#[derive(Serialize)]
+struct ErrorBody {
+    code: Client,
+    message: Message,
+    detail: Detail,
+}
+
+#[derive(Serialize)]
+struct ErrorList {
+    errors: Vec<ErrorBody>,
+}
+
 impl Client {
+    /// The HTTP status code the error is returned with
+    pub fn status(&self) -> Status {
+        match *self {
+            Client::BLOB_UNKNOWN => Status::NotFound,
+            Client::BLOB_UPLOAD_INVALID => Status::BadRequest,
+            Client::BLOB_UPLOAD_UNKNOWN => Status::NotFound,
+            Client::RANGE_INVALID => Status::RangeNotSatisfiable,
+            Client::DIGEST_INVALID => Status::BadRequest,
+            Client::MANIFEST_BLOB_UNKNOWN => Status::BadRequest,
+            Client::MANIFEST_INVALID => Status::BadRequest,
+            Client::MANIFEST_UNKNOWN => Status::NotFound,
+            Client::MANIFEST_UNVERIFIED => Status::BadRequest,
+            Client::NAME_INVALID => Status::BadRequest,
+            Client::NAME_UNKNOWN => Status::NotFound,
+            Client::SIZE_INVALID => Status::BadRequest,
+            Client::TAG_INVALID => Status::BadRequest,
+            Client::UNAUTHORIZED => Status::Unauthorized,
+            Client::DENIED => Status::Forbidden,
+            Client::UNSUPPORTED => Status::MethodNotAllowed,
+            Client::UNAVAILABLE => Status::ServiceUnavailable,
+            Client::UNKNOWN => Status::InternalServerError,
+        }
+    }
+
     fn message(self) -> Message {
         match self {
             Client::BLOB_UNKNOWN => "blob unknown to registry",
             Client::BLOB_UPLOAD_INVALID => "blob upload invalid",
             Client::BLOB_UPLOAD_UNKNOWN => "blob upload unknown to registry",
+            Client::RANGE_INVALID => "blob upload invalid",
             Client::DIGEST_INVALID => "provided digest did not match uploaded content",
             Client::MANIFEST_BLOB_UNKNOWN => "blob unknown to registry",
             Client::MANIFEST_INVALID => "manifest invalid",
@@ -74,6 +123,8 @@ impl Client {
             Client::UNAUTHORIZED => "authentication required",
             Client::DENIED => "requested access to the resource is denied",
             Client::UNSUPPORTED => "The operation is unsupported",
+            Client::UNAVAILABLE => "service unavailable",
+            Client::UNKNOWN => "unknown error",
         }
     }
 
@@ -88,6 +139,9 @@ impl Client {
             Client::BLOB_UPLOAD_UNKNOWN => {
                 "If a blob upload has been cancelled or was never started, this error code may be returned"
             }
+            Client::RANGE_INVALID => {
+                "The chunk doesn't continue the upload where it is, e.g. because another chunk of it is still being received"
+            }
             Client::DIGEST_INVALID => {
                 "When a blob is uploaded, the registry will check that the content matches the digest provided by the client. The error may include a detail structure with the key \"digest\" including the invalid digest string. This error may also be returned when a minfest includes an invalid layer digest."
             }
@@ -124,14 +178,34 @@ impl Client {
             Client::UNSUPPORTED => {
                 "The operation was unsupported due to a missing implementation or invalid set of parameters."
             }
+            Client::UNAVAILABLE => {
+                "The registry's storage backend could not be reached in time. The request may be retried later."
+            }
+            Client::UNKNOWN => {
+                "The registry failed to handle the request. The details were logged."
+            }
         }
     }
 }
 
 impl<'r> Responder<'r> for Client {
     fn respond_to(self, _req: &Request) -> Result<Response<'r>, Status> {
-        // use response::json_response;
-        // json_response(self)
-        Response::build().status(Status::raw(418)).ok()
+        let status = self.status();
+        let body = ErrorList {
+            errors: vec![
+                ErrorBody {
+                    code: self.clone(),
+                    message: self.clone().message(),
+                    detail: self.detail(),
+                },
+            ],
+        };
+        let body = serde_json::to_string(&body).map_err(|_| Status::InternalServerError)?;
+
+        Response::build()
+            .status(status)
+            .header(ContentType::JSON)
+            .sized_body(Cursor::new(body))
+            .ok()
     }
 }
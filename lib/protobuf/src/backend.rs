@@ -25,7 +25,6 @@ use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 pub struct Layer {
     // message fields
     pub name: ::std::string::String,
-    pub repo: ::std::string::String,
     pub digest: ::std::string::String,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
@@ -84,40 +83,6 @@ impl Layer {
         &mut self.name
     }
 
-    // string repo = 2;
-
-    pub fn clear_repo(&mut self) {
-        self.repo.clear();
-    }
-
-    // Param is passed by value, moved
-    pub fn set_repo(&mut self, v: ::std::string::String) {
-        self.repo = v;
-    }
-
-    // Mutable pointer to the field.
-    // If field is not initialized, it is initialized with default value first.
-    pub fn mut_repo(&mut self) -> &mut ::std::string::String {
-        &mut self.repo
-    }
-
-    // Take field
-    pub fn take_repo(&mut self) -> ::std::string::String {
-        ::std::mem::replace(&mut self.repo, ::std::string::String::new())
-    }
-
-    pub fn get_repo(&self) -> &str {
-        &self.repo
-    }
-
-    fn get_repo_for_reflect(&self) -> &::std::string::String {
-        &self.repo
-    }
-
-    fn mut_repo_for_reflect(&mut self) -> &mut ::std::string::String {
-        &mut self.repo
-    }
-
     // string digest = 3;
 
     pub fn clear_digest(&mut self) {
@@ -165,9 +130,6 @@ impl ::protobuf::Message for Layer {
                 1 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
                 },
-                2 => {
-                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.repo)?;
-                },
                 3 => {
                     ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
                 },
@@ -186,9 +148,6 @@ impl ::protobuf::Message for Layer {
         if !self.name.is_empty() {
             my_size += ::protobuf::rt::string_size(1, &self.name);
         }
-        if !self.repo.is_empty() {
-            my_size += ::protobuf::rt::string_size(2, &self.repo);
-        }
         if !self.digest.is_empty() {
             my_size += ::protobuf::rt::string_size(3, &self.digest);
         }
@@ -201,9 +160,6 @@ impl ::protobuf::Message for Layer {
         if !self.name.is_empty() {
             os.write_string(1, &self.name)?;
         }
-        if !self.repo.is_empty() {
-            os.write_string(2, &self.repo)?;
-        }
         if !self.digest.is_empty() {
             os.write_string(3, &self.digest)?;
         }
@@ -256,11 +212,6 @@ impl ::protobuf::MessageStatic for Layer {
                     Layer::get_name_for_reflect,
                     Layer::mut_name_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                    "repo",
-                    Layer::get_repo_for_reflect,
-                    Layer::mut_repo_for_reflect,
-                ));
                 fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
                     "digest",
                     Layer::get_digest_for_reflect,
@@ -279,7 +230,6 @@ impl ::protobuf::MessageStatic for Layer {
 impl ::protobuf::Clear for Layer {
     fn clear(&mut self) {
         self.clear_name();
-        self.clear_repo();
         self.clear_digest();
         self.unknown_fields.clear();
     }
@@ -1212,94 +1162,2461 @@ impl ::protobuf::reflect::ProtobufValue for UuidList {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct CommittedBlob {
+    // message fields
+    pub name: ::std::string::String,
+    pub uuid: ::std::string::String,
+    pub digest: ::std::string::String,
+    pub size: u64,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for CommittedBlob {}
+
+impl CommittedBlob {
+    pub fn new() -> CommittedBlob {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static CommittedBlob {
+        static mut instance: ::protobuf::lazy::Lazy<CommittedBlob> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const CommittedBlob,
+        };
+        unsafe {
+            instance.get(CommittedBlob::new)
+        }
+    }
+
+    // string name = 1;
+
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_name_for_reflect(&self) -> &::std::string::String {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // string uuid = 3;
+
+    pub fn clear_uuid(&mut self) {
+        self.uuid.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_uuid(&mut self, v: ::std::string::String) {
+        self.uuid = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_uuid(&mut self) -> &mut ::std::string::String {
+        &mut self.uuid
+    }
+
+    // Take field
+    pub fn take_uuid(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.uuid, ::std::string::String::new())
+    }
+
+    pub fn get_uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    fn get_uuid_for_reflect(&self) -> &::std::string::String {
+        &self.uuid
+    }
+
+    fn mut_uuid_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.uuid
+    }
+
+    // string digest = 4;
+
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+
+    fn get_digest_for_reflect(&self) -> &::std::string::String {
+        &self.digest
+    }
+
+    fn mut_digest_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // uint64 size = 5;
+
+    pub fn clear_size(&mut self) {
+        self.size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_size(&mut self, v: u64) {
+        self.size = v;
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    fn get_size_for_reflect(&self) -> &u64 {
+        &self.size
+    }
+
+    fn mut_size_for_reflect(&mut self) -> &mut u64 {
+        &mut self.size
+    }
+}
+
+impl ::protobuf::Message for CommittedBlob {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.uuid)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.size = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if !self.uuid.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.uuid);
+        }
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.digest);
+        }
+        if self.size != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if !self.uuid.is_empty() {
+            os.write_string(3, &self.uuid)?;
+        }
+        if !self.digest.is_empty() {
+            os.write_string(4, &self.digest)?;
+        }
+        if self.size != 0 {
+            os.write_uint64(5, self.size)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for CommittedBlob {
+    fn new() -> CommittedBlob {
+        CommittedBlob::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<CommittedBlob>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "name",
+                    CommittedBlob::get_name_for_reflect,
+                    CommittedBlob::mut_name_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "uuid",
+                    CommittedBlob::get_uuid_for_reflect,
+                    CommittedBlob::mut_uuid_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "digest",
+                    CommittedBlob::get_digest_for_reflect,
+                    CommittedBlob::mut_digest_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "size",
+                    CommittedBlob::get_size_for_reflect,
+                    CommittedBlob::mut_size_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<CommittedBlob>(
+                    "CommittedBlob",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for CommittedBlob {
+    fn clear(&mut self) {
+        self.clear_name();
+        self.clear_uuid();
+        self.clear_digest();
+        self.clear_size();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for CommittedBlob {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for CommittedBlob {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct WatchRequest {
+    // message fields
+    pub repositories: ::protobuf::RepeatedField<::std::string::String>,
+    pub cursor: u64,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for WatchRequest {}
+
+impl WatchRequest {
+    pub fn new() -> WatchRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static WatchRequest {
+        static mut instance: ::protobuf::lazy::Lazy<WatchRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const WatchRequest,
+        };
+        unsafe {
+            instance.get(WatchRequest::new)
+        }
+    }
+
+    // repeated string repositories = 1;
+
+    pub fn clear_repositories(&mut self) {
+        self.repositories.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_repositories(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.repositories = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_repositories(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.repositories
+    }
+
+    // Take field
+    pub fn take_repositories(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.repositories, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_repositories(&self) -> &[::std::string::String] {
+        &self.repositories
+    }
+
+    fn get_repositories_for_reflect(&self) -> &::protobuf::RepeatedField<::std::string::String> {
+        &self.repositories
+    }
+
+    fn mut_repositories_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.repositories
+    }
+
+    // uint64 cursor = 2;
+
+    pub fn clear_cursor(&mut self) {
+        self.cursor = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cursor(&mut self, v: u64) {
+        self.cursor = v;
+    }
+
+    pub fn get_cursor(&self) -> u64 {
+        self.cursor
+    }
+
+    fn get_cursor_for_reflect(&self) -> &u64 {
+        &self.cursor
+    }
+
+    fn mut_cursor_for_reflect(&mut self) -> &mut u64 {
+        &mut self.cursor
+    }
+}
+
+impl ::protobuf::Message for WatchRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.repositories)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.cursor = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.repositories {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.cursor != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.cursor, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.repositories {
+            os.write_string(1, &v)?;
+        };
+        if self.cursor != 0 {
+            os.write_uint64(2, self.cursor)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for WatchRequest {
+    fn new() -> WatchRequest {
+        WatchRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<WatchRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "repositories",
+                    WatchRequest::get_repositories_for_reflect,
+                    WatchRequest::mut_repositories_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "cursor",
+                    WatchRequest::get_cursor_for_reflect,
+                    WatchRequest::mut_cursor_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<WatchRequest>(
+                    "WatchRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for WatchRequest {
+    fn clear(&mut self) {
+        self.clear_repositories();
+        self.clear_cursor();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for WatchRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for WatchRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Event {
+    // message fields
+    pub cursor: u64,
+    pub kind: EventKind,
+    pub repository: ::std::string::String,
+    pub digest: ::std::string::String,
+    pub tag: ::std::string::String,
+    pub peer: ::std::string::String,
+    pub timestamp: i64,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Event {}
+
+impl Event {
+    pub fn new() -> Event {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Event {
+        static mut instance: ::protobuf::lazy::Lazy<Event> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Event,
+        };
+        unsafe {
+            instance.get(Event::new)
+        }
+    }
+
+    // uint64 cursor = 1;
+
+    pub fn clear_cursor(&mut self) {
+        self.cursor = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cursor(&mut self, v: u64) {
+        self.cursor = v;
+    }
+
+    pub fn get_cursor(&self) -> u64 {
+        self.cursor
+    }
+
+    fn get_cursor_for_reflect(&self) -> &u64 {
+        &self.cursor
+    }
+
+    fn mut_cursor_for_reflect(&mut self) -> &mut u64 {
+        &mut self.cursor
+    }
+
+    // .lycaon.EventKind kind = 2;
+
+    pub fn clear_kind(&mut self) {
+        self.kind = EventKind::UPLOAD_STARTED;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_kind(&mut self, v: EventKind) {
+        self.kind = v;
+    }
+
+    pub fn get_kind(&self) -> EventKind {
+        self.kind
+    }
+
+    fn get_kind_for_reflect(&self) -> &EventKind {
+        &self.kind
+    }
+
+    fn mut_kind_for_reflect(&mut self) -> &mut EventKind {
+        &mut self.kind
+    }
+
+    // string repository = 3;
+
+    pub fn clear_repository(&mut self) {
+        self.repository.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_repository(&mut self, v: ::std::string::String) {
+        self.repository = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_repository(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // Take field
+    pub fn take_repository(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.repository, ::std::string::String::new())
+    }
+
+    pub fn get_repository(&self) -> &str {
+        &self.repository
+    }
+
+    fn get_repository_for_reflect(&self) -> &::std::string::String {
+        &self.repository
+    }
+
+    fn mut_repository_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // string digest = 4;
+
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+
+    fn get_digest_for_reflect(&self) -> &::std::string::String {
+        &self.digest
+    }
+
+    fn mut_digest_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // string tag = 5;
+
+    pub fn clear_tag(&mut self) {
+        self.tag.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tag(&mut self, v: ::std::string::String) {
+        self.tag = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_tag(&mut self) -> &mut ::std::string::String {
+        &mut self.tag
+    }
+
+    // Take field
+    pub fn take_tag(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.tag, ::std::string::String::new())
+    }
+
+    pub fn get_tag(&self) -> &str {
+        &self.tag
+    }
+
+    fn get_tag_for_reflect(&self) -> &::std::string::String {
+        &self.tag
+    }
+
+    fn mut_tag_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.tag
+    }
+
+    // string peer = 6;
+
+    pub fn clear_peer(&mut self) {
+        self.peer.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_peer(&mut self, v: ::std::string::String) {
+        self.peer = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_peer(&mut self) -> &mut ::std::string::String {
+        &mut self.peer
+    }
+
+    // Take field
+    pub fn take_peer(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.peer, ::std::string::String::new())
+    }
+
+    pub fn get_peer(&self) -> &str {
+        &self.peer
+    }
+
+    fn get_peer_for_reflect(&self) -> &::std::string::String {
+        &self.peer
+    }
+
+    fn mut_peer_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.peer
+    }
+
+    // int64 timestamp = 7;
+
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: i64) {
+        self.timestamp = v;
+    }
+
+    pub fn get_timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    fn get_timestamp_for_reflect(&self) -> &i64 {
+        &self.timestamp
+    }
+
+    fn mut_timestamp_for_reflect(&mut self) -> &mut i64 {
+        &mut self.timestamp
+    }
+}
+
+impl ::protobuf::Message for Event {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.cursor = tmp;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.kind = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.repository)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.tag)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.peer)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.timestamp = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.cursor != 0 {
+            my_size += ::protobuf::rt::value_size(1, self.cursor, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.kind != EventKind::UPLOAD_STARTED {
+            my_size += ::protobuf::rt::enum_size(2, self.kind);
+        }
+        if !self.repository.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.repository);
+        }
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.digest);
+        }
+        if !self.tag.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.tag);
+        }
+        if !self.peer.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.peer);
+        }
+        if self.timestamp != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.timestamp, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.cursor != 0 {
+            os.write_uint64(1, self.cursor)?;
+        }
+        if self.kind != EventKind::UPLOAD_STARTED {
+            os.write_enum(2, self.kind.value())?;
+        }
+        if !self.repository.is_empty() {
+            os.write_string(3, &self.repository)?;
+        }
+        if !self.digest.is_empty() {
+            os.write_string(4, &self.digest)?;
+        }
+        if !self.tag.is_empty() {
+            os.write_string(5, &self.tag)?;
+        }
+        if !self.peer.is_empty() {
+            os.write_string(6, &self.peer)?;
+        }
+        if self.timestamp != 0 {
+            os.write_int64(7, self.timestamp)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Event {
+    fn new() -> Event {
+        Event::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Event>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "cursor",
+                    Event::get_cursor_for_reflect,
+                    Event::mut_cursor_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<EventKind>>(
+                    "kind",
+                    Event::get_kind_for_reflect,
+                    Event::mut_kind_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "repository",
+                    Event::get_repository_for_reflect,
+                    Event::mut_repository_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "digest",
+                    Event::get_digest_for_reflect,
+                    Event::mut_digest_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "tag",
+                    Event::get_tag_for_reflect,
+                    Event::mut_tag_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "peer",
+                    Event::get_peer_for_reflect,
+                    Event::mut_peer_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "timestamp",
+                    Event::get_timestamp_for_reflect,
+                    Event::mut_timestamp_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Event>(
+                    "Event",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Event {
+    fn clear(&mut self) {
+        self.clear_cursor();
+        self.clear_kind();
+        self.clear_repository();
+        self.clear_digest();
+        self.clear_tag();
+        self.clear_peer();
+        self.clear_timestamp();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Event {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Event {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Referrer {
+    // message fields
+    pub repository: ::std::string::String,
+    pub subject: ::std::string::String,
+    pub digest: ::std::string::String,
+    pub media_type: ::std::string::String,
+    pub artifact_type: ::std::string::String,
+    pub size: u64,
+    pub annotations: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Referrer {}
+
+impl Referrer {
+    pub fn new() -> Referrer {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Referrer {
+        static mut instance: ::protobuf::lazy::Lazy<Referrer> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Referrer,
+        };
+        unsafe {
+            instance.get(Referrer::new)
+        }
+    }
+
+    // string repository = 1;
+
+    pub fn clear_repository(&mut self) {
+        self.repository.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_repository(&mut self, v: ::std::string::String) {
+        self.repository = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_repository(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // Take field
+    pub fn take_repository(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.repository, ::std::string::String::new())
+    }
+
+    pub fn get_repository(&self) -> &str {
+        &self.repository
+    }
+
+    fn get_repository_for_reflect(&self) -> &::std::string::String {
+        &self.repository
+    }
+
+    fn mut_repository_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // string subject = 2;
+
+    pub fn clear_subject(&mut self) {
+        self.subject.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_subject(&mut self, v: ::std::string::String) {
+        self.subject = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_subject(&mut self) -> &mut ::std::string::String {
+        &mut self.subject
+    }
+
+    // Take field
+    pub fn take_subject(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.subject, ::std::string::String::new())
+    }
+
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn get_subject_for_reflect(&self) -> &::std::string::String {
+        &self.subject
+    }
+
+    fn mut_subject_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.subject
+    }
+
+    // string digest = 3;
+
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+
+    fn get_digest_for_reflect(&self) -> &::std::string::String {
+        &self.digest
+    }
+
+    fn mut_digest_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // string media_type = 4;
+
+    pub fn clear_media_type(&mut self) {
+        self.media_type.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_media_type(&mut self, v: ::std::string::String) {
+        self.media_type = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_media_type(&mut self) -> &mut ::std::string::String {
+        &mut self.media_type
+    }
+
+    // Take field
+    pub fn take_media_type(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.media_type, ::std::string::String::new())
+    }
+
+    pub fn get_media_type(&self) -> &str {
+        &self.media_type
+    }
+
+    fn get_media_type_for_reflect(&self) -> &::std::string::String {
+        &self.media_type
+    }
+
+    fn mut_media_type_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.media_type
+    }
+
+    // string artifact_type = 5;
+
+    pub fn clear_artifact_type(&mut self) {
+        self.artifact_type.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_artifact_type(&mut self, v: ::std::string::String) {
+        self.artifact_type = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_artifact_type(&mut self) -> &mut ::std::string::String {
+        &mut self.artifact_type
+    }
+
+    // Take field
+    pub fn take_artifact_type(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.artifact_type, ::std::string::String::new())
+    }
+
+    pub fn get_artifact_type(&self) -> &str {
+        &self.artifact_type
+    }
+
+    fn get_artifact_type_for_reflect(&self) -> &::std::string::String {
+        &self.artifact_type
+    }
+
+    fn mut_artifact_type_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.artifact_type
+    }
+
+    // uint64 size = 6;
+
+    pub fn clear_size(&mut self) {
+        self.size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_size(&mut self, v: u64) {
+        self.size = v;
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    fn get_size_for_reflect(&self) -> &u64 {
+        &self.size
+    }
+
+    fn mut_size_for_reflect(&mut self) -> &mut u64 {
+        &mut self.size
+    }
+
+    // string annotations = 7;
+
+    pub fn clear_annotations(&mut self) {
+        self.annotations.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_annotations(&mut self, v: ::std::string::String) {
+        self.annotations = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_annotations(&mut self) -> &mut ::std::string::String {
+        &mut self.annotations
+    }
+
+    // Take field
+    pub fn take_annotations(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.annotations, ::std::string::String::new())
+    }
+
+    pub fn get_annotations(&self) -> &str {
+        &self.annotations
+    }
+
+    fn get_annotations_for_reflect(&self) -> &::std::string::String {
+        &self.annotations
+    }
+
+    fn mut_annotations_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.annotations
+    }
+}
+
+impl ::protobuf::Message for Referrer {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.repository)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.subject)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.media_type)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.artifact_type)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.size = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.annotations)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.repository.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.repository);
+        }
+        if !self.subject.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.subject);
+        }
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.digest);
+        }
+        if !self.media_type.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.media_type);
+        }
+        if !self.artifact_type.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.artifact_type);
+        }
+        if self.size != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.annotations.is_empty() {
+            my_size += ::protobuf::rt::string_size(7, &self.annotations);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.repository.is_empty() {
+            os.write_string(1, &self.repository)?;
+        }
+        if !self.subject.is_empty() {
+            os.write_string(2, &self.subject)?;
+        }
+        if !self.digest.is_empty() {
+            os.write_string(3, &self.digest)?;
+        }
+        if !self.media_type.is_empty() {
+            os.write_string(4, &self.media_type)?;
+        }
+        if !self.artifact_type.is_empty() {
+            os.write_string(5, &self.artifact_type)?;
+        }
+        if self.size != 0 {
+            os.write_uint64(6, self.size)?;
+        }
+        if !self.annotations.is_empty() {
+            os.write_string(7, &self.annotations)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Referrer {
+    fn new() -> Referrer {
+        Referrer::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Referrer>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "repository",
+                    Referrer::get_repository_for_reflect,
+                    Referrer::mut_repository_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "subject",
+                    Referrer::get_subject_for_reflect,
+                    Referrer::mut_subject_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "digest",
+                    Referrer::get_digest_for_reflect,
+                    Referrer::mut_digest_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "media_type",
+                    Referrer::get_media_type_for_reflect,
+                    Referrer::mut_media_type_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "artifact_type",
+                    Referrer::get_artifact_type_for_reflect,
+                    Referrer::mut_artifact_type_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "size",
+                    Referrer::get_size_for_reflect,
+                    Referrer::mut_size_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "annotations",
+                    Referrer::get_annotations_for_reflect,
+                    Referrer::mut_annotations_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Referrer>(
+                    "Referrer",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Referrer {
+    fn clear(&mut self) {
+        self.clear_repository();
+        self.clear_subject();
+        self.clear_digest();
+        self.clear_media_type();
+        self.clear_artifact_type();
+        self.clear_size();
+        self.clear_annotations();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Referrer {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Referrer {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ReferrersRequest {
+    // message fields
+    pub repository: ::std::string::String,
+    pub subject: ::std::string::String,
+    pub artifact_type: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ReferrersRequest {}
+
+impl ReferrersRequest {
+    pub fn new() -> ReferrersRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ReferrersRequest {
+        static mut instance: ::protobuf::lazy::Lazy<ReferrersRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ReferrersRequest,
+        };
+        unsafe {
+            instance.get(ReferrersRequest::new)
+        }
+    }
+
+    // string repository = 1;
+
+    pub fn clear_repository(&mut self) {
+        self.repository.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_repository(&mut self, v: ::std::string::String) {
+        self.repository = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_repository(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // Take field
+    pub fn take_repository(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.repository, ::std::string::String::new())
+    }
+
+    pub fn get_repository(&self) -> &str {
+        &self.repository
+    }
+
+    fn get_repository_for_reflect(&self) -> &::std::string::String {
+        &self.repository
+    }
+
+    fn mut_repository_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.repository
+    }
+
+    // string subject = 2;
+
+    pub fn clear_subject(&mut self) {
+        self.subject.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_subject(&mut self, v: ::std::string::String) {
+        self.subject = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_subject(&mut self) -> &mut ::std::string::String {
+        &mut self.subject
+    }
+
+    // Take field
+    pub fn take_subject(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.subject, ::std::string::String::new())
+    }
+
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    fn get_subject_for_reflect(&self) -> &::std::string::String {
+        &self.subject
+    }
+
+    fn mut_subject_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.subject
+    }
+
+    // string artifact_type = 3;
+
+    pub fn clear_artifact_type(&mut self) {
+        self.artifact_type.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_artifact_type(&mut self, v: ::std::string::String) {
+        self.artifact_type = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_artifact_type(&mut self) -> &mut ::std::string::String {
+        &mut self.artifact_type
+    }
+
+    // Take field
+    pub fn take_artifact_type(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.artifact_type, ::std::string::String::new())
+    }
+
+    pub fn get_artifact_type(&self) -> &str {
+        &self.artifact_type
+    }
+
+    fn get_artifact_type_for_reflect(&self) -> &::std::string::String {
+        &self.artifact_type
+    }
+
+    fn mut_artifact_type_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.artifact_type
+    }
+}
+
+impl ::protobuf::Message for ReferrersRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.repository)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.subject)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.artifact_type)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.repository.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.repository);
+        }
+        if !self.subject.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.subject);
+        }
+        if !self.artifact_type.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.artifact_type);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.repository.is_empty() {
+            os.write_string(1, &self.repository)?;
+        }
+        if !self.subject.is_empty() {
+            os.write_string(2, &self.subject)?;
+        }
+        if !self.artifact_type.is_empty() {
+            os.write_string(3, &self.artifact_type)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ReferrersRequest {
+    fn new() -> ReferrersRequest {
+        ReferrersRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ReferrersRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "repository",
+                    ReferrersRequest::get_repository_for_reflect,
+                    ReferrersRequest::mut_repository_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "subject",
+                    ReferrersRequest::get_subject_for_reflect,
+                    ReferrersRequest::mut_subject_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "artifact_type",
+                    ReferrersRequest::get_artifact_type_for_reflect,
+                    ReferrersRequest::mut_artifact_type_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ReferrersRequest>(
+                    "ReferrersRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ReferrersRequest {
+    fn clear(&mut self) {
+        self.clear_repository();
+        self.clear_subject();
+        self.clear_artifact_type();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ReferrersRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ReferrersRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ReferrerList {
+    // message fields
+    pub referrers: ::protobuf::RepeatedField<Referrer>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ReferrerList {}
+
+impl ReferrerList {
+    pub fn new() -> ReferrerList {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ReferrerList {
+        static mut instance: ::protobuf::lazy::Lazy<ReferrerList> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ReferrerList,
+        };
+        unsafe {
+            instance.get(ReferrerList::new)
+        }
+    }
+
+    // repeated .lycaon.Referrer referrers = 1;
+
+    pub fn clear_referrers(&mut self) {
+        self.referrers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_referrers(&mut self, v: ::protobuf::RepeatedField<Referrer>) {
+        self.referrers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_referrers(&mut self) -> &mut ::protobuf::RepeatedField<Referrer> {
+        &mut self.referrers
+    }
+
+    // Take field
+    pub fn take_referrers(&mut self) -> ::protobuf::RepeatedField<Referrer> {
+        ::std::mem::replace(&mut self.referrers, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_referrers(&self) -> &[Referrer] {
+        &self.referrers
+    }
+
+    fn get_referrers_for_reflect(&self) -> &::protobuf::RepeatedField<Referrer> {
+        &self.referrers
+    }
+
+    fn mut_referrers_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Referrer> {
+        &mut self.referrers
+    }
+}
+
+impl ::protobuf::Message for ReferrerList {
+    fn is_initialized(&self) -> bool {
+        for v in &self.referrers {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.referrers)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.referrers {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.referrers {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ReferrerList {
+    fn new() -> ReferrerList {
+        ReferrerList::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ReferrerList>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Referrer>>(
+                    "referrers",
+                    ReferrerList::get_referrers_for_reflect,
+                    ReferrerList::mut_referrers_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ReferrerList>(
+                    "ReferrerList",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ReferrerList {
+    fn clear(&mut self) {
+        self.clear_referrers();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ReferrerList {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ReferrerList {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BlobChunk {
+    // message fields
+    pub data: ::std::vec::Vec<u8>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for BlobChunk {}
+
+impl BlobChunk {
+    pub fn new() -> BlobChunk {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static BlobChunk {
+        static mut instance: ::protobuf::lazy::Lazy<BlobChunk> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BlobChunk,
+        };
+        unsafe {
+            instance.get(BlobChunk::new)
+        }
+    }
+
+    // bytes data = 1;
+
+    pub fn clear_data(&mut self) {
+        self.data.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_data(&mut self, v: ::std::vec::Vec<u8>) {
+        self.data = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_data(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.data
+    }
+
+    // Take field
+    pub fn take_data(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.data, ::std::vec::Vec::new())
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn get_data_for_reflect(&self) -> &::std::vec::Vec<u8> {
+        &self.data
+    }
+
+    fn mut_data_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.data
+    }
+}
+
+impl ::protobuf::Message for BlobChunk {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.data)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.data.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.data);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.data.is_empty() {
+            os.write_bytes(1, &self.data)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for BlobChunk {
+    fn new() -> BlobChunk {
+        BlobChunk::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<BlobChunk>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "data",
+                    BlobChunk::get_data_for_reflect,
+                    BlobChunk::mut_data_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<BlobChunk>(
+                    "BlobChunk",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for BlobChunk {
+    fn clear(&mut self) {
+        self.clear_data();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BlobChunk {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BlobChunk {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum EventKind {
+    UPLOAD_STARTED = 0,
+    UPLOAD_CANCELLED = 1,
+    BLOB_COMMITTED = 2,
+    TAG_UPDATED = 3,
+    MANIFEST_DELETED = 4,
+    PEER_JOINED = 5,
+    PEER_LEFT = 6,
+}
+
+impl ::protobuf::ProtobufEnum for EventKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<EventKind> {
+        match value {
+            0 => ::std::option::Option::Some(EventKind::UPLOAD_STARTED),
+            1 => ::std::option::Option::Some(EventKind::UPLOAD_CANCELLED),
+            2 => ::std::option::Option::Some(EventKind::BLOB_COMMITTED),
+            3 => ::std::option::Option::Some(EventKind::TAG_UPDATED),
+            4 => ::std::option::Option::Some(EventKind::MANIFEST_DELETED),
+            5 => ::std::option::Option::Some(EventKind::PEER_JOINED),
+            6 => ::std::option::Option::Some(EventKind::PEER_LEFT),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [EventKind] = &[
+            EventKind::UPLOAD_STARTED,
+            EventKind::UPLOAD_CANCELLED,
+            EventKind::BLOB_COMMITTED,
+            EventKind::TAG_UPDATED,
+            EventKind::MANIFEST_DELETED,
+            EventKind::PEER_JOINED,
+            EventKind::PEER_LEFT,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<EventKind>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("EventKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for EventKind {
+}
+
+impl ::std::default::Default for EventKind {
+    fn default() -> Self {
+        EventKind::UPLOAD_STARTED
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for EventKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x11src/backend.proto\x12\x06lycaon\"G\n\x05Layer\x12\x12\n\x04name\
-    \x18\x01\x20\x01(\tR\x04name\x12\x12\n\x04repo\x18\x02\x20\x01(\tR\x04re\
-    po\x12\x16\n\x06digest\x18\x03\x20\x01(\tR\x06digest\"\x07\n\x05Empty\"6\
-    \n\x06Result\x12\x18\n\x07success\x18\x01\x20\x01(\x08R\x07success\x12\
-    \x12\n\x04text\x18\x02\x20\x01(\tR\x04text\"E\n\x11LayerExistsResult\x12\
-    \x18\n\x07success\x18\x01\x20\x01(\x08R\x07success\x12\x16\n\x06length\
-    \x18\x02\x20\x01(\x04R\x06length\"#\n\rGenUuidResult\x12\x12\n\x04uuid\
-    \x18\x01\x20\x01(\tR\x04uuid\"7\n\x08UuidList\x12+\n\x05uuids\x18\x01\
-    \x20\x03(\x0b2\x15.lycaon.GenUuidResultR\x05uuids2\x86\x02\n\x07Backend\
-    \x129\n\x0blayerExists\x12\r.lycaon.Layer\x1a\x19.lycaon.LayerExistsResu\
-    lt\"\0\x121\n\x07GenUuid\x12\r.lycaon.Layer\x1a\x15.lycaon.GenUuidResult\
-    \"\0\x12-\n\nUuidExists\x12\r.lycaon.Layer\x1a\x0e.lycaon.Result\"\0\x12\
-    /\n\x0ccancelUpload\x12\r.lycaon.Layer\x1a\x0e.lycaon.Result\"\0\x12-\n\
-    \x08getUuids\x12\r.lycaon.Empty\x1a\x10.lycaon.UuidList\"\0J\xf6\x0f\n\
-    \x06\x12\x04\0\0C\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\
-    \x12\x03\x02\x08\x0e\n\xdc\x01\n\x02\x04\0\x12\x04\x0b\0\x0f\x012\xcf\
-    \x01\x20The\x20backend\x20Protobuf\x20protocol\x20is\x20used\x20between\
-    \x20the\x20Client-facing\n\x20frontend\x20and\x20the\x20Business-logic\
-    \x20backend.\n\n\x20A\x20single\x20service\x20defines\x20the\x20legal\
-    \x20rpc\x20calls\x20that\x20can\x20be\x20made\x20to\n\x20the\x20backend\
-    \x20from\x20the\x20Frontend.\n\n\n\n\n\x03\x04\0\x01\x12\x03\x0b\x08\r\n\
-    \x0b\n\x04\x04\0\x02\0\x12\x03\x0c\x02\x12\n\r\n\x05\x04\0\x02\0\x04\x12\
-    \x04\x0c\x02\x0b\x0f\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x0c\x02\x08\n\
-    \x0c\n\x05\x04\0\x02\0\x01\x12\x03\x0c\t\r\n\x0c\n\x05\x04\0\x02\0\x03\
-    \x12\x03\x0c\x10\x11\n\x0b\n\x04\x04\0\x02\x01\x12\x03\r\x02\x12\n\r\n\
-    \x05\x04\0\x02\x01\x04\x12\x04\r\x02\x0c\x12\n\x0c\n\x05\x04\0\x02\x01\
-    \x05\x12\x03\r\x02\x08\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\r\t\r\n\x0c\
-    \n\x05\x04\0\x02\x01\x03\x12\x03\r\x10\x11\n\x0b\n\x04\x04\0\x02\x02\x12\
-    \x03\x0e\x02\x14\n\r\n\x05\x04\0\x02\x02\x04\x12\x04\x0e\x02\r\x12\n\x0c\
-    \n\x05\x04\0\x02\x02\x05\x12\x03\x0e\x02\x08\n\x0c\n\x05\x04\0\x02\x02\
-    \x01\x12\x03\x0e\t\x0f\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x0e\x12\x13\
-    \n=\n\x02\x04\x01\x12\x03\x12\0\x10\x1a2\x20An\x20Empty\x20message\x20us\
-    ed\x20where\x20no\x20inputs\x20are\x20needed\n\n\n\n\x03\x04\x01\x01\x12\
-    \x03\x12\x08\r\n5\n\x02\x04\x02\x12\x04\x15\0\x18\x01\x1a)\x20A\x20gener\
-    ic\x20success/fail\x20response\x20message\n\n\n\n\x03\x04\x02\x01\x12\
-    \x03\x15\x08\x0e\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x16\x02\x13\n\r\n\x05\
-    \x04\x02\x02\0\x04\x12\x04\x16\x02\x15\x10\n\x0c\n\x05\x04\x02\x02\0\x05\
-    \x12\x03\x16\x02\x06\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x16\x07\x0e\n\
-    \x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x16\x11\x12\n\x0b\n\x04\x04\x02\x02\
-    \x01\x12\x03\x17\x02\x12\n\r\n\x05\x04\x02\x02\x01\x04\x12\x04\x17\x02\
-    \x16\x13\n\x0c\n\x05\x04\x02\x02\x01\x05\x12\x03\x17\x02\x08\n\x0c\n\x05\
-    \x04\x02\x02\x01\x01\x12\x03\x17\t\r\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\
-    \x03\x17\x10\x11\n\xa8\x01\n\x02\x04\x03\x12\x04\x1e\0!\x01\x1a\x9b\x01\
-    \x20The\x20result\x20message\x20for\x20a\x20request\x20of\x20image\x20ex\
-    istence.\n\n\x20success\x20:=\x20whether\x20or\x20not\x20the\x20image\
-    \x20exists\n\x20length\x20\x20:=\x20the\x20length\x20of\x20the\x20querie\
-    d\x20image\x20(if\x20exists)\n\n\n\n\x03\x04\x03\x01\x12\x03\x1e\x08\x19\
-    \n\x0b\n\x04\x04\x03\x02\0\x12\x03\x1f\x02\x13\n\r\n\x05\x04\x03\x02\0\
-    \x04\x12\x04\x1f\x02\x1e\x1b\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03\x1f\
-    \x02\x06\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x1f\x07\x0e\n\x0c\n\x05\
-    \x04\x03\x02\0\x03\x12\x03\x1f\x11\x12\n\x0b\n\x04\x04\x03\x02\x01\x12\
-    \x03\x20\x02\x14\n\r\n\x05\x04\x03\x02\x01\x04\x12\x04\x20\x02\x1f\x13\n\
-    \x0c\n\x05\x04\x03\x02\x01\x05\x12\x03\x20\x02\x08\n\x0c\n\x05\x04\x03\
-    \x02\x01\x01\x12\x03\x20\t\x0f\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03\
-    \x20\x12\x13\nR\n\x02\x04\x04\x12\x04%\0'\x01\x1aF\x20The\x20result\x20m\
-    essage\x20of\x20a\x20uuid\x20Generation.\n\x20uuid\x20:=\x20the\x20gener\
-    ated\x20uuid\n\n\n\n\x03\x04\x04\x01\x12\x03%\x08\x15\n\x0b\n\x04\x04\
-    \x04\x02\0\x12\x03&\x02\x12\n\r\n\x05\x04\x04\x02\0\x04\x12\x04&\x02%\
-    \x17\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x03&\x02\x08\n\x0c\n\x05\x04\x04\
-    \x02\0\x01\x12\x03&\t\r\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03&\x10\x11\n\
-    &\n\x02\x04\x05\x12\x04+\0-\x01\x1a\x1a\x20A\x20list\x20of\x20Uuids\n\
-    \x20:Admin:\n\n\n\n\x03\x04\x05\x01\x12\x03+\x08\x10\n\x0b\n\x04\x04\x05\
-    \x02\0\x12\x03,\x02#\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03,\x02\n\n\x0c\
-    \n\x05\x04\x05\x02\0\x06\x12\x03,\x0b\x18\n\x0c\n\x05\x04\x05\x02\0\x01\
-    \x12\x03,\x19\x1e\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03,!\"\n\n\n\x02\
-    \x06\0\x12\x04/\0C\x01\n\n\n\x03\x06\0\x01\x12\x03/\x08\x0f\nZ\n\x04\x06\
-    \0\x02\0\x12\x032\x028\x1aM\x20-----\x20Image\x20Upload\x20Flow\x20-----\
-    -----\n\x20Check\x20if\x20a\x20layer\x20exists\x20in\x20the\x20Registry\
-    \n\n\x0c\n\x05\x06\0\x02\0\x01\x12\x032\x06\x11\n\x0c\n\x05\x06\0\x02\0\
-    \x02\x12\x032\x13\x18\n\x0c\n\x05\x06\0\x02\0\x03\x12\x032#4\n=\n\x04\
-    \x06\0\x02\x01\x12\x035\x020\x1a0\x20Generate\x20a\x20uuid\x20for\x20a\
-    \x20new\x20layer\x20being\x20uploaded\n\n\x0c\n\x05\x06\0\x02\x01\x01\
-    \x12\x035\x06\r\n\x0c\n\x05\x06\0\x02\x01\x02\x12\x035\x0f\x14\n\x0c\n\
-    \x05\x06\0\x02\x01\x03\x12\x035\x1f,\nC\n\x04\x06\0\x02\x02\x12\x038\x02\
-    ,\x1a6\x20Given\x20a\x20Uuid,\x20check\x20whether\x20it\x20exists\x20in\
-    \x20the\x20cluster\n\n\x0c\n\x05\x06\0\x02\x02\x01\x12\x038\x06\x10\n\
-    \x0c\n\x05\x06\0\x02\x02\x02\x12\x038\x12\x17\n\x0c\n\x05\x06\0\x02\x02\
-    \x03\x12\x038\"(\n^\n\x04\x06\0\x02\x03\x12\x03<\x02.\x1aQ\x20Cancel\x20\
-    a\x20pending\x20upload\n\x20The\x20digest\x20field\x20is\x20used\x20for\
-    \x20the\x20uuid\x20in\x20this\x20rpc\x20call\n\n\x0c\n\x05\x06\0\x02\x03\
-    \x01\x12\x03<\x06\x12\n\x0c\n\x05\x06\0\x02\x03\x02\x12\x03<\x14\x19\n\
-    \x0c\n\x05\x06\0\x02\x03\x03\x12\x03<$*\nu\n\x04\x06\0\x02\x04\x12\x03B\
-    \x02,\x1a7\x20returns\x20a\x20list\x20of\x20all\x20Uuids\x20currently\
-    \x20in\x20the\x20\x20backend\n2/\x20------------\x20Admin\x20calls\x20--\
-    ------------------\n\n\x0c\n\x05\x06\0\x02\x04\x01\x12\x03B\x06\x0e\n\
-    \x0c\n\x05\x06\0\x02\x04\x02\x12\x03B\x10\x15\n\x0c\n\x05\x06\0\x02\x04\
-    \x03\x12\x03B\x20(b\x06proto3\
+    \n\x11src/backend.proto\x12\x06lycaon\"9\n\x05Layer\x12\x12\n\x04name\
+    \x18\x01\x20\x01(\tR\x04name\x12\x16\n\x06digest\x18\x03\x20\x01(\tR\x06\
+    digestJ\x04\x08\x02\x10\x03\"\x07\n\x05Empty\"6\n\x06Result\x12\x18\n\
+    \x07success\x18\x01\x20\x01(\x08R\x07success\x12\x12\n\x04text\x18\x02\
+    \x20\x01(\tR\x04text\"E\n\x11LayerExistsResult\x12\x18\n\x07success\x18\
+    \x01\x20\x01(\x08R\x07success\x12\x16\n\x06length\x18\x02\x20\x01(\x04R\
+    \x06length\"#\n\rGenUuidResult\x12\x12\n\x04uuid\x18\x01\x20\x01(\tR\x04\
+    uuid\"7\n\x08UuidList\x12+\n\x05uuids\x18\x01\x20\x03(\x0b2\x15.lycaon.G\
+    enUuidResultR\x05uuids\"i\n\rCommittedBlob\x12\x12\n\x04name\x18\x01\x20\
+    \x01(\tR\x04name\x12\x12\n\x04uuid\x18\x03\x20\x01(\tR\x04uuid\x12\x16\n\
+    \x06digest\x18\x04\x20\x01(\tR\x06digest\x12\x12\n\x04size\x18\x05\x20\
+    \x01(\x04R\x04sizeJ\x04\x08\x02\x10\x03\"J\n\x0cWatchRequest\x12\"\n\x0c\
+    repositories\x18\x01\x20\x03(\tR\x0crepositories\x12\x16\n\x06cursor\x18\
+    \x02\x20\x01(\x04R\x06cursor\"\xc2\x01\n\x05Event\x12\x16\n\x06cursor\
+    \x18\x01\x20\x01(\x04R\x06cursor\x12%\n\x04kind\x18\x02\x20\x01(\x0e2\
+    \x11.lycaon.EventKindR\x04kind\x12\x1e\n\nrepository\x18\x03\x20\x01(\tR\
+    \nrepository\x12\x16\n\x06digest\x18\x04\x20\x01(\tR\x06digest\x12\x10\n\
+    \x03tag\x18\x05\x20\x01(\tR\x03tag\x12\x12\n\x04peer\x18\x06\x20\x01(\tR\
+    \x04peer\x12\x1c\n\ttimestamp\x18\x07\x20\x01(\x03R\ttimestamp\"\xd6\x01\
+    \n\x08Referrer\x12\x1e\n\nrepository\x18\x01\x20\x01(\tR\nrepository\x12\
+    \x18\n\x07subject\x18\x02\x20\x01(\tR\x07subject\x12\x16\n\x06digest\x18\
+    \x03\x20\x01(\tR\x06digest\x12\x1d\n\nmedia_type\x18\x04\x20\x01(\tR\tme\
+    diaType\x12#\n\rartifact_type\x18\x05\x20\x01(\tR\x0cartifactType\x12\
+    \x12\n\x04size\x18\x06\x20\x01(\x04R\x04size\x12\x20\n\x0bannotations\
+    \x18\x07\x20\x01(\tR\x0bannotations\"q\n\x10ReferrersRequest\x12\x1e\n\n\
+    repository\x18\x01\x20\x01(\tR\nrepository\x12\x18\n\x07subject\x18\x02\
+    \x20\x01(\tR\x07subject\x12#\n\rartifact_type\x18\x03\x20\x01(\tR\x0cart\
+    ifactType\">\n\x0cReferrerList\x12.\n\treferrers\x18\x01\x20\x03(\x0b2\
+    \x10.lycaon.ReferrerR\treferrers\"\x1f\n\tBlobChunk\x12\x12\n\x04data\
+    \x18\x01\x20\x01(\x0cR\x04data*\x90\x01\n\tEventKind\x12\x12\n\x0eUPLOAD\
+    _STARTED\x10\0\x12\x14\n\x10UPLOAD_CANCELLED\x10\x01\x12\x12\n\x0eBLOB_C\
+    OMMITTED\x10\x02\x12\x0f\n\x0bTAG_UPDATED\x10\x03\x12\x14\n\x10MANIFEST_\
+    DELETED\x10\x04\x12\x0f\n\x0bPEER_JOINED\x10\x05\x12\r\n\tPEER_LEFT\x10\
+    \x062\xb7\x05\n\x07Backend\x129\n\x0blayerExists\x12\r.lycaon.Layer\x1a\
+    \x19.lycaon.LayerExistsResult\"\0\x121\n\x07GenUuid\x12\r.lycaon.Layer\
+    \x1a\x15.lycaon.GenUuidResult\"\0\x12-\n\nUuidExists\x12\r.lycaon.Layer\
+    \x1a\x0e.lycaon.Result\"\0\x12/\n\x0ccancelUpload\x12\r.lycaon.Layer\x1a\
+    \x0e.lycaon.Result\"\0\x127\n\x0ccommitUpload\x12\x15.lycaon.CommittedBl\
+    ob\x1a\x0e.lycaon.Result\"\0\x121\n\x0baddReferrer\x12\x10.lycaon.Referr\
+    er\x1a\x0e.lycaon.Result\"\0\x12A\n\rlistReferrers\x12\x18.lycaon.Referr\
+    ersRequest\x1a\x14.lycaon.ReferrerList\"\0\x12C\n\x0fremoveReferrers\x12\
+    \x18.lycaon.ReferrersRequest\x1a\x14.lycaon.ReferrerList\"\0\x120\n\x05w\
+    atch\x12\x14.lycaon.WatchRequest\x1a\r.lycaon.Event\"\00\x01\x12*\n\x07p\
+    ublish\x12\r.lycaon.Event\x1a\x0e.lycaon.Result\"\0\x121\n\tfetchBlob\
+    \x12\r.lycaon.Layer\x1a\x11.lycaon.BlobChunk\"\00\x01\x12-\n\x08getUuids\
+    \x12\r.lycaon.Empty\x1a\x10.lycaon.UuidList\"\0\x12*\n\x07storage\x12\r.\
+    lycaon.Empty\x1a\x0e.lycaon.Result\"\0J\xbe3\n\x07\x12\x05\0\0\xb1\x01\
+    \x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\x02\x12\x03\x02\x08\x0e\
+    \n\x95\x02\n\x02\x04\0\x12\x04\x0c\0\x11\x01\x1a7\x20name\x20:=\x20the\
+    \x20full\x20repository\x20name,\x20e.g.\x20library/ubuntu\n2\xcf\x01\x20\
+    The\x20backend\x20Protobuf\x20protocol\x20is\x20used\x20between\x20the\
+    \x20Client-facing\n\x20frontend\x20and\x20the\x20Business-logic\x20backe\
+    nd.\n\n\x20A\x20single\x20service\x20defines\x20the\x20legal\x20rpc\x20c\
+    alls\x20that\x20can\x20be\x20made\x20to\n\x20the\x20backend\x20from\x20t\
+    he\x20Frontend.\n\n\n\n\n\x03\x04\0\x01\x12\x03\x0c\x08\r\n-\n\x03\x04\0\
+    \t\x12\x03\x0e\x02\r\x1a!\x20was\x20the\x20second\x20half\x20of\x20the\
+    \x20name\n\n\x0b\n\x04\x04\0\t\0\x12\x03\x0e\x0b\x0c\n\x0c\n\x05\x04\0\t\
+    \0\x01\x12\x03\x0e\x0b\x0c\n\x0c\n\x05\x04\0\t\0\x02\x12\x03\x0e\x0b\x0c\
+    \n\x0b\n\x04\x04\0\x02\0\x12\x03\x0f\x02\x12\n\r\n\x05\x04\0\x02\0\x04\
+    \x12\x04\x0f\x02\x0e\r\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x0f\x02\x08\n\
+    \x0c\n\x05\x04\0\x02\0\x01\x12\x03\x0f\t\r\n\x0c\n\x05\x04\0\x02\0\x03\
+    \x12\x03\x0f\x10\x11\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x10\x02\x14\n\r\n\
+    \x05\x04\0\x02\x01\x04\x12\x04\x10\x02\x0f\x12\n\x0c\n\x05\x04\0\x02\x01\
+    \x05\x12\x03\x10\x02\x08\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x10\t\x0f\
+    \n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x10\x12\x13\n=\n\x02\x04\x01\x12\
+    \x03\x14\0\x10\x1a2\x20An\x20Empty\x20message\x20used\x20where\x20no\x20\
+    inputs\x20are\x20needed\n\n\n\n\x03\x04\x01\x01\x12\x03\x14\x08\r\n5\n\
+    \x02\x04\x02\x12\x04\x17\0\x1a\x01\x1a)\x20A\x20generic\x20success/fail\
+    \x20response\x20message\n\n\n\n\x03\x04\x02\x01\x12\x03\x17\x08\x0e\n\
+    \x0b\n\x04\x04\x02\x02\0\x12\x03\x18\x02\x13\n\r\n\x05\x04\x02\x02\0\x04\
+    \x12\x04\x18\x02\x17\x10\n\x0c\n\x05\x04\x02\x02\0\x05\x12\x03\x18\x02\
+    \x06\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x18\x07\x0e\n\x0c\n\x05\x04\
+    \x02\x02\0\x03\x12\x03\x18\x11\x12\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\
+    \x19\x02\x12\n\r\n\x05\x04\x02\x02\x01\x04\x12\x04\x19\x02\x18\x13\n\x0c\
+    \n\x05\x04\x02\x02\x01\x05\x12\x03\x19\x02\x08\n\x0c\n\x05\x04\x02\x02\
+    \x01\x01\x12\x03\x19\t\r\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x19\x10\
+    \x11\n\xa8\x01\n\x02\x04\x03\x12\x04\x20\0#\x01\x1a\x9b\x01\x20The\x20re\
+    sult\x20message\x20for\x20a\x20request\x20of\x20image\x20existence.\n\n\
+    \x20success\x20:=\x20whether\x20or\x20not\x20the\x20image\x20exists\n\
+    \x20length\x20\x20:=\x20the\x20length\x20of\x20the\x20queried\x20image\
+    \x20(if\x20exists)\n\n\n\n\x03\x04\x03\x01\x12\x03\x20\x08\x19\n\x0b\n\
+    \x04\x04\x03\x02\0\x12\x03!\x02\x13\n\r\n\x05\x04\x03\x02\0\x04\x12\x04!\
+    \x02\x20\x1b\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03!\x02\x06\n\x0c\n\x05\
+    \x04\x03\x02\0\x01\x12\x03!\x07\x0e\n\x0c\n\x05\x04\x03\x02\0\x03\x12\
+    \x03!\x11\x12\n\x0b\n\x04\x04\x03\x02\x01\x12\x03\"\x02\x14\n\r\n\x05\
+    \x04\x03\x02\x01\x04\x12\x04\"\x02!\x13\n\x0c\n\x05\x04\x03\x02\x01\x05\
+    \x12\x03\"\x02\x08\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03\"\t\x0f\n\x0c\
+    \n\x05\x04\x03\x02\x01\x03\x12\x03\"\x12\x13\nR\n\x02\x04\x04\x12\x04'\0\
+    )\x01\x1aF\x20The\x20result\x20message\x20of\x20a\x20uuid\x20Generation.\
+    \n\x20uuid\x20:=\x20the\x20generated\x20uuid\n\n\n\n\x03\x04\x04\x01\x12\
+    \x03'\x08\x15\n\x0b\n\x04\x04\x04\x02\0\x12\x03(\x02\x12\n\r\n\x05\x04\
+    \x04\x02\0\x04\x12\x04(\x02'\x17\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x03(\
+    \x02\x08\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03(\t\r\n\x0c\n\x05\x04\x04\
+    \x02\0\x03\x12\x03(\x10\x11\n&\n\x02\x04\x05\x12\x04-\0/\x01\x1a\x1a\x20\
+    A\x20list\x20of\x20Uuids\n\x20:Admin:\n\n\n\n\x03\x04\x05\x01\x12\x03-\
+    \x08\x10\n\x0b\n\x04\x04\x05\x02\0\x12\x03.\x02#\n\x0c\n\x05\x04\x05\x02\
+    \0\x04\x12\x03.\x02\n\n\x0c\n\x05\x04\x05\x02\0\x06\x12\x03.\x0b\x18\n\
+    \x0c\n\x05\x04\x05\x02\0\x01\x12\x03.\x19\x1e\n\x0c\n\x05\x04\x05\x02\0\
+    \x03\x12\x03.!\"\n\xc3\x01\n\x02\x04\x06\x12\x046\0<\x01\x1a\xb6\x01\x20\
+    A\x20finished\x20upload,\x20moved\x20into\x20the\x20layer\x20store\x20by\
+    \x20the\x20frontend.\n\n\x20name\x20\x20\x20:=\x20the\x20full\x20reposit\
+    ory\x20name\n\x20uuid\x20\x20\x20:=\x20the\x20upload\x20being\x20committ\
+    ed\n\x20digest\x20:=\x20the\x20verified\x20digest\x20of\x20the\x20blob\n\
+    \n\n\n\x03\x04\x06\x01\x12\x036\x08\x15\n\n\n\x03\x04\x06\t\x12\x037\x02\
+    \r\n\x0b\n\x04\x04\x06\t\0\x12\x037\x0b\x0c\n\x0c\n\x05\x04\x06\t\0\x01\
+    \x12\x037\x0b\x0c\n\x0c\n\x05\x04\x06\t\0\x02\x12\x037\x0b\x0c\n\x0b\n\
+    \x04\x04\x06\x02\0\x12\x038\x02\x12\n\r\n\x05\x04\x06\x02\0\x04\x12\x048\
+    \x027\r\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x038\x02\x08\n\x0c\n\x05\x04\
+    \x06\x02\0\x01\x12\x038\t\r\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x038\x10\
+    \x11\n\x0b\n\x04\x04\x06\x02\x01\x12\x039\x02\x12\n\r\n\x05\x04\x06\x02\
+    \x01\x04\x12\x049\x028\x12\n\x0c\n\x05\x04\x06\x02\x01\x05\x12\x039\x02\
+    \x08\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x039\t\r\n\x0c\n\x05\x04\x06\
+    \x02\x01\x03\x12\x039\x10\x11\n\x0b\n\x04\x04\x06\x02\x02\x12\x03:\x02\
+    \x14\n\r\n\x05\x04\x06\x02\x02\x04\x12\x04:\x029\x12\n\x0c\n\x05\x04\x06\
+    \x02\x02\x05\x12\x03:\x02\x08\n\x0c\n\x05\x04\x06\x02\x02\x01\x12\x03:\t\
+    \x0f\n\x0c\n\x05\x04\x06\x02\x02\x03\x12\x03:\x12\x13\n\x0b\n\x04\x04\
+    \x06\x02\x03\x12\x03;\x02\x12\n\r\n\x05\x04\x06\x02\x03\x04\x12\x04;\x02\
+    :\x14\n\x0c\n\x05\x04\x06\x02\x03\x05\x12\x03;\x02\x08\n\x0c\n\x05\x04\
+    \x06\x02\x03\x01\x12\x03;\t\r\n\x0c\n\x05\x04\x06\x02\x03\x03\x12\x03;\
+    \x10\x11\n\xc7\x01\n\x02\x04\x07\x12\x04B\0E\x01\x1a\xba\x01\x20Subscrip\
+    tion\x20to\x20registry\x20events.\n\n\x20repositories\x20:=\x20only\x20s\
+    end\x20events\x20for\x20these\x20repositories,\x20all\x20if\x20empty\n\
+    \x20cursor\x20\x20\x20\x20\x20\x20\x20:=\x20resume\x20after\x20the\x20ev\
+    ent\x20with\x20this\x20cursor,\x200\x20for\x20only\x20new\x20events\n\n\
+    \n\n\x03\x04\x07\x01\x12\x03B\x08\x14\n\x0b\n\x04\x04\x07\x02\0\x12\x03C\
+    \x02#\n\x0c\n\x05\x04\x07\x02\0\x04\x12\x03C\x02\n\n\x0c\n\x05\x04\x07\
+    \x02\0\x05\x12\x03C\x0b\x11\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x03C\x12\
+    \x1e\n\x0c\n\x05\x04\x07\x02\0\x03\x12\x03C!\"\n\x0b\n\x04\x04\x07\x02\
+    \x01\x12\x03D\x02\x14\n\r\n\x05\x04\x07\x02\x01\x04\x12\x04D\x02C#\n\x0c\
+    \n\x05\x04\x07\x02\x01\x05\x12\x03D\x02\x08\n\x0c\n\x05\x04\x07\x02\x01\
+    \x01\x12\x03D\t\x0f\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\x03D\x12\x13\n\n\
+    \n\x02\x05\0\x12\x04G\0O\x01\n\n\n\x03\x05\0\x01\x12\x03G\x05\x0e\n\x0b\
+    \n\x04\x05\0\x02\0\x12\x03H\x02\x15\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03H\
+    \x02\x10\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03H\x13\x14\n\x0b\n\x04\x05\0\
+    \x02\x01\x12\x03I\x02\x17\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03I\x02\x12\
+    \n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03I\x15\x16\n\x0b\n\x04\x05\0\x02\
+    \x02\x12\x03J\x02\x15\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03J\x02\x10\n\
+    \x0c\n\x05\x05\0\x02\x02\x02\x12\x03J\x13\x14\n\x0b\n\x04\x05\0\x02\x03\
+    \x12\x03K\x02\x12\n\x0c\n\x05\x05\0\x02\x03\x01\x12\x03K\x02\r\n\x0c\n\
+    \x05\x05\0\x02\x03\x02\x12\x03K\x10\x11\n\x0b\n\x04\x05\0\x02\x04\x12\
+    \x03L\x02\x17\n\x0c\n\x05\x05\0\x02\x04\x01\x12\x03L\x02\x12\n\x0c\n\x05\
+    \x05\0\x02\x04\x02\x12\x03L\x15\x16\n\x0b\n\x04\x05\0\x02\x05\x12\x03M\
+    \x02\x12\n\x0c\n\x05\x05\0\x02\x05\x01\x12\x03M\x02\r\n\x0c\n\x05\x05\0\
+    \x02\x05\x02\x12\x03M\x10\x11\n\x0b\n\x04\x05\0\x02\x06\x12\x03N\x02\x10\
+    \n\x0c\n\x05\x05\0\x02\x06\x01\x12\x03N\x02\x0b\n\x0c\n\x05\x05\0\x02\
+    \x06\x02\x12\x03N\x0e\x0f\n\xb7\x01\n\x02\x04\x08\x12\x04U\0]\x01\x1a\
+    \xaa\x01\x20A\x20single\x20registry\x20event,\x20fields\x20not\x20releva\
+    nt\x20to\x20the\x20kind\x20are\x20empty.\n\n\x20cursor\x20\x20\x20\x20:=\
+    \x20increasing\x20sequence\x20number,\x20used\x20to\x20resume\x20a\x20wa\
+    tch\n\x20timestamp\x20:=\x20unix\x20time\x20in\x20seconds\n\n\n\n\x03\
+    \x04\x08\x01\x12\x03U\x08\r\n\x0b\n\x04\x04\x08\x02\0\x12\x03V\x02\x14\n\
+    \r\n\x05\x04\x08\x02\0\x04\x12\x04V\x02U\x0f\n\x0c\n\x05\x04\x08\x02\0\
+    \x05\x12\x03V\x02\x08\n\x0c\n\x05\x04\x08\x02\0\x01\x12\x03V\t\x0f\n\x0c\
+    \n\x05\x04\x08\x02\0\x03\x12\x03V\x12\x13\n\x0b\n\x04\x04\x08\x02\x01\
+    \x12\x03W\x02\x15\n\r\n\x05\x04\x08\x02\x01\x04\x12\x04W\x02V\x14\n\x0c\
+    \n\x05\x04\x08\x02\x01\x06\x12\x03W\x02\x0b\n\x0c\n\x05\x04\x08\x02\x01\
+    \x01\x12\x03W\x0c\x10\n\x0c\n\x05\x04\x08\x02\x01\x03\x12\x03W\x13\x14\n\
+    \x0b\n\x04\x04\x08\x02\x02\x12\x03X\x02\x18\n\r\n\x05\x04\x08\x02\x02\
+    \x04\x12\x04X\x02W\x15\n\x0c\n\x05\x04\x08\x02\x02\x05\x12\x03X\x02\x08\
+    \n\x0c\n\x05\x04\x08\x02\x02\x01\x12\x03X\t\x13\n\x0c\n\x05\x04\x08\x02\
+    \x02\x03\x12\x03X\x16\x17\n\x0b\n\x04\x04\x08\x02\x03\x12\x03Y\x02\x14\n\
+    \r\n\x05\x04\x08\x02\x03\x04\x12\x04Y\x02X\x18\n\x0c\n\x05\x04\x08\x02\
+    \x03\x05\x12\x03Y\x02\x08\n\x0c\n\x05\x04\x08\x02\x03\x01\x12\x03Y\t\x0f\
+    \n\x0c\n\x05\x04\x08\x02\x03\x03\x12\x03Y\x12\x13\n\x0b\n\x04\x04\x08\
+    \x02\x04\x12\x03Z\x02\x11\n\r\n\x05\x04\x08\x02\x04\x04\x12\x04Z\x02Y\
+    \x14\n\x0c\n\x05\x04\x08\x02\x04\x05\x12\x03Z\x02\x08\n\x0c\n\x05\x04\
+    \x08\x02\x04\x01\x12\x03Z\t\x0c\n\x0c\n\x05\x04\x08\x02\x04\x03\x12\x03Z\
+    \x0f\x10\n\x0b\n\x04\x04\x08\x02\x05\x12\x03[\x02\x12\n\r\n\x05\x04\x08\
+    \x02\x05\x04\x12\x04[\x02Z\x11\n\x0c\n\x05\x04\x08\x02\x05\x05\x12\x03[\
+    \x02\x08\n\x0c\n\x05\x04\x08\x02\x05\x01\x12\x03[\t\r\n\x0c\n\x05\x04\
+    \x08\x02\x05\x03\x12\x03[\x10\x11\n\x0b\n\x04\x04\x08\x02\x06\x12\x03\\\
+    \x02\x16\n\r\n\x05\x04\x08\x02\x06\x04\x12\x04\\\x02[\x12\n\x0c\n\x05\
+    \x04\x08\x02\x06\x05\x12\x03\\\x02\x07\n\x0c\n\x05\x04\x08\x02\x06\x01\
+    \x12\x03\\\x08\x11\n\x0c\n\x05\x04\x08\x02\x06\x03\x12\x03\\\x14\x15\n\
+    \xc9\x02\n\x02\x04\t\x12\x04e\0m\x01\x1a\xbc\x02\x20A\x20manifest\x20ref\
+    erring\x20to\x20another\x20one\x20through\x20its\x20subject\x20field.\n\
+    \n\x20subject\x20\x20\x20\x20\x20\x20\x20:=\x20digest\x20of\x20the\x20ma\
+    nifest\x20referred\x20to\n\x20digest\x20\x20\x20\x20\x20\x20\x20\x20:=\
+    \x20digest\x20of\x20the\x20referrer\x20itself\n\x20artifact_type\x20:=\
+    \x20artifactType\x20of\x20the\x20referrer,\x20or\x20its\x20config\x20med\
+    ia\x20type\n\x20annotations\x20\x20\x20:=\x20annotations\x20of\x20the\
+    \x20referrer\x20as\x20a\x20JSON\x20object,\x20may\x20be\x20empty\n\n\n\n\
+    \x03\x04\t\x01\x12\x03e\x08\x10\n\x0b\n\x04\x04\t\x02\0\x12\x03f\x02\x18\
+    \n\r\n\x05\x04\t\x02\0\x04\x12\x04f\x02e\x12\n\x0c\n\x05\x04\t\x02\0\x05\
+    \x12\x03f\x02\x08\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03f\t\x13\n\x0c\n\x05\
+    \x04\t\x02\0\x03\x12\x03f\x16\x17\n\x0b\n\x04\x04\t\x02\x01\x12\x03g\x02\
+    \x15\n\r\n\x05\x04\t\x02\x01\x04\x12\x04g\x02f\x18\n\x0c\n\x05\x04\t\x02\
+    \x01\x05\x12\x03g\x02\x08\n\x0c\n\x05\x04\t\x02\x01\x01\x12\x03g\t\x10\n\
+    \x0c\n\x05\x04\t\x02\x01\x03\x12\x03g\x13\x14\n\x0b\n\x04\x04\t\x02\x02\
+    \x12\x03h\x02\x14\n\r\n\x05\x04\t\x02\x02\x04\x12\x04h\x02g\x15\n\x0c\n\
+    \x05\x04\t\x02\x02\x05\x12\x03h\x02\x08\n\x0c\n\x05\x04\t\x02\x02\x01\
+    \x12\x03h\t\x0f\n\x0c\n\x05\x04\t\x02\x02\x03\x12\x03h\x12\x13\n\x0b\n\
+    \x04\x04\t\x02\x03\x12\x03i\x02\x18\n\r\n\x05\x04\t\x02\x03\x04\x12\x04i\
+    \x02h\x14\n\x0c\n\x05\x04\t\x02\x03\x05\x12\x03i\x02\x08\n\x0c\n\x05\x04\
+    \t\x02\x03\x01\x12\x03i\t\x13\n\x0c\n\x05\x04\t\x02\x03\x03\x12\x03i\x16\
+    \x17\n\x0b\n\x04\x04\t\x02\x04\x12\x03j\x02\x1b\n\r\n\x05\x04\t\x02\x04\
+    \x04\x12\x04j\x02i\x18\n\x0c\n\x05\x04\t\x02\x04\x05\x12\x03j\x02\x08\n\
+    \x0c\n\x05\x04\t\x02\x04\x01\x12\x03j\t\x16\n\x0c\n\x05\x04\t\x02\x04\
+    \x03\x12\x03j\x19\x1a\n\x0b\n\x04\x04\t\x02\x05\x12\x03k\x02\x12\n\r\n\
+    \x05\x04\t\x02\x05\x04\x12\x04k\x02j\x1b\n\x0c\n\x05\x04\t\x02\x05\x05\
+    \x12\x03k\x02\x08\n\x0c\n\x05\x04\t\x02\x05\x01\x12\x03k\t\r\n\x0c\n\x05\
+    \x04\t\x02\x05\x03\x12\x03k\x10\x11\n\x0b\n\x04\x04\t\x02\x06\x12\x03l\
+    \x02\x19\n\r\n\x05\x04\t\x02\x06\x04\x12\x04l\x02k\x12\n\x0c\n\x05\x04\t\
+    \x02\x06\x05\x12\x03l\x02\x08\n\x0c\n\x05\x04\t\x02\x06\x01\x12\x03l\t\
+    \x14\n\x0c\n\x05\x04\t\x02\x06\x03\x12\x03l\x17\x18\nS\n\x02\x04\n\x12\
+    \x04p\0t\x01\x1aG\x20Selects\x20the\x20referrers\x20of\x20subject,\x20on\
+    ly\x20of\x20artifact_type\x20if\x20not\x20empty.\n\n\n\n\x03\x04\n\x01\
+    \x12\x03p\x08\x18\n\x0b\n\x04\x04\n\x02\0\x12\x03q\x02\x18\n\r\n\x05\x04\
+    \n\x02\0\x04\x12\x04q\x02p\x1a\n\x0c\n\x05\x04\n\x02\0\x05\x12\x03q\x02\
+    \x08\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03q\t\x13\n\x0c\n\x05\x04\n\x02\0\
+    \x03\x12\x03q\x16\x17\n\x0b\n\x04\x04\n\x02\x01\x12\x03r\x02\x15\n\r\n\
+    \x05\x04\n\x02\x01\x04\x12\x04r\x02q\x18\n\x0c\n\x05\x04\n\x02\x01\x05\
+    \x12\x03r\x02\x08\n\x0c\n\x05\x04\n\x02\x01\x01\x12\x03r\t\x10\n\x0c\n\
+    \x05\x04\n\x02\x01\x03\x12\x03r\x13\x14\n\x0b\n\x04\x04\n\x02\x02\x12\
+    \x03s\x02\x1b\n\r\n\x05\x04\n\x02\x02\x04\x12\x04s\x02r\x15\n\x0c\n\x05\
+    \x04\n\x02\x02\x05\x12\x03s\x02\x08\n\x0c\n\x05\x04\n\x02\x02\x01\x12\
+    \x03s\t\x16\n\x0c\n\x05\x04\n\x02\x02\x03\x12\x03s\x19\x1a\n\n\n\x02\x04\
+    \x0b\x12\x04v\0x\x01\n\n\n\x03\x04\x0b\x01\x12\x03v\x08\x14\n\x0b\n\x04\
+    \x04\x0b\x02\0\x12\x03w\x02\"\n\x0c\n\x05\x04\x0b\x02\0\x04\x12\x03w\x02\
+    \n\n\x0c\n\x05\x04\x0b\x02\0\x06\x12\x03w\x0b\x13\n\x0c\n\x05\x04\x0b\
+    \x02\0\x01\x12\x03w\x14\x1d\n\x0c\n\x05\x04\x0b\x02\0\x03\x12\x03w\x20!\
+    \n)\n\x02\x04\x0c\x12\x04{\0}\x01\x1a\x1d\x20A\x20piece\x20of\x20a\x20bl\
+    ob's\x20content\n\n\n\n\x03\x04\x0c\x01\x12\x03{\x08\x11\n\x0b\n\x04\x04\
+    \x0c\x02\0\x12\x03|\x02\x11\n\r\n\x05\x04\x0c\x02\0\x04\x12\x04|\x02{\
+    \x13\n\x0c\n\x05\x04\x0c\x02\0\x05\x12\x03|\x02\x07\n\x0c\n\x05\x04\x0c\
+    \x02\0\x01\x12\x03|\x08\x0c\n\x0c\n\x05\x04\x0c\x02\0\x03\x12\x03|\x0f\
+    \x10\n\x0b\n\x02\x06\0\x12\x05\x7f\0\xb1\x01\x01\n\n\n\x03\x06\0\x01\x12\
+    \x03\x7f\x08\x0f\n[\n\x04\x06\0\x02\0\x12\x04\x82\x01\x028\x1aM\x20-----\
+    \x20Image\x20Upload\x20Flow\x20----------\n\x20Check\x20if\x20a\x20layer\
+    \x20exists\x20in\x20the\x20Registry\n\n\r\n\x05\x06\0\x02\0\x01\x12\x04\
+    \x82\x01\x06\x11\n\r\n\x05\x06\0\x02\0\x02\x12\x04\x82\x01\x13\x18\n\r\n\
+    \x05\x06\0\x02\0\x03\x12\x04\x82\x01#4\n>\n\x04\x06\0\x02\x01\x12\x04\
+    \x85\x01\x020\x1a0\x20Generate\x20a\x20uuid\x20for\x20a\x20new\x20layer\
+    \x20being\x20uploaded\n\n\r\n\x05\x06\0\x02\x01\x01\x12\x04\x85\x01\x06\
+    \r\n\r\n\x05\x06\0\x02\x01\x02\x12\x04\x85\x01\x0f\x14\n\r\n\x05\x06\0\
+    \x02\x01\x03\x12\x04\x85\x01\x1f,\nD\n\x04\x06\0\x02\x02\x12\x04\x88\x01\
+    \x02,\x1a6\x20Given\x20a\x20Uuid,\x20check\x20whether\x20it\x20exists\
+    \x20in\x20the\x20cluster\n\n\r\n\x05\x06\0\x02\x02\x01\x12\x04\x88\x01\
+    \x06\x10\n\r\n\x05\x06\0\x02\x02\x02\x12\x04\x88\x01\x12\x17\n\r\n\x05\
+    \x06\0\x02\x02\x03\x12\x04\x88\x01\"(\n_\n\x04\x06\0\x02\x03\x12\x04\x8c\
+    \x01\x02.\x1aQ\x20Cancel\x20a\x20pending\x20upload\n\x20The\x20digest\
+    \x20field\x20is\x20used\x20for\x20the\x20uuid\x20in\x20this\x20rpc\x20ca\
+    ll\n\n\r\n\x05\x06\0\x02\x03\x01\x12\x04\x8c\x01\x06\x12\n\r\n\x05\x06\0\
+    \x02\x03\x02\x12\x04\x8c\x01\x14\x19\n\r\n\x05\x06\0\x02\x03\x03\x12\x04\
+    \x8c\x01$*\n>\n\x04\x06\0\x02\x04\x12\x04\x8f\x01\x026\x1a0\x20Finish\
+    \x20an\x20upload\x20once\x20its\x20blob\x20has\x20been\x20stored\n\n\r\n\
+    \x05\x06\0\x02\x04\x01\x12\x04\x8f\x01\x06\x12\n\r\n\x05\x06\0\x02\x04\
+    \x02\x12\x04\x8f\x01\x14!\n\r\n\x05\x06\0\x02\x04\x03\x12\x04\x8f\x01,2\
+    \nS\n\x04\x06\0\x02\x05\x12\x04\x93\x01\x020\x1aE\x20-----\x20Referrers\
+    \x20----------\n\x20Record\x20a\x20manifest\x20pushed\x20with\x20a\x20su\
+    bject\n\n\r\n\x05\x06\0\x02\x05\x01\x12\x04\x93\x01\x06\x11\n\r\n\x05\
+    \x06\0\x02\x05\x02\x12\x04\x93\x01\x13\x1b\n\r\n\x05\x06\0\x02\x05\x03\
+    \x12\x04\x93\x01&,\n+\n\x04\x06\0\x02\x06\x12\x04\x96\x01\x02@\x1a\x1d\
+    \x20The\x20referrers\x20of\x20a\x20manifest\n\n\r\n\x05\x06\0\x02\x06\
+    \x01\x12\x04\x96\x01\x06\x13\n\r\n\x05\x06\0\x02\x06\x02\x12\x04\x96\x01\
+    \x15%\n\r\n\x05\x06\0\x02\x06\x03\x12\x04\x96\x010<\n\x85\x01\n\x04\x06\
+    \0\x02\x07\x12\x04\x9a\x01\x02B\x1aw\x20Forget\x20a\x20deleted\x20manife\
+    st,\x20both\x20as\x20referrer\x20and\x20as\x20subject.\n\x20Returns\x20i\
+    ts\x20referrers,\x20which\x20the\x20caller\x20deletes\x20in\x20turn.\n\n\
+    \r\n\x05\x06\0\x02\x07\x01\x12\x04\x9a\x01\x06\x15\n\r\n\x05\x06\0\x02\
+    \x07\x02\x12\x04\x9a\x01\x17'\n\r\n\x05\x06\0\x02\x07\x03\x12\x04\x9a\
+    \x012>\nN\n\x04\x06\0\x02\x08\x12\x04\x9e\x01\x024\x1a@\x20-----\x20Even\
+    ts\x20----------\n\x20Stream\x20registry\x20events\x20as\x20they\x20happ\
+    en\n\n\r\n\x05\x06\0\x02\x08\x01\x12\x04\x9e\x01\x06\x0b\n\r\n\x05\x06\0\
+    \x02\x08\x02\x12\x04\x9e\x01\r\x19\n\r\n\x05\x06\0\x02\x08\x06\x12\x04\
+    \x9e\x01$*\n\r\n\x05\x06\0\x02\x08\x03\x12\x04\x9e\x01+0\nR\n\x04\x06\0\
+    \x02\t\x12\x04\xa1\x01\x02)\x1aD\x20Publish\x20an\x20event\x20which\x20h\
+    appened\x20in\x20the\x20frontend,\x20e.g.\x20a\x20tag\x20update\n\n\r\n\
+    \x05\x06\0\x02\t\x01\x12\x04\xa1\x01\x06\r\n\r\n\x05\x06\0\x02\t\x02\x12\
+    \x04\xa1\x01\x0f\x14\n\r\n\x05\x06\0\x02\t\x03\x12\x04\xa1\x01\x1f%\n\
+    \xa1\x01\n\x04\x06\0\x02\n\x12\x04\xa6\x01\x025\x1a\x92\x01\x20-----\x20\
+    Repair\x20----------\n\x20Stream\x20a\x20blob\x20from\x20the\x20first\
+    \x20peer\x20which\x20has\x20a\x20copy,\x20e.g.\x20to\x20replace\n\x20a\
+    \x20corrupted\x20one.\x20Fails\x20with\x20NOT_FOUND\x20if\x20none\x20has\
+    .\n\n\r\n\x05\x06\0\x02\n\x01\x12\x04\xa6\x01\x06\x0f\n\r\n\x05\x06\0\
+    \x02\n\x02\x12\x04\xa6\x01\x11\x16\n\r\n\x05\x06\0\x02\n\x06\x12\x04\xa6\
+    \x01!'\n\r\n\x05\x06\0\x02\n\x03\x12\x04\xa6\x01(1\nv\n\x04\x06\0\x02\
+    \x0b\x12\x04\xac\x01\x02,\x1a7\x20returns\x20a\x20list\x20of\x20all\x20U\
+    uids\x20currently\x20in\x20the\x20\x20backend\n2/\x20------------\x20Adm\
+    in\x20calls\x20--------------------\n\n\r\n\x05\x06\0\x02\x0b\x01\x12\
+    \x04\xac\x01\x06\x0e\n\r\n\x05\x06\0\x02\x0b\x02\x12\x04\xac\x01\x10\x15\
+    \n\r\n\x05\x06\0\x02\x0b\x03\x12\x04\xac\x01\x20(\n\x96\x01\n\x04\x06\0\
+    \x02\x0c\x12\x04\xb0\x01\x02)\x1a\x87\x01\x20The\x20id\x20of\x20the\x20d\
+    ata\x20directory\x20the\x20backend\x20stores\x20blobs\x20in,\x20in\x20te\
+    xt.\n\x20Frontends\x20write\x20to\x20the\x20same\x20one\x20and\x20refuse\
+    \x20backends\x20with\x20another.\n\n\r\n\x05\x06\0\x02\x0c\x01\x12\x04\
+    \xb0\x01\x06\r\n\r\n\x05\x06\0\x02\x0c\x02\x12\x04\xb0\x01\x0f\x14\n\r\n\
+    \x05\x06\0\x02\x0c\x03\x12\x04\xb0\x01\x1f%b\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
@@ -46,6 +46,55 @@ const METHOD_BACKEND_CANCEL_UPLOAD: ::grpcio::Method<super::backend::Layer, supe
     resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
 };
 
+const METHOD_BACKEND_COMMIT_UPLOAD: ::grpcio::Method<super::backend::CommittedBlob, super::backend::Result> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/commitUpload",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_ADD_REFERRER: ::grpcio::Method<super::backend::Referrer, super::backend::Result> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/addReferrer",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_LIST_REFERRERS: ::grpcio::Method<super::backend::ReferrersRequest, super::backend::ReferrerList> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/listReferrers",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_REMOVE_REFERRERS: ::grpcio::Method<super::backend::ReferrersRequest, super::backend::ReferrerList> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/removeReferrers",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_WATCH: ::grpcio::Method<super::backend::WatchRequest, super::backend::Event> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::ServerStreaming,
+    name: "/lycaon.Backend/watch",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_PUBLISH: ::grpcio::Method<super::backend::Event, super::backend::Result> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/publish",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_BACKEND_FETCH_BLOB: ::grpcio::Method<super::backend::Layer, super::backend::BlobChunk> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::ServerStreaming,
+    name: "/lycaon.Backend/fetchBlob",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
 const METHOD_BACKEND_GET_UUIDS: ::grpcio::Method<super::backend::Empty, super::backend::UuidList> = ::grpcio::Method {
     ty: ::grpcio::MethodType::Unary,
     name: "/lycaon.Backend/getUuids",
@@ -53,6 +102,13 @@ const METHOD_BACKEND_GET_UUIDS: ::grpcio::Method<super::backend::Empty, super::b
     resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
 };
 
+const METHOD_BACKEND_STORAGE: ::grpcio::Method<super::backend::Empty, super::backend::Result> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::Unary,
+    name: "/lycaon.Backend/storage",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
 pub struct BackendClient {
     client: ::grpcio::Client,
 }
@@ -64,85 +120,197 @@ impl BackendClient {
         }
     }
 
-    pub fn layer_exists_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::LayerExistsResult> {
+    pub fn layer_exists_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::LayerExistsResult> {
         self.client.unary_call(&METHOD_BACKEND_LAYER_EXISTS, req, opt)
     }
 
-    pub fn layer_exists(&self, req: super::backend::Layer) -> ::grpcio::Result<super::backend::LayerExistsResult> {
+    pub fn layer_exists(&self, req: &super::backend::Layer) -> ::grpcio::Result<super::backend::LayerExistsResult> {
         self.layer_exists_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn layer_exists_async_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::backend::LayerExistsResult> {
+    pub fn layer_exists_async_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::LayerExistsResult>> {
         self.client.unary_call_async(&METHOD_BACKEND_LAYER_EXISTS, req, opt)
     }
 
-    pub fn layer_exists_async(&self, req: super::backend::Layer) -> ::grpcio::ClientUnaryReceiver<super::backend::LayerExistsResult> {
+    pub fn layer_exists_async(&self, req: &super::backend::Layer) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::LayerExistsResult>> {
         self.layer_exists_async_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn gen_uuid_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::GenUuidResult> {
+    pub fn gen_uuid_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::GenUuidResult> {
         self.client.unary_call(&METHOD_BACKEND_GEN_UUID, req, opt)
     }
 
-    pub fn gen_uuid(&self, req: super::backend::Layer) -> ::grpcio::Result<super::backend::GenUuidResult> {
+    pub fn gen_uuid(&self, req: &super::backend::Layer) -> ::grpcio::Result<super::backend::GenUuidResult> {
         self.gen_uuid_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn gen_uuid_async_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::backend::GenUuidResult> {
+    pub fn gen_uuid_async_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::GenUuidResult>> {
         self.client.unary_call_async(&METHOD_BACKEND_GEN_UUID, req, opt)
     }
 
-    pub fn gen_uuid_async(&self, req: super::backend::Layer) -> ::grpcio::ClientUnaryReceiver<super::backend::GenUuidResult> {
+    pub fn gen_uuid_async(&self, req: &super::backend::Layer) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::GenUuidResult>> {
         self.gen_uuid_async_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn uuid_exists_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+    pub fn uuid_exists_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
         self.client.unary_call(&METHOD_BACKEND_UUID_EXISTS, req, opt)
     }
 
-    pub fn uuid_exists(&self, req: super::backend::Layer) -> ::grpcio::Result<super::backend::Result> {
+    pub fn uuid_exists(&self, req: &super::backend::Layer) -> ::grpcio::Result<super::backend::Result> {
         self.uuid_exists_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn uuid_exists_async_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::backend::Result> {
+    pub fn uuid_exists_async_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
         self.client.unary_call_async(&METHOD_BACKEND_UUID_EXISTS, req, opt)
     }
 
-    pub fn uuid_exists_async(&self, req: super::backend::Layer) -> ::grpcio::ClientUnaryReceiver<super::backend::Result> {
+    pub fn uuid_exists_async(&self, req: &super::backend::Layer) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
         self.uuid_exists_async_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn cancel_upload_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+    pub fn cancel_upload_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
         self.client.unary_call(&METHOD_BACKEND_CANCEL_UPLOAD, req, opt)
     }
 
-    pub fn cancel_upload(&self, req: super::backend::Layer) -> ::grpcio::Result<super::backend::Result> {
+    pub fn cancel_upload(&self, req: &super::backend::Layer) -> ::grpcio::Result<super::backend::Result> {
         self.cancel_upload_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn cancel_upload_async_opt(&self, req: super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::backend::Result> {
+    pub fn cancel_upload_async_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
         self.client.unary_call_async(&METHOD_BACKEND_CANCEL_UPLOAD, req, opt)
     }
 
-    pub fn cancel_upload_async(&self, req: super::backend::Layer) -> ::grpcio::ClientUnaryReceiver<super::backend::Result> {
+    pub fn cancel_upload_async(&self, req: &super::backend::Layer) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
         self.cancel_upload_async_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn get_uuids_opt(&self, req: super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::UuidList> {
+    pub fn commit_upload_opt(&self, req: &super::backend::CommittedBlob, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+        self.client.unary_call(&METHOD_BACKEND_COMMIT_UPLOAD, req, opt)
+    }
+
+    pub fn commit_upload(&self, req: &super::backend::CommittedBlob) -> ::grpcio::Result<super::backend::Result> {
+        self.commit_upload_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn commit_upload_async_opt(&self, req: &super::backend::CommittedBlob, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.client.unary_call_async(&METHOD_BACKEND_COMMIT_UPLOAD, req, opt)
+    }
+
+    pub fn commit_upload_async(&self, req: &super::backend::CommittedBlob) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.commit_upload_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn add_referrer_opt(&self, req: &super::backend::Referrer, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+        self.client.unary_call(&METHOD_BACKEND_ADD_REFERRER, req, opt)
+    }
+
+    pub fn add_referrer(&self, req: &super::backend::Referrer) -> ::grpcio::Result<super::backend::Result> {
+        self.add_referrer_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn add_referrer_async_opt(&self, req: &super::backend::Referrer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.client.unary_call_async(&METHOD_BACKEND_ADD_REFERRER, req, opt)
+    }
+
+    pub fn add_referrer_async(&self, req: &super::backend::Referrer) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.add_referrer_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn list_referrers_opt(&self, req: &super::backend::ReferrersRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::ReferrerList> {
+        self.client.unary_call(&METHOD_BACKEND_LIST_REFERRERS, req, opt)
+    }
+
+    pub fn list_referrers(&self, req: &super::backend::ReferrersRequest) -> ::grpcio::Result<super::backend::ReferrerList> {
+        self.list_referrers_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn list_referrers_async_opt(&self, req: &super::backend::ReferrersRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::ReferrerList>> {
+        self.client.unary_call_async(&METHOD_BACKEND_LIST_REFERRERS, req, opt)
+    }
+
+    pub fn list_referrers_async(&self, req: &super::backend::ReferrersRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::ReferrerList>> {
+        self.list_referrers_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn remove_referrers_opt(&self, req: &super::backend::ReferrersRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::ReferrerList> {
+        self.client.unary_call(&METHOD_BACKEND_REMOVE_REFERRERS, req, opt)
+    }
+
+    pub fn remove_referrers(&self, req: &super::backend::ReferrersRequest) -> ::grpcio::Result<super::backend::ReferrerList> {
+        self.remove_referrers_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn remove_referrers_async_opt(&self, req: &super::backend::ReferrersRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::ReferrerList>> {
+        self.client.unary_call_async(&METHOD_BACKEND_REMOVE_REFERRERS, req, opt)
+    }
+
+    pub fn remove_referrers_async(&self, req: &super::backend::ReferrersRequest) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::ReferrerList>> {
+        self.remove_referrers_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn watch_opt(&self, req: &super::backend::WatchRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::backend::Event>> {
+        self.client.server_streaming(&METHOD_BACKEND_WATCH, req, opt)
+    }
+
+    pub fn watch(&self, req: &super::backend::WatchRequest) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::backend::Event>> {
+        self.watch_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn publish_opt(&self, req: &super::backend::Event, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+        self.client.unary_call(&METHOD_BACKEND_PUBLISH, req, opt)
+    }
+
+    pub fn publish(&self, req: &super::backend::Event) -> ::grpcio::Result<super::backend::Result> {
+        self.publish_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn publish_async_opt(&self, req: &super::backend::Event, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.client.unary_call_async(&METHOD_BACKEND_PUBLISH, req, opt)
+    }
+
+    pub fn publish_async(&self, req: &super::backend::Event) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.publish_async_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn fetch_blob_opt(&self, req: &super::backend::Layer, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::backend::BlobChunk>> {
+        self.client.server_streaming(&METHOD_BACKEND_FETCH_BLOB, req, opt)
+    }
+
+    pub fn fetch_blob(&self, req: &super::backend::Layer) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::backend::BlobChunk>> {
+        self.fetch_blob_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn get_uuids_opt(&self, req: &super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::UuidList> {
         self.client.unary_call(&METHOD_BACKEND_GET_UUIDS, req, opt)
     }
 
-    pub fn get_uuids(&self, req: super::backend::Empty) -> ::grpcio::Result<super::backend::UuidList> {
+    pub fn get_uuids(&self, req: &super::backend::Empty) -> ::grpcio::Result<super::backend::UuidList> {
         self.get_uuids_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn get_uuids_async_opt(&self, req: super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::backend::UuidList> {
+    pub fn get_uuids_async_opt(&self, req: &super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::UuidList>> {
         self.client.unary_call_async(&METHOD_BACKEND_GET_UUIDS, req, opt)
     }
 
-    pub fn get_uuids_async(&self, req: super::backend::Empty) -> ::grpcio::ClientUnaryReceiver<super::backend::UuidList> {
+    pub fn get_uuids_async(&self, req: &super::backend::Empty) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::UuidList>> {
         self.get_uuids_async_opt(req, ::grpcio::CallOption::default())
     }
+
+    pub fn storage_opt(&self, req: &super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::backend::Result> {
+        self.client.unary_call(&METHOD_BACKEND_STORAGE, req, opt)
+    }
+
+    pub fn storage(&self, req: &super::backend::Empty) -> ::grpcio::Result<super::backend::Result> {
+        self.storage_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn storage_async_opt(&self, req: &super::backend::Empty, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.client.unary_call_async(&METHOD_BACKEND_STORAGE, req, opt)
+    }
+
+    pub fn storage_async(&self, req: &super::backend::Empty) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::backend::Result>> {
+        self.storage_async_opt(req, ::grpcio::CallOption::default())
+    }
     pub fn spawn<F>(&self, f: F) where F: ::futures::Future<Item = (), Error = ()> + Send + 'static {
         self.client.spawn(f)
     }
@@ -153,7 +321,15 @@ pub trait Backend {
     fn gen_uuid(&self, ctx: ::grpcio::RpcContext, req: super::backend::Layer, sink: ::grpcio::UnarySink<super::backend::GenUuidResult>);
     fn uuid_exists(&self, ctx: ::grpcio::RpcContext, req: super::backend::Layer, sink: ::grpcio::UnarySink<super::backend::Result>);
     fn cancel_upload(&self, ctx: ::grpcio::RpcContext, req: super::backend::Layer, sink: ::grpcio::UnarySink<super::backend::Result>);
+    fn commit_upload(&self, ctx: ::grpcio::RpcContext, req: super::backend::CommittedBlob, sink: ::grpcio::UnarySink<super::backend::Result>);
+    fn add_referrer(&self, ctx: ::grpcio::RpcContext, req: super::backend::Referrer, sink: ::grpcio::UnarySink<super::backend::Result>);
+    fn list_referrers(&self, ctx: ::grpcio::RpcContext, req: super::backend::ReferrersRequest, sink: ::grpcio::UnarySink<super::backend::ReferrerList>);
+    fn remove_referrers(&self, ctx: ::grpcio::RpcContext, req: super::backend::ReferrersRequest, sink: ::grpcio::UnarySink<super::backend::ReferrerList>);
+    fn watch(&self, ctx: ::grpcio::RpcContext, req: super::backend::WatchRequest, sink: ::grpcio::ServerStreamingSink<super::backend::Event>);
+    fn publish(&self, ctx: ::grpcio::RpcContext, req: super::backend::Event, sink: ::grpcio::UnarySink<super::backend::Result>);
+    fn fetch_blob(&self, ctx: ::grpcio::RpcContext, req: super::backend::Layer, sink: ::grpcio::ServerStreamingSink<super::backend::BlobChunk>);
     fn get_uuids(&self, ctx: ::grpcio::RpcContext, req: super::backend::Empty, sink: ::grpcio::UnarySink<super::backend::UuidList>);
+    fn storage(&self, ctx: ::grpcio::RpcContext, req: super::backend::Empty, sink: ::grpcio::UnarySink<super::backend::Result>);
 }
 
 pub fn create_backend<S: Backend + Send + Clone + 'static>(s: S) -> ::grpcio::Service {
@@ -175,8 +351,40 @@ pub fn create_backend<S: Backend + Send + Clone + 'static>(s: S) -> ::grpcio::Se
         instance.cancel_upload(ctx, req, resp)
     });
     let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_COMMIT_UPLOAD, move |ctx, req, resp| {
+        instance.commit_upload(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_ADD_REFERRER, move |ctx, req, resp| {
+        instance.add_referrer(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_LIST_REFERRERS, move |ctx, req, resp| {
+        instance.list_referrers(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_REMOVE_REFERRERS, move |ctx, req, resp| {
+        instance.remove_referrers(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_server_streaming_handler(&METHOD_BACKEND_WATCH, move |ctx, req, resp| {
+        instance.watch(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_PUBLISH, move |ctx, req, resp| {
+        instance.publish(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_server_streaming_handler(&METHOD_BACKEND_FETCH_BLOB, move |ctx, req, resp| {
+        instance.fetch_blob(ctx, req, resp)
+    });
+    let instance = s.clone();
     builder = builder.add_unary_handler(&METHOD_BACKEND_GET_UUIDS, move |ctx, req, resp| {
         instance.get_uuids(ctx, req, resp)
     });
+    let instance = s.clone();
+    builder = builder.add_unary_handler(&METHOD_BACKEND_STORAGE, move |ctx, req, resp| {
+        instance.storage(ctx, req, resp)
+    });
     builder.build()
 }
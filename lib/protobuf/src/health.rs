@@ -0,0 +1,488 @@
+// This file is generated. Do not edit
+// @generated
+
+// https://github.com/Manishearth/rust-clippy/issues/702
+#![allow(unknown_lints)]
+#![allow(clippy)]
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+
+#![allow(box_pointers)]
+#![allow(dead_code)]
+#![allow(missing_docs)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+#![allow(trivial_casts)]
+#![allow(unsafe_code)]
+#![allow(unused_imports)]
+#![allow(unused_results)]
+
+use protobuf::Message as Message_imported_for_functions;
+use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
+
+#[derive(PartialEq,Clone,Default)]
+pub struct HealthCheckRequest {
+    // message fields
+    pub service: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for HealthCheckRequest {}
+
+impl HealthCheckRequest {
+    pub fn new() -> HealthCheckRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static HealthCheckRequest {
+        static mut instance: ::protobuf::lazy::Lazy<HealthCheckRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const HealthCheckRequest,
+        };
+        unsafe {
+            instance.get(HealthCheckRequest::new)
+        }
+    }
+
+    // string service = 1;
+
+    pub fn clear_service(&mut self) {
+        self.service.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_service(&mut self, v: ::std::string::String) {
+        self.service = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_service(&mut self) -> &mut ::std::string::String {
+        &mut self.service
+    }
+
+    // Take field
+    pub fn take_service(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.service, ::std::string::String::new())
+    }
+
+    pub fn get_service(&self) -> &str {
+        &self.service
+    }
+
+    fn get_service_for_reflect(&self) -> &::std::string::String {
+        &self.service
+    }
+
+    fn mut_service_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.service
+    }
+}
+
+impl ::protobuf::Message for HealthCheckRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.service)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.service.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.service);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.service.is_empty() {
+            os.write_string(1, &self.service)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for HealthCheckRequest {
+    fn new() -> HealthCheckRequest {
+        HealthCheckRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<HealthCheckRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "service",
+                    HealthCheckRequest::get_service_for_reflect,
+                    HealthCheckRequest::mut_service_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<HealthCheckRequest>(
+                    "HealthCheckRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for HealthCheckRequest {
+    fn clear(&mut self) {
+        self.clear_service();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HealthCheckRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HealthCheckRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct HealthCheckResponse {
+    // message fields
+    pub status: HealthCheckResponse_ServingStatus,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for HealthCheckResponse {}
+
+impl HealthCheckResponse {
+    pub fn new() -> HealthCheckResponse {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static HealthCheckResponse {
+        static mut instance: ::protobuf::lazy::Lazy<HealthCheckResponse> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const HealthCheckResponse,
+        };
+        unsafe {
+            instance.get(HealthCheckResponse::new)
+        }
+    }
+
+    // .grpc.health.v1.HealthCheckResponse.ServingStatus status = 1;
+
+    pub fn clear_status(&mut self) {
+        self.status = HealthCheckResponse_ServingStatus::UNKNOWN;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: HealthCheckResponse_ServingStatus) {
+        self.status = v;
+    }
+
+    pub fn get_status(&self) -> HealthCheckResponse_ServingStatus {
+        self.status
+    }
+
+    fn get_status_for_reflect(&self) -> &HealthCheckResponse_ServingStatus {
+        &self.status
+    }
+
+    fn mut_status_for_reflect(&mut self) -> &mut HealthCheckResponse_ServingStatus {
+        &mut self.status
+    }
+}
+
+impl ::protobuf::Message for HealthCheckResponse {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.status = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.status != HealthCheckResponse_ServingStatus::UNKNOWN {
+            my_size += ::protobuf::rt::enum_size(1, self.status);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if self.status != HealthCheckResponse_ServingStatus::UNKNOWN {
+            os.write_enum(1, self.status.value())?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for HealthCheckResponse {
+    fn new() -> HealthCheckResponse {
+        HealthCheckResponse::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<HealthCheckResponse>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<HealthCheckResponse_ServingStatus>>(
+                    "status",
+                    HealthCheckResponse::get_status_for_reflect,
+                    HealthCheckResponse::mut_status_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<HealthCheckResponse>(
+                    "HealthCheckResponse",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for HealthCheckResponse {
+    fn clear(&mut self) {
+        self.clear_status();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for HealthCheckResponse {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HealthCheckResponse {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum HealthCheckResponse_ServingStatus {
+    UNKNOWN = 0,
+    SERVING = 1,
+    NOT_SERVING = 2,
+    SERVICE_UNKNOWN = 3,
+}
+
+impl ::protobuf::ProtobufEnum for HealthCheckResponse_ServingStatus {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<HealthCheckResponse_ServingStatus> {
+        match value {
+            0 => ::std::option::Option::Some(HealthCheckResponse_ServingStatus::UNKNOWN),
+            1 => ::std::option::Option::Some(HealthCheckResponse_ServingStatus::SERVING),
+            2 => ::std::option::Option::Some(HealthCheckResponse_ServingStatus::NOT_SERVING),
+            3 => ::std::option::Option::Some(HealthCheckResponse_ServingStatus::SERVICE_UNKNOWN),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [HealthCheckResponse_ServingStatus] = &[
+            HealthCheckResponse_ServingStatus::UNKNOWN,
+            HealthCheckResponse_ServingStatus::SERVING,
+            HealthCheckResponse_ServingStatus::NOT_SERVING,
+            HealthCheckResponse_ServingStatus::SERVICE_UNKNOWN,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<HealthCheckResponse_ServingStatus>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("HealthCheckResponse_ServingStatus", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for HealthCheckResponse_ServingStatus {
+}
+
+impl ::std::default::Default for HealthCheckResponse_ServingStatus {
+    fn default() -> Self {
+        HealthCheckResponse_ServingStatus::UNKNOWN
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for HealthCheckResponse_ServingStatus {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+static file_descriptor_proto_data: &'static [u8] = b"\
+    \n\x10src/health.proto\x12\x0egrpc.health.v1\".\n\x12HealthCheckRequest\
+    \x12\x18\n\x07service\x18\x01\x20\x01(\tR\x07service\"\xb1\x01\n\x13Heal\
+    thCheckResponse\x12I\n\x06status\x18\x01\x20\x01(\x0e21.grpc.health.v1.H\
+    ealthCheckResponse.ServingStatusR\x06status\"O\n\rServingStatus\x12\x0b\
+    \n\x07UNKNOWN\x10\0\x12\x0b\n\x07SERVING\x10\x01\x12\x0f\n\x0bNOT_SERVIN\
+    G\x10\x02\x12\x13\n\x0fSERVICE_UNKNOWN\x10\x032\xb2\x01\n\x06Health\x12R\
+    \n\x05Check\x12\".grpc.health.v1.HealthCheckRequest\x1a#.grpc.health.v1.\
+    HealthCheckResponse\"\0\x12T\n\x05Watch\x12\".grpc.health.v1.HealthCheck\
+    Request\x1a#.grpc.health.v1.HealthCheckResponse\"\00\x01J\xc6\x05\n\x06\
+    \x12\x04\0\0\x18\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\ni\n\x01\x02\x12\
+    \x03\x04\x08\x16\x1a_\x20The\x20standard\x20gRPC\x20health\x20checking\
+    \x20protocol,\x20so\x20gRPC-aware\x20load\n\x20balancers\x20can\x20check\
+    \x20backends.\n\n\n\n\x02\x04\0\x12\x04\x06\0\x08\x01\n\n\n\x03\x04\0\
+    \x01\x12\x03\x06\x08\x1a\n\x0b\n\x04\x04\0\x02\0\x12\x03\x07\x02\x15\n\r\
+    \n\x05\x04\0\x02\0\x04\x12\x04\x07\x02\x06\x1c\n\x0c\n\x05\x04\0\x02\0\
+    \x05\x12\x03\x07\x02\x08\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x07\t\x10\n\
+    \x0c\n\x05\x04\0\x02\0\x03\x12\x03\x07\x13\x14\n\n\n\x02\x04\x01\x12\x04\
+    \n\0\x13\x01\n\n\n\x03\x04\x01\x01\x12\x03\n\x08\x1b\n\x0c\n\x04\x04\x01\
+    \x04\0\x12\x04\x0b\x02\x11\x03\n\x0c\n\x05\x04\x01\x04\0\x01\x12\x03\x0b\
+    \x07\x14\n\r\n\x06\x04\x01\x04\0\x02\0\x12\x03\x0c\x04\x10\n\x0e\n\x07\
+    \x04\x01\x04\0\x02\0\x01\x12\x03\x0c\x04\x0b\n\x0e\n\x07\x04\x01\x04\0\
+    \x02\0\x02\x12\x03\x0c\x0e\x0f\n\r\n\x06\x04\x01\x04\0\x02\x01\x12\x03\r\
+    \x04\x10\n\x0e\n\x07\x04\x01\x04\0\x02\x01\x01\x12\x03\r\x04\x0b\n\x0e\n\
+    \x07\x04\x01\x04\0\x02\x01\x02\x12\x03\r\x0e\x0f\n\r\n\x06\x04\x01\x04\0\
+    \x02\x02\x12\x03\x0e\x04\x14\n\x0e\n\x07\x04\x01\x04\0\x02\x02\x01\x12\
+    \x03\x0e\x04\x0f\n\x0e\n\x07\x04\x01\x04\0\x02\x02\x02\x12\x03\x0e\x12\
+    \x13\n.\n\x06\x04\x01\x04\0\x02\x03\x12\x03\x10\x04\x18\x1a\x1f\x20Used\
+    \x20only\x20by\x20the\x20Watch\x20method\n\n\x0e\n\x07\x04\x01\x04\0\x02\
+    \x03\x01\x12\x03\x10\x04\x13\n\x0e\n\x07\x04\x01\x04\0\x02\x03\x02\x12\
+    \x03\x10\x16\x17\n\x0b\n\x04\x04\x01\x02\0\x12\x03\x12\x02\x1b\n\r\n\x05\
+    \x04\x01\x02\0\x04\x12\x04\x12\x02\x11\x03\n\x0c\n\x05\x04\x01\x02\0\x06\
+    \x12\x03\x12\x02\x0f\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\x12\x10\x16\n\
+    \x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x12\x19\x1a\n\n\n\x02\x06\0\x12\x04\
+    \x15\0\x18\x01\n\n\n\x03\x06\0\x01\x12\x03\x15\x08\x0e\n\x0b\n\x04\x06\0\
+    \x02\0\x12\x03\x16\x02A\n\x0c\n\x05\x06\0\x02\0\x01\x12\x03\x16\x06\x0b\
+    \n\x0c\n\x05\x06\0\x02\0\x02\x12\x03\x16\r\x1f\n\x0c\n\x05\x06\0\x02\0\
+    \x03\x12\x03\x16*=\n\x0b\n\x04\x06\0\x02\x01\x12\x03\x17\x02H\n\x0c\n\
+    \x05\x06\0\x02\x01\x01\x12\x03\x17\x06\x0b\n\x0c\n\x05\x06\0\x02\x01\x02\
+    \x12\x03\x17\r\x1f\n\x0c\n\x05\x06\0\x02\x01\x06\x12\x03\x17*0\n\x0c\n\
+    \x05\x06\0\x02\x01\x03\x12\x03\x171Db\x06proto3\
+";
+
+static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
+    lock: ::protobuf::lazy::ONCE_INIT,
+    ptr: 0 as *const ::protobuf::descriptor::FileDescriptorProto,
+};
+
+fn parse_descriptor_proto() -> ::protobuf::descriptor::FileDescriptorProto {
+    ::protobuf::parse_from_bytes(file_descriptor_proto_data).unwrap()
+}
+
+pub fn file_descriptor_proto() -> &'static ::protobuf::descriptor::FileDescriptorProto {
+    unsafe {
+        file_descriptor_proto_lazy.get(|| {
+            parse_descriptor_proto()
+        })
+    }
+}
@@ -4,5 +4,7 @@ extern crate futures;
 
 pub mod backend;
 pub mod backend_grpc;
+pub mod health;
+pub mod health_grpc;
 pub mod peer;
 pub mod peer_grpc;
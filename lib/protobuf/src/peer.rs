@@ -371,6 +371,7 @@ pub struct ORSetDelta {
     pub deltatype: DeltaType,
     pub element: ::std::string::String,
     pub dots: ::protobuf::RepeatedField<Dot>,
+    pub produced: i64,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -483,6 +484,29 @@ impl ORSetDelta {
     fn mut_dots_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Dot> {
         &mut self.dots
     }
+
+    // int64 produced = 4;
+
+    pub fn clear_produced(&mut self) {
+        self.produced = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_produced(&mut self, v: i64) {
+        self.produced = v;
+    }
+
+    pub fn get_produced(&self) -> i64 {
+        self.produced
+    }
+
+    fn get_produced_for_reflect(&self) -> &i64 {
+        &self.produced
+    }
+
+    fn mut_produced_for_reflect(&mut self) -> &mut i64 {
+        &mut self.produced
+    }
 }
 
 impl ::protobuf::Message for ORSetDelta {
@@ -512,6 +536,13 @@ impl ::protobuf::Message for ORSetDelta {
                 3 => {
                     ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.dots)?;
                 },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.produced = tmp;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -534,6 +565,9 @@ impl ::protobuf::Message for ORSetDelta {
             let len = value.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
+        if self.produced != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.produced, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -551,6 +585,9 @@ impl ::protobuf::Message for ORSetDelta {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         };
+        if self.produced != 0 {
+            os.write_int64(4, self.produced)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -610,6 +647,11 @@ impl ::protobuf::MessageStatic for ORSetDelta {
                     ORSetDelta::get_dots_for_reflect,
                     ORSetDelta::mut_dots_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "produced",
+                    ORSetDelta::get_produced_for_reflect,
+                    ORSetDelta::mut_produced_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<ORSetDelta>(
                     "ORSetDelta",
                     fields,
@@ -625,6 +667,7 @@ impl ::protobuf::Clear for ORSetDelta {
         self.clear_deltatype();
         self.clear_element();
         self.clear_dots();
+        self.clear_produced();
         self.unknown_fields.clear();
     }
 }
@@ -985,6 +1028,358 @@ impl ::protobuf::reflect::ProtobufValue for Heartbeat {
     }
 }
 
+#[derive(PartialEq,Clone,Default)]
+pub struct BlobRequest {
+    // message fields
+    pub digest: ::std::string::String,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for BlobRequest {}
+
+impl BlobRequest {
+    pub fn new() -> BlobRequest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static BlobRequest {
+        static mut instance: ::protobuf::lazy::Lazy<BlobRequest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BlobRequest,
+        };
+        unsafe {
+            instance.get(BlobRequest::new)
+        }
+    }
+
+    // string digest = 1;
+
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::string::String) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.digest, ::std::string::String::new())
+    }
+
+    pub fn get_digest(&self) -> &str {
+        &self.digest
+    }
+
+    fn get_digest_for_reflect(&self) -> &::std::string::String {
+        &self.digest
+    }
+
+    fn mut_digest_for_reflect(&mut self) -> &mut ::std::string::String {
+        &mut self.digest
+    }
+}
+
+impl ::protobuf::Message for BlobRequest {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.digest)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.digest);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.digest.is_empty() {
+            os.write_string(1, &self.digest)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for BlobRequest {
+    fn new() -> BlobRequest {
+        BlobRequest::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<BlobRequest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "digest",
+                    BlobRequest::get_digest_for_reflect,
+                    BlobRequest::mut_digest_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<BlobRequest>(
+                    "BlobRequest",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for BlobRequest {
+    fn clear(&mut self) {
+        self.clear_digest();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BlobRequest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BlobRequest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct BlobData {
+    // message fields
+    pub data: ::std::vec::Vec<u8>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for BlobData {}
+
+impl BlobData {
+    pub fn new() -> BlobData {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static BlobData {
+        static mut instance: ::protobuf::lazy::Lazy<BlobData> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const BlobData,
+        };
+        unsafe {
+            instance.get(BlobData::new)
+        }
+    }
+
+    // bytes data = 1;
+
+    pub fn clear_data(&mut self) {
+        self.data.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_data(&mut self, v: ::std::vec::Vec<u8>) {
+        self.data = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_data(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.data
+    }
+
+    // Take field
+    pub fn take_data(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.data, ::std::vec::Vec::new())
+    }
+
+    pub fn get_data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn get_data_for_reflect(&self) -> &::std::vec::Vec<u8> {
+        &self.data
+    }
+
+    fn mut_data_for_reflect(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.data
+    }
+}
+
+impl ::protobuf::Message for BlobData {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.data)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.data.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(1, &self.data);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if !self.data.is_empty() {
+            os.write_bytes(1, &self.data)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for BlobData {
+    fn new() -> BlobData {
+        BlobData::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<BlobData>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "data",
+                    BlobData::get_data_for_reflect,
+                    BlobData::mut_data_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<BlobData>(
+                    "BlobData",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for BlobData {
+    fn clear(&mut self) {
+        self.clear_data();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for BlobData {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BlobData {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 #[derive(Clone,PartialEq,Eq,Debug,Hash)]
 pub enum DeltaType {
     ADD = 0,
@@ -1043,91 +1438,123 @@ impl ::protobuf::reflect::ProtobufValue for DeltaType {
 static file_descriptor_proto_data: &'static [u8] = b"\
     \n\x0esrc/peer.proto\x12\x06lycaon\"5\n\x03Dot\x12\x14\n\x05actor\x18\
     \x01\x20\x01(\tR\x05actor\x12\x18\n\x07counter\x18\x02\x20\x01(\x04R\x07\
-    counter\"\x0f\n\rORSetFullSync\"x\n\nORSetDelta\x12/\n\tdeltatype\x18\
-    \x01\x20\x01(\x0e2\x11.lycaon.DeltaTypeR\tdeltatype\x12\x18\n\x07element\
-    \x18\x02\x20\x01(\tR\x07element\x12\x1f\n\x04dots\x18\x03\x20\x03(\x0b2\
-    \x0b.lycaon.DotR\x04dots\"\\\n\x0fORSetDeltaReply\x12/\n\tdeltatype\x18\
-    \x01\x20\x01(\x0e2\x11.lycaon.DeltaTypeR\tdeltatype\x12\x18\n\x07element\
-    \x18\x02\x20\x01(\tR\x07element\"\x0b\n\tHeartbeat*\x20\n\tDeltaType\x12\
-    \x07\n\x03ADD\x10\0\x12\n\n\x06REMOVE\x10\x012w\n\x04Peer\x123\n\theartb\
-    eat\x12\x11.lycaon.Heartbeat\x1a\x11.lycaon.Heartbeat\"\0\x12:\n\tdeltaS\
-    ync\x12\x12.lycaon.ORSetDelta\x1a\x17.lycaon.ORSetDeltaReply\"\0J\x8b\
-    \x13\n\x06\x12\x04\0\0I\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\
-    \x02\x12\x03\x02\x08\x0e\n\x9f\x06\n\x02\x04\0\x12\x04\x18\0\x1b\x01\x1a\
-    \x81\x01\x20Generic\x20Types\n\n\x20These\x20are\x20types\x20that\x20are\
-    \x20reused\x20throughout\x20the\x20specification\x20and\n\x20are\x20defi\
-    ned\x20at\x20the\x20top\x20for\x20ease\x20of\x20discovery.\n2\x8e\x05\
-    \x20Looking\x20at\x20the\x20ORSet\x20library\x20which\x20we\x20are\x20us\
-    ing\x20for\x20our\x20CRDT\n\x20We\x20have\x20two\x20kinds\x20of\x20messa\
-    ges\x20that\x20relate\x20to\x20the\x20propogation\x20of\n\x20data.\n\n\
-    \x201.\x20When\x20a\x20new\x20Instance\x20comes\x20online\x20and\x20requ\
-    ests\x20a\x20sync.\n\x20\x20\x20-\x20This\x20could\x20be\x20implemented\
-    \x20using\x20no.\x202\x20and\x20just\x20applying\x20all\n\x20\x20\x20\
-    \x20\x20deltas\x20from\x20an\x20empty\x20ORSet.\n\x202.\x20When\x20an\
-    \x20existing\x20instance\x20needs\x20to\x20send\x20a\x20delta\x20to\x20l\
-    istening\x20instances.\n\n\x20The\x20second\x20set\x20of\x20messages\x20\
-    relates\x20to\x20locating\x20and\x20downloading\n\x20information\x20from\
-    \x20other\x20services.\x20This\x20includes\x20(non-exhaustive):\n\n\x20-\
-    \x20Querying\x20a\x20layers\x20existence\x20on\x20a\x20remote\x20instanc\
-    e\n\x20-\x20Querying\x20permissions\x20regarding\x20a\x20layer\n\x20-\
-    \x20Propogating\x20any\x20state\x20changes\x20(such\x20as\x20deletion\
-    \x20requests)\n\n\n\n\x03\x04\0\x01\x12\x03\x18\x08\x0b\n\x0b\n\x04\x04\
-    \0\x02\0\x12\x03\x19\x02\x13\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x19\x02\
-    \x18\r\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x19\x02\x08\n\x0c\n\x05\x04\0\
-    \x02\0\x01\x12\x03\x19\t\x0e\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x19\x11\
-    \x12\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x1a\x02\x15\n\r\n\x05\x04\0\x02\
-    \x01\x04\x12\x04\x1a\x02\x19\x13\n\x0c\n\x05\x04\0\x02\x01\x05\x12\x03\
-    \x1a\x02\x08\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x1a\t\x10\n\x0c\n\x05\
-    \x04\0\x02\x01\x03\x12\x03\x1a\x13\x14\ny\n\x02\x05\0\x12\x04!\0$\x01\
-    \x1am\x20Describe\x20the\x20type\x20of\x20Delta\x20Message\n\n\x20Add:\
-    \x20Addition\x20of\x20a\x20key\x20to\x20the\x20set\n\x20Remove:\x20Delet\
-    ion\x20of\x20a\x20tag\x20for\x20a\x20key\n\n\n\n\x03\x05\0\x01\x12\x03!\
-    \x05\x0e\n\x0b\n\x04\x05\0\x02\0\x12\x03\"\x02\n\n\x0c\n\x05\x05\0\x02\0\
-    \x01\x12\x03\"\x02\x05\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\"\x08\t\n\x0b\
-    \n\x04\x05\0\x02\x01\x12\x03#\x02\r\n\x0c\n\x05\x05\0\x02\x01\x01\x12\
-    \x03#\x02\x08\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03#\x0b\x0c\n^\n\x02\
-    \x04\x01\x12\x03)\0\x18\x1aS\x20ORSet\x20messages\n\n\x20This\x20message\
-    \x20is\x20a\x20sync\x20of\x20the\x20entire\x20current\x20state\x20of\x20\
-    the\x20ORSet.\n\n\n\n\x03\x04\x01\x01\x12\x03)\x08\x15\n\x99\x01\n\x02\
-    \x04\x02\x12\x04/\03\x01\x1a\x8c\x01\x20This\x20message\x20represents\
-    \x20a\x20single\x20Delta\x20of\x20the\x20ORSet.\n\n\x20It\x20is\x20sent\
-    \x20out\x20on\x20every\x20update\x20of\x20the\x20data-structure,\x20and\
-    \n\x20propogated\x20to\x20all\x20peers.\n\n\n\n\x03\x04\x02\x01\x12\x03/\
-    \x08\x12\n\x0b\n\x04\x04\x02\x02\0\x12\x030\x02\x1a\n\r\n\x05\x04\x02\
-    \x02\0\x04\x12\x040\x02/\x14\n\x0c\n\x05\x04\x02\x02\0\x06\x12\x030\x02\
-    \x0b\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x030\x0c\x15\n\x0c\n\x05\x04\x02\
-    \x02\0\x03\x12\x030\x18\x19\n\x0b\n\x04\x04\x02\x02\x01\x12\x031\x02\x15\
-    \n\r\n\x05\x04\x02\x02\x01\x04\x12\x041\x020\x1a\n\x0c\n\x05\x04\x02\x02\
-    \x01\x05\x12\x031\x02\x08\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x031\t\x10\
-    \n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x031\x13\x14\n\x0b\n\x04\x04\x02\
-    \x02\x02\x12\x032\x02\x18\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x032\x02\n\
-    \n\x0c\n\x05\x04\x02\x02\x02\x06\x12\x032\x0b\x0e\n\x0c\n\x05\x04\x02\
-    \x02\x02\x01\x12\x032\x0f\x13\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x032\
-    \x16\x17\n\xb9\x01\n\x02\x04\x03\x12\x048\0;\x01\x1a\xac\x01\x20This\x20\
-    message\x20represents\x20a\x20reply\x20to\x20a\x20sent\x20delta.\n\x20Cu\
-    rrently\x20this\x20message\x20simply\x20returns\x20the\x20DeltaType\x20a\
-    nd\x20the\x20element\n\x20so\x20the\x20client\x20can\x20verify\x20a\x20s\
-    uccessful\x20message\x20sent.\n\n\n\n\x03\x04\x03\x01\x12\x038\x08\x17\n\
-    \x0b\n\x04\x04\x03\x02\0\x12\x039\x02\x1a\n\r\n\x05\x04\x03\x02\0\x04\
-    \x12\x049\x028\x19\n\x0c\n\x05\x04\x03\x02\0\x06\x12\x039\x02\x0b\n\x0c\
-    \n\x05\x04\x03\x02\0\x01\x12\x039\x0c\x15\n\x0c\n\x05\x04\x03\x02\0\x03\
-    \x12\x039\x18\x19\n\x0b\n\x04\x04\x03\x02\x01\x12\x03:\x02\x15\n\r\n\x05\
-    \x04\x03\x02\x01\x04\x12\x04:\x029\x1a\n\x0c\n\x05\x04\x03\x02\x01\x05\
-    \x12\x03:\x02\x08\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03:\t\x10\n\x0c\n\
-    \x05\x04\x03\x02\x01\x03\x12\x03:\x13\x14\n8\n\x02\x04\x04\x12\x03>\0\
-    \x14\x1a-\x20An\x20empty\x20message\x20representing\x20the\x20heartbeat\
-    \n\n\n\n\x03\x04\x04\x01\x12\x03>\x08\x11\n\x9c\x01\n\x02\x06\0\x12\x04E\
-    \0I\x01\x1a\x8f\x01\x20RPC's\x20that\x20directly\x20deal\x20with\x20Prop\
-    ogation\x20of\x20core\x20business\x20data\n\n\x20-\x20Delta\x20Changes\n\
-    \x20-\x20A\x20full\x20Sync\x20Request.\n\x20-\x20Requests\x20for\x20Laye\
-    r\x20data/location\n\n\n\n\x03\x06\0\x01\x12\x03E\x08\x0c\nO\n\x04\x06\0\
-    \x02\0\x12\x03G\x022\x1aB\x20Sends\x20a\x20heartbeat\x20to\x20verify\x20\
-    that\x20the\x20connection\x20exists\x20and\x20is\x20up\n\n\x0c\n\x05\x06\
-    \0\x02\0\x01\x12\x03G\x06\x0f\n\x0c\n\x05\x06\0\x02\0\x02\x12\x03G\x11\
-    \x1a\n\x0c\n\x05\x06\0\x02\0\x03\x12\x03G%.\n\x0b\n\x04\x06\0\x02\x01\
-    \x12\x03H\x029\n\x0c\n\x05\x06\0\x02\x01\x01\x12\x03H\x06\x0f\n\x0c\n\
-    \x05\x06\0\x02\x01\x02\x12\x03H\x11\x1b\n\x0c\n\x05\x06\0\x02\x01\x03\
-    \x12\x03H&5b\x06proto3\
+    counter\"\x0f\n\rORSetFullSync\"\x94\x01\n\nORSetDelta\x12/\n\tdeltatype\
+    \x18\x01\x20\x01(\x0e2\x11.lycaon.DeltaTypeR\tdeltatype\x12\x18\n\x07ele\
+    ment\x18\x02\x20\x01(\tR\x07element\x12\x1f\n\x04dots\x18\x03\x20\x03(\
+    \x0b2\x0b.lycaon.DotR\x04dots\x12\x1a\n\x08produced\x18\x04\x20\x01(\x03\
+    R\x08produced\"\\\n\x0fORSetDeltaReply\x12/\n\tdeltatype\x18\x01\x20\x01\
+    (\x0e2\x11.lycaon.DeltaTypeR\tdeltatype\x12\x18\n\x07element\x18\x02\x20\
+    \x01(\tR\x07element\"\x0b\n\tHeartbeat\"%\n\x0bBlobRequest\x12\x16\n\x06\
+    digest\x18\x01\x20\x01(\tR\x06digest\"\x1e\n\x08BlobData\x12\x12\n\x04da\
+    ta\x18\x01\x20\x01(\x0cR\x04data*\x20\n\tDeltaType\x12\x07\n\x03ADD\x10\
+    \0\x12\n\n\x06REMOVE\x10\x012\xea\x01\n\x04Peer\x123\n\theartbeat\x12\
+    \x11.lycaon.Heartbeat\x1a\x11.lycaon.Heartbeat\"\0\x12:\n\tdeltaSync\x12\
+    \x12.lycaon.ORSetDelta\x1a\x17.lycaon.ORSetDeltaReply\"\0\x129\n\x08full\
+    Sync\x12\x15.lycaon.ORSetFullSync\x1a\x12.lycaon.ORSetDelta\"\00\x01\x12\
+    6\n\tfetchBlob\x12\x13.lycaon.BlobRequest\x1a\x10.lycaon.BlobData\"\00\
+    \x01J\x99\x19\n\x06\x12\x04\0\0^\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\
+    \x08\n\x01\x02\x12\x03\x02\x08\x0e\n\x9f\x06\n\x02\x04\0\x12\x04\x18\0\
+    \x1b\x01\x1a\x81\x01\x20Generic\x20Types\n\n\x20These\x20are\x20types\
+    \x20that\x20are\x20reused\x20throughout\x20the\x20specification\x20and\n\
+    \x20are\x20defined\x20at\x20the\x20top\x20for\x20ease\x20of\x20discovery\
+    .\n2\x8e\x05\x20Looking\x20at\x20the\x20ORSet\x20library\x20which\x20we\
+    \x20are\x20using\x20for\x20our\x20CRDT\n\x20We\x20have\x20two\x20kinds\
+    \x20of\x20messages\x20that\x20relate\x20to\x20the\x20propogation\x20of\n\
+    \x20data.\n\n\x201.\x20When\x20a\x20new\x20Instance\x20comes\x20online\
+    \x20and\x20requests\x20a\x20sync.\n\x20\x20\x20-\x20This\x20could\x20be\
+    \x20implemented\x20using\x20no.\x202\x20and\x20just\x20applying\x20all\n\
+    \x20\x20\x20\x20\x20deltas\x20from\x20an\x20empty\x20ORSet.\n\x202.\x20W\
+    hen\x20an\x20existing\x20instance\x20needs\x20to\x20send\x20a\x20delta\
+    \x20to\x20listening\x20instances.\n\n\x20The\x20second\x20set\x20of\x20m\
+    essages\x20relates\x20to\x20locating\x20and\x20downloading\n\x20informat\
+    ion\x20from\x20other\x20services.\x20This\x20includes\x20(non-exhaustive\
+    ):\n\n\x20-\x20Querying\x20a\x20layers\x20existence\x20on\x20a\x20remote\
+    \x20instance\n\x20-\x20Querying\x20permissions\x20regarding\x20a\x20laye\
+    r\n\x20-\x20Propogating\x20any\x20state\x20changes\x20(such\x20as\x20del\
+    etion\x20requests)\n\n\n\n\x03\x04\0\x01\x12\x03\x18\x08\x0b\n\x0b\n\x04\
+    \x04\0\x02\0\x12\x03\x19\x02\x13\n\r\n\x05\x04\0\x02\0\x04\x12\x04\x19\
+    \x02\x18\r\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x19\x02\x08\n\x0c\n\x05\
+    \x04\0\x02\0\x01\x12\x03\x19\t\x0e\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\
+    \x19\x11\x12\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x1a\x02\x15\n\r\n\x05\x04\
+    \0\x02\x01\x04\x12\x04\x1a\x02\x19\x13\n\x0c\n\x05\x04\0\x02\x01\x05\x12\
+    \x03\x1a\x02\x08\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x1a\t\x10\n\x0c\n\
+    \x05\x04\0\x02\x01\x03\x12\x03\x1a\x13\x14\ny\n\x02\x05\0\x12\x04!\0$\
+    \x01\x1am\x20Describe\x20the\x20type\x20of\x20Delta\x20Message\n\n\x20Ad\
+    d:\x20Addition\x20of\x20a\x20key\x20to\x20the\x20set\n\x20Remove:\x20Del\
+    etion\x20of\x20a\x20tag\x20for\x20a\x20key\n\n\n\n\x03\x05\0\x01\x12\x03\
+    !\x05\x0e\n\x0b\n\x04\x05\0\x02\0\x12\x03\"\x02\n\n\x0c\n\x05\x05\0\x02\
+    \0\x01\x12\x03\"\x02\x05\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03\"\x08\t\n\
+    \x0b\n\x04\x05\0\x02\x01\x12\x03#\x02\r\n\x0c\n\x05\x05\0\x02\x01\x01\
+    \x12\x03#\x02\x08\n\x0c\n\x05\x05\0\x02\x01\x02\x12\x03#\x0b\x0c\n\x99\
+    \x01\n\x02\x04\x01\x12\x03*\0\x18\x1a\x8d\x01\x20ORSet\x20messages\n\n\
+    \x20This\x20message\x20requests\x20a\x20sync\x20of\x20the\x20entire\x20c\
+    urrent\x20state\x20of\x20the\n\x20ORSet,\x20which\x20is\x20sent\x20back\
+    \x20as\x20a\x20delta\x20adding\x20each\x20element.\n\n\n\n\x03\x04\x01\
+    \x01\x12\x03*\x08\x15\n\xd8\x01\n\x02\x04\x02\x12\x042\07\x01\x1a\xcb\
+    \x01\x20This\x20message\x20represents\x20a\x20single\x20Delta\x20of\x20t\
+    he\x20ORSet.\n\n\x20It\x20is\x20sent\x20out\x20on\x20every\x20update\x20\
+    of\x20the\x20data-structure,\x20and\n\x20propogated\x20to\x20all\x20peer\
+    s.\n\n\x20produced\x20:=\x20unix\x20time\x20in\x20milliseconds\x20the\
+    \x20change\x20was\x20made\x20at\n\n\n\n\x03\x04\x02\x01\x12\x032\x08\x12\
+    \n\x0b\n\x04\x04\x02\x02\0\x12\x033\x02\x1a\n\r\n\x05\x04\x02\x02\0\x04\
+    \x12\x043\x022\x14\n\x0c\n\x05\x04\x02\x02\0\x06\x12\x033\x02\x0b\n\x0c\
+    \n\x05\x04\x02\x02\0\x01\x12\x033\x0c\x15\n\x0c\n\x05\x04\x02\x02\0\x03\
+    \x12\x033\x18\x19\n\x0b\n\x04\x04\x02\x02\x01\x12\x034\x02\x15\n\r\n\x05\
+    \x04\x02\x02\x01\x04\x12\x044\x023\x1a\n\x0c\n\x05\x04\x02\x02\x01\x05\
+    \x12\x034\x02\x08\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x034\t\x10\n\x0c\n\
+    \x05\x04\x02\x02\x01\x03\x12\x034\x13\x14\n\x0b\n\x04\x04\x02\x02\x02\
+    \x12\x035\x02\x18\n\x0c\n\x05\x04\x02\x02\x02\x04\x12\x035\x02\n\n\x0c\n\
+    \x05\x04\x02\x02\x02\x06\x12\x035\x0b\x0e\n\x0c\n\x05\x04\x02\x02\x02\
+    \x01\x12\x035\x0f\x13\n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x035\x16\x17\n\
+    \x0b\n\x04\x04\x02\x02\x03\x12\x036\x02\x15\n\r\n\x05\x04\x02\x02\x03\
+    \x04\x12\x046\x025\x18\n\x0c\n\x05\x04\x02\x02\x03\x05\x12\x036\x02\x07\
+    \n\x0c\n\x05\x04\x02\x02\x03\x01\x12\x036\x08\x10\n\x0c\n\x05\x04\x02\
+    \x02\x03\x03\x12\x036\x13\x14\n\xb9\x01\n\x02\x04\x03\x12\x04<\0?\x01\
+    \x1a\xac\x01\x20This\x20message\x20represents\x20a\x20reply\x20to\x20a\
+    \x20sent\x20delta.\n\x20Currently\x20this\x20message\x20simply\x20return\
+    s\x20the\x20DeltaType\x20and\x20the\x20element\n\x20so\x20the\x20client\
+    \x20can\x20verify\x20a\x20successful\x20message\x20sent.\n\n\n\n\x03\x04\
+    \x03\x01\x12\x03<\x08\x17\n\x0b\n\x04\x04\x03\x02\0\x12\x03=\x02\x1a\n\r\
+    \n\x05\x04\x03\x02\0\x04\x12\x04=\x02<\x19\n\x0c\n\x05\x04\x03\x02\0\x06\
+    \x12\x03=\x02\x0b\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03=\x0c\x15\n\x0c\n\
+    \x05\x04\x03\x02\0\x03\x12\x03=\x18\x19\n\x0b\n\x04\x04\x03\x02\x01\x12\
+    \x03>\x02\x15\n\r\n\x05\x04\x03\x02\x01\x04\x12\x04>\x02=\x1a\n\x0c\n\
+    \x05\x04\x03\x02\x01\x05\x12\x03>\x02\x08\n\x0c\n\x05\x04\x03\x02\x01\
+    \x01\x12\x03>\t\x10\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03>\x13\x14\n8\
+    \n\x02\x04\x04\x12\x03B\0\x14\x1a-\x20An\x20empty\x20message\x20represen\
+    ting\x20the\x20heartbeat\n\n\n\n\x03\x04\x04\x01\x12\x03B\x08\x11\ns\n\
+    \x02\x04\x05\x12\x04H\0J\x01\x1ag\x20Blob\x20messages\n\n\x20A\x20reques\
+    t\x20for\x20the\x20content\x20of\x20a\x20stored\x20blob,\x20and\x20the\
+    \x20pieces\x20of\x20it\n\x20that\x20are\x20sent\x20back.\n\n\n\n\x03\x04\
+    \x05\x01\x12\x03H\x08\x13\n\x0b\n\x04\x04\x05\x02\0\x12\x03I\x02\x14\n\r\
+    \n\x05\x04\x05\x02\0\x04\x12\x04I\x02H\x15\n\x0c\n\x05\x04\x05\x02\0\x05\
+    \x12\x03I\x02\x08\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03I\t\x0f\n\x0c\n\
+    \x05\x04\x05\x02\0\x03\x12\x03I\x12\x13\n\n\n\x02\x04\x06\x12\x04L\0N\
+    \x01\n\n\n\x03\x04\x06\x01\x12\x03L\x08\x10\n\x0b\n\x04\x04\x06\x02\0\
+    \x12\x03M\x02\x11\n\r\n\x05\x04\x06\x02\0\x04\x12\x04M\x02L\x12\n\x0c\n\
+    \x05\x04\x06\x02\0\x05\x12\x03M\x02\x07\n\x0c\n\x05\x04\x06\x02\0\x01\
+    \x12\x03M\x08\x0c\n\x0c\n\x05\x04\x06\x02\0\x03\x12\x03M\x0f\x10\n\x9c\
+    \x01\n\x02\x06\0\x12\x04U\0^\x01\x1a\x8f\x01\x20RPC's\x20that\x20directl\
+    y\x20deal\x20with\x20Propogation\x20of\x20core\x20business\x20data\n\n\
+    \x20-\x20Delta\x20Changes\n\x20-\x20A\x20full\x20Sync\x20Request.\n\x20-\
+    \x20Requests\x20for\x20Layer\x20data/location\n\n\n\n\x03\x06\0\x01\x12\
+    \x03U\x08\x0c\nO\n\x04\x06\0\x02\0\x12\x03W\x022\x1aB\x20Sends\x20a\x20h\
+    eartbeat\x20to\x20verify\x20that\x20the\x20connection\x20exists\x20and\
+    \x20is\x20up\n\n\x0c\n\x05\x06\0\x02\0\x01\x12\x03W\x06\x0f\n\x0c\n\x05\
+    \x06\0\x02\0\x02\x12\x03W\x11\x1a\n\x0c\n\x05\x06\0\x02\0\x03\x12\x03W%.\
+    \n\x0b\n\x04\x06\0\x02\x01\x12\x03X\x029\n\x0c\n\x05\x06\0\x02\x01\x01\
+    \x12\x03X\x06\x0f\n\x0c\n\x05\x06\0\x02\x01\x02\x12\x03X\x11\x1b\n\x0c\n\
+    \x05\x06\0\x02\x01\x03\x12\x03X&5\nh\n\x04\x06\0\x02\x02\x12\x03[\x02=\
+    \x1a[\x20Streams\x20every\x20blob\x20stored\x20on\x20this\x20instance,\
+    \x20for\x20a\x20joining\x20one\x20to\n\x20fetch\x20what\x20it's\x20missi\
+    ng\n\n\x0c\n\x05\x06\0\x02\x02\x01\x12\x03[\x06\x0e\n\x0c\n\x05\x06\0\
+    \x02\x02\x02\x12\x03[\x10\x1d\n\x0c\n\x05\x06\0\x02\x02\x06\x12\x03[(.\n\
+    \x0c\n\x05\x06\0\x02\x02\x03\x12\x03[/9\nL\n\x04\x06\0\x02\x03\x12\x03]\
+    \x02:\x1a?\x20Streams\x20a\x20blob\x20stored\x20on\x20this\x20instance,\
+    \x20NOT_FOUND\x20if\x20it\x20isn't\n\n\x0c\n\x05\x06\0\x02\x03\x01\x12\
+    \x03]\x06\x0f\n\x0c\n\x05\x06\0\x02\x03\x02\x12\x03]\x11\x1c\n\x0c\n\x05\
+    \x06\0\x02\x03\x06\x12\x03]'-\n\x0c\n\x05\x06\0\x02\x03\x03\x12\x03].6b\
+    \x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
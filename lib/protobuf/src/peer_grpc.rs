@@ -32,6 +32,20 @@ const METHOD_PEER_DELTA_SYNC: ::grpcio::Method<super::peer::ORSetDelta, super::p
     resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
 };
 
+const METHOD_PEER_FULL_SYNC: ::grpcio::Method<super::peer::ORSetFullSync, super::peer::ORSetDelta> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::ServerStreaming,
+    name: "/lycaon.Peer/fullSync",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
+const METHOD_PEER_FETCH_BLOB: ::grpcio::Method<super::peer::BlobRequest, super::peer::BlobData> = ::grpcio::Method {
+    ty: ::grpcio::MethodType::ServerStreaming,
+    name: "/lycaon.Peer/fetchBlob",
+    req_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+    resp_mar: ::grpcio::Marshaller { ser: ::grpcio::pb_ser, de: ::grpcio::pb_de },
+};
+
 pub struct PeerClient {
     client: ::grpcio::Client,
 }
@@ -43,37 +57,53 @@ impl PeerClient {
         }
     }
 
-    pub fn heartbeat_opt(&self, req: super::peer::Heartbeat, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::peer::Heartbeat> {
+    pub fn heartbeat_opt(&self, req: &super::peer::Heartbeat, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::peer::Heartbeat> {
         self.client.unary_call(&METHOD_PEER_HEARTBEAT, req, opt)
     }
 
-    pub fn heartbeat(&self, req: super::peer::Heartbeat) -> ::grpcio::Result<super::peer::Heartbeat> {
+    pub fn heartbeat(&self, req: &super::peer::Heartbeat) -> ::grpcio::Result<super::peer::Heartbeat> {
         self.heartbeat_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn heartbeat_async_opt(&self, req: super::peer::Heartbeat, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::peer::Heartbeat> {
+    pub fn heartbeat_async_opt(&self, req: &super::peer::Heartbeat, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::peer::Heartbeat>> {
         self.client.unary_call_async(&METHOD_PEER_HEARTBEAT, req, opt)
     }
 
-    pub fn heartbeat_async(&self, req: super::peer::Heartbeat) -> ::grpcio::ClientUnaryReceiver<super::peer::Heartbeat> {
+    pub fn heartbeat_async(&self, req: &super::peer::Heartbeat) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::peer::Heartbeat>> {
         self.heartbeat_async_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn delta_sync_opt(&self, req: super::peer::ORSetDelta, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::peer::ORSetDeltaReply> {
+    pub fn delta_sync_opt(&self, req: &super::peer::ORSetDelta, opt: ::grpcio::CallOption) -> ::grpcio::Result<super::peer::ORSetDeltaReply> {
         self.client.unary_call(&METHOD_PEER_DELTA_SYNC, req, opt)
     }
 
-    pub fn delta_sync(&self, req: super::peer::ORSetDelta) -> ::grpcio::Result<super::peer::ORSetDeltaReply> {
+    pub fn delta_sync(&self, req: &super::peer::ORSetDelta) -> ::grpcio::Result<super::peer::ORSetDeltaReply> {
         self.delta_sync_opt(req, ::grpcio::CallOption::default())
     }
 
-    pub fn delta_sync_async_opt(&self, req: super::peer::ORSetDelta, opt: ::grpcio::CallOption) -> ::grpcio::ClientUnaryReceiver<super::peer::ORSetDeltaReply> {
+    pub fn delta_sync_async_opt(&self, req: &super::peer::ORSetDelta, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::peer::ORSetDeltaReply>> {
         self.client.unary_call_async(&METHOD_PEER_DELTA_SYNC, req, opt)
     }
 
-    pub fn delta_sync_async(&self, req: super::peer::ORSetDelta) -> ::grpcio::ClientUnaryReceiver<super::peer::ORSetDeltaReply> {
+    pub fn delta_sync_async(&self, req: &super::peer::ORSetDelta) -> ::grpcio::Result<::grpcio::ClientUnaryReceiver<super::peer::ORSetDeltaReply>> {
         self.delta_sync_async_opt(req, ::grpcio::CallOption::default())
     }
+
+    pub fn full_sync_opt(&self, req: &super::peer::ORSetFullSync, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::peer::ORSetDelta>> {
+        self.client.server_streaming(&METHOD_PEER_FULL_SYNC, req, opt)
+    }
+
+    pub fn full_sync(&self, req: &super::peer::ORSetFullSync) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::peer::ORSetDelta>> {
+        self.full_sync_opt(req, ::grpcio::CallOption::default())
+    }
+
+    pub fn fetch_blob_opt(&self, req: &super::peer::BlobRequest, opt: ::grpcio::CallOption) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::peer::BlobData>> {
+        self.client.server_streaming(&METHOD_PEER_FETCH_BLOB, req, opt)
+    }
+
+    pub fn fetch_blob(&self, req: &super::peer::BlobRequest) -> ::grpcio::Result<::grpcio::ClientSStreamReceiver<super::peer::BlobData>> {
+        self.fetch_blob_opt(req, ::grpcio::CallOption::default())
+    }
     pub fn spawn<F>(&self, f: F) where F: ::futures::Future<Item = (), Error = ()> + Send + 'static {
         self.client.spawn(f)
     }
@@ -82,6 +112,8 @@ impl PeerClient {
 pub trait Peer {
     fn heartbeat(&self, ctx: ::grpcio::RpcContext, req: super::peer::Heartbeat, sink: ::grpcio::UnarySink<super::peer::Heartbeat>);
     fn delta_sync(&self, ctx: ::grpcio::RpcContext, req: super::peer::ORSetDelta, sink: ::grpcio::UnarySink<super::peer::ORSetDeltaReply>);
+    fn full_sync(&self, ctx: ::grpcio::RpcContext, req: super::peer::ORSetFullSync, sink: ::grpcio::ServerStreamingSink<super::peer::ORSetDelta>);
+    fn fetch_blob(&self, ctx: ::grpcio::RpcContext, req: super::peer::BlobRequest, sink: ::grpcio::ServerStreamingSink<super::peer::BlobData>);
 }
 
 pub fn create_peer<S: Peer + Send + Clone + 'static>(s: S) -> ::grpcio::Service {
@@ -94,5 +126,13 @@ pub fn create_peer<S: Peer + Send + Clone + 'static>(s: S) -> ::grpcio::Service
     builder = builder.add_unary_handler(&METHOD_PEER_DELTA_SYNC, move |ctx, req, resp| {
         instance.delta_sync(ctx, req, resp)
     });
+    let instance = s.clone();
+    builder = builder.add_server_streaming_handler(&METHOD_PEER_FULL_SYNC, move |ctx, req, resp| {
+        instance.full_sync(ctx, req, resp)
+    });
+    let instance = s.clone();
+    builder = builder.add_server_streaming_handler(&METHOD_PEER_FETCH_BLOB, move |ctx, req, resp| {
+        instance.fetch_blob(ctx, req, resp)
+    });
     builder.build()
 }
@@ -0,0 +1,334 @@
+//! Moving repositories in and out of a stopped registry as OCI image
+//! layouts.
+//!
+//! `export` writes the tagged manifests of a repository, everything
+//! they reference and an `index.json` naming them by their tags.
+//! `import` reads such a layout, e.g. one written by `skopeo` or
+//! `oras`, verifying every manifest and blob against its digest
+//! before it's stored, and tags what the index names.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use failure::{err_msg, Error};
+use serde_json::{self, Value};
+
+use controller;
+use datadir::{self, DataDir};
+use manifest;
+use types::Digest;
+
+const LAYOUT_VERSION: &'static str = "1.0.0";
+const OCI_INDEX: &'static str = "application/vnd.oci.image.index.v1+json";
+const REF_NAME: &'static str = "org.opencontainers.image.ref.name";
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub tags: Vec<String>,
+    pub manifests: u64,
+    pub blobs: u64,
+    pub bytes: u64,
+}
+
+/// Where the content of _digest_ is kept in the layout at _layout_.
+fn layout_blob(layout: &Path, digest: &Digest) -> PathBuf {
+    layout
+        .join("blobs")
+        .join(digest.algorithm().name())
+        .join(digest.hex())
+}
+
+/// The tag an index names a manifest by. `ref.name` may also be a
+/// full reference, `<name>:<tag>`.
+fn tag_of(ref_name: &str) -> Option<String> {
+    if controller::tag::is_valid(ref_name) {
+        return Some(ref_name.to_owned());
+    }
+    let tag = ref_name.rsplit(':').next()?;
+    match controller::tag::is_valid(tag) {
+        true => Some(tag.to_owned()),
+        false => None,
+    }
+}
+
+/// Exports _tags_ of _repository_, or all of them if empty, from the
+/// data directory at _root_ to the layout at _output_.
+pub fn export(root: &Path, repository: &str, tags: &[String], output: &Path) -> Result<Report, Error> {
+    let data = DataDir::open(root)?;
+    let mut exported = data.tags(repository)?;
+    if !tags.is_empty() {
+        for tag in tags {
+            if !exported.iter().any(|&(ref name, _)| name == tag) {
+                return Err(err_msg(format!("{}:{} doesn't exist", repository, tag)));
+            }
+        }
+        exported.retain(|&(ref name, _)| tags.contains(name));
+    }
+    if exported.is_empty() {
+        return Err(err_msg(format!("{} has no tags", repository)));
+    }
+
+    let mut export = Export {
+        data,
+        repository,
+        output,
+        copied: BTreeSet::new(),
+        report: Report::default(),
+    };
+    let mut descriptors = Vec::new();
+    for (tag, digest) in exported {
+        let (media_type, size) = export.manifest(&digest)?;
+        descriptors.push(json!({
+            "mediaType": media_type,
+            "digest": digest.to_string(),
+            "size": size,
+            "annotations": { "org.opencontainers.image.ref.name": tag },
+        }));
+        export.report.tags.push(tag);
+    }
+
+    let index = json!({
+        "schemaVersion": 2,
+        "mediaType": OCI_INDEX,
+        "manifests": descriptors,
+    });
+    datadir::write(&output.join("index.json"), &serde_json::to_vec_pretty(&index)?)?;
+    let layout = json!({ "imageLayoutVersion": LAYOUT_VERSION });
+    datadir::write(&output.join("oci-layout"), &serde_json::to_vec(&layout)?)?;
+    Ok(export.report)
+}
+
+struct Export<'a> {
+    data: DataDir,
+    repository: &'a str,
+    output: &'a Path,
+    /// Manifests and blobs already in the layout
+    copied: BTreeSet<Digest>,
+    report: Report,
+}
+
+impl<'a> Export<'a> {
+    /// Copies the manifest _digest_ and everything it references,
+    /// returns its media type and size.
+    fn manifest(&mut self, digest: &Digest) -> Result<(String, u64), Error> {
+        let parsed = self.data.parse_manifest(self.repository, digest)?;
+        let (content, _) = self.data
+            .manifest(self.repository, digest)
+            .ok_or_else(|| err_msg(format!("manifest {}@{} is missing", self.repository, digest)))?;
+        let size = content.len() as u64;
+        if !self.copied.insert(digest.clone()) {
+            return Ok((parsed.media_type, size));
+        }
+
+        for child in &parsed.manifests {
+            self.manifest(child)?;
+        }
+        for blob in &parsed.blobs {
+            if !self.copied.insert(blob.clone()) {
+                continue;
+            }
+            let target = layout_blob(self.output, blob);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            self.report.bytes += fs::copy(self.data.blob(blob), &target)
+                .map_err(|e| err_msg(format!("blob {}: {}", blob, e)))?;
+            self.report.blobs += 1;
+        }
+        datadir::write(&layout_blob(self.output, digest), &content)?;
+        self.report.manifests += 1;
+        self.report.bytes += size;
+        Ok((parsed.media_type, size))
+    }
+}
+
+/// Imports the layout at _layout_ into _repository_ of the data
+/// directory at _root_.
+pub fn import(root: &Path, layout: &Path, repository: &str) -> Result<Report, Error> {
+    let data = DataDir::open(root)?;
+    let repository = controller::repository::validate(repository)
+        .map_err(|_| err_msg(format!("invalid repository name {:?}", repository)))?;
+    let index: Value = datadir::read(&layout.join("index.json"))
+        .map_err(|e| err_msg(format!("{} is not an OCI layout: {}", layout.display(), e)))
+        .and_then(|index| serde_json::from_slice(&index).map_err(Error::from))?;
+    let descriptors = index["manifests"]
+        .as_array()
+        .ok_or_else(|| err_msg("index.json lists no manifests"))?;
+
+    let mut import = Import {
+        data,
+        layout,
+        repository: &repository,
+        imported: BTreeSet::new(),
+        links: Vec::new(),
+        report: Report::default(),
+    };
+    for descriptor in descriptors {
+        let digest = parse_digest(&descriptor["digest"])?;
+        import.manifest(&digest, descriptor["mediaType"].as_str())?;
+        if let Some(tag) = descriptor["annotations"][REF_NAME].as_str().and_then(tag_of) {
+            let path = import.data.manifests(&repository).join("tags").join(&tag);
+            datadir::write(&path, digest.to_string().as_bytes())?;
+            import.report.tags.push(tag);
+        }
+    }
+    if !import.links.is_empty() {
+        link_referrers(&import.data, import.links)?;
+    }
+    Ok(import.report)
+}
+
+fn parse_digest(digest: &Value) -> Result<Digest, Error> {
+    let digest = digest.as_str().unwrap_or_default();
+    digest
+        .parse()
+        .map_err(|_| err_msg(format!("invalid digest {:?} in layout", digest)))
+}
+
+struct Import<'a> {
+    data: DataDir,
+    layout: &'a Path,
+    repository: &'a str,
+    /// Manifests and blobs already stored
+    imported: BTreeSet<Digest>,
+    /// Referrers links of the imported manifests with a subject
+    links: Vec<Value>,
+    report: Report,
+}
+
+impl<'a> Import<'a> {
+    /// Stores the manifest _digest_ of the layout and everything it
+    /// references.
+    fn manifest(&mut self, digest: &Digest, media_type: Option<&str>) -> Result<(), Error> {
+        if !self.imported.insert(digest.clone()) {
+            return Ok(());
+        }
+        let content = datadir::read(&layout_blob(self.layout, digest))
+            .map_err(|e| err_msg(format!("manifest {}: {}", digest, e)))?;
+        if Digest::of(digest.algorithm(), &content) != *digest {
+            return Err(err_msg(format!("manifest {} doesn't match its digest", digest)));
+        }
+        let parsed = manifest::parse(media_type, &content)
+            .map_err(|e| err_msg(format!("manifest {}: {}", digest, e)))?;
+
+        for child in &parsed.manifests {
+            self.manifest(child, None)?;
+        }
+        for blob in &parsed.blobs {
+            if !self.data.blob(blob).is_file() && self.imported.insert(blob.clone()) {
+                self.report.bytes += self.blob(blob)?;
+                self.report.blobs += 1;
+            }
+            self.data.link_blob(self.repository, blob)?;
+        }
+
+        let path = self.data
+            .manifests(self.repository)
+            .join("revisions")
+            .join(digest.to_string());
+        datadir::write(&path, &content)?;
+        datadir::write(&path.with_extension("type"), parsed.media_type.as_bytes())?;
+        self.report.manifests += 1;
+        self.report.bytes += content.len() as u64;
+
+        if let Some(ref subject) = parsed.subject {
+            self.links.push(json!({
+                "repository": self.repository,
+                "subject": subject.to_string(),
+                "digest": digest.to_string(),
+                "media_type": parsed.media_type,
+                "artifact_type": parsed.artifact_type,
+                "size": content.len() as u64,
+                "annotations": parsed.annotations,
+            }));
+        }
+        Ok(())
+    }
+
+    /// Copies the blob _digest_ into the layer store once it's been
+    /// verified, returns its size.
+    fn blob(&self, digest: &Digest) -> Result<u64, Error> {
+        let tmp = self.data.layers().join(format!(".{}.tmp", digest));
+        let size = fs::copy(layout_blob(self.layout, digest), &tmp)
+            .map_err(|e| err_msg(format!("blob {}: {}", digest, e)))?;
+        if datadir::hash(&tmp, digest)? != *digest {
+            let _ = fs::remove_file(&tmp);
+            return Err(err_msg(format!("blob {} doesn't match its digest", digest)));
+        }
+        fs::rename(&tmp, self.data.blob(digest))?;
+        Ok(size)
+    }
+}
+
+/// Adds _links_ to `referrers.json`, replacing earlier links of the
+/// same manifests.
+fn link_referrers(data: &DataDir, links: Vec<Value>) -> Result<(), Error> {
+    let path = data.root().join("referrers.json");
+    let mut existing: Vec<Value> = match datadir::read(&path) {
+        Ok(contents) => serde_json::from_slice(&contents)?,
+        Err(_) => Vec::new(),
+    };
+    existing.retain(|link| {
+        !links.iter().any(|new| {
+            new["repository"] == link["repository"] && new["digest"] == link["digest"]
+        })
+    });
+    existing.extend(links);
+    datadir::write(&path, &serde_json::to_vec(&existing)?)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use uuid::Uuid;
+
+    use types::Algorithm;
+
+    use super::*;
+
+    #[test]
+    fn tags_from_ref_names() {
+        assert_eq!(tag_of("latest"), Some("latest".to_owned()));
+        assert_eq!(tag_of("docker.io/library/alpine:3.7"), Some("3.7".to_owned()));
+        assert_eq!(tag_of("library/alpine"), None);
+    }
+
+    #[test]
+    fn export_then_import() {
+        let dir = env::temp_dir().join(format!("lycaon-archive-{}", Uuid::new_v4()));
+        let (source, target, layout) = (dir.join("source"), dir.join("target"), dir.join("layout"));
+        fs::create_dir_all(target.join("layers")).unwrap();
+
+        let config = Digest::of(Algorithm::Sha256, b"config");
+        datadir::write(&source.join("layers").join(config.to_string()), b"config").unwrap();
+        let image = format!(
+            r#"{{"schemaVersion": 2, "config": {{"digest": "{}"}}, "layers": []}}"#,
+            config
+        );
+        let digest = Digest::of(Algorithm::Sha256, image.as_bytes());
+        let manifests = source.join("repositories/library/alpine/_manifests");
+        datadir::write(&manifests.join("revisions").join(digest.to_string()), image.as_bytes()).unwrap();
+        datadir::write(&manifests.join("tags/3.7"), digest.to_string().as_bytes()).unwrap();
+
+        assert!(export(&source, "library/alpine", &["edge".to_owned()], &layout).is_err());
+        let report = export(&source, "library/alpine", &[], &layout).unwrap();
+        assert_eq!((report.manifests, report.blobs), (1, 1));
+        assert!(layout.join("oci-layout").is_file());
+
+        let report = import(&target, &layout, "mirror/alpine").unwrap();
+        assert_eq!(report.tags, vec!["3.7".to_owned()]);
+        let data = DataDir::open(&target).unwrap();
+        assert_eq!(data.tags("mirror/alpine").unwrap(), vec![("3.7".to_owned(), digest.clone())]);
+        assert!(data.parse_manifest("mirror/alpine", &digest).is_ok());
+        assert!(data.has_blob("mirror/alpine", &config));
+
+        // a tampered blob is refused
+        datadir::write(&layout_blob(&layout, &config), b"tampered").unwrap();
+        fs::remove_file(data.blob(&config)).unwrap();
+        assert!(import(&target, &layout, "mirror/alpine").is_err());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
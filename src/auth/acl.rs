@@ -0,0 +1,315 @@
+//! Per-repository access control.
+//!
+//! Policies are read from a TOML file and re-read whenever the file
+//! changes on disk:
+//!
+//! ```toml
+//! [groups]
+//! admins = ["moredhel"]
+//!
+//! [admin]
+//! groups = ["admins"]
+//!
+//! [[rules]]
+//! repository = "library/*"
+//! anonymous = true
+//! actions = ["pull"]
+//!
+//! [[rules]]
+//! repository = "**"
+//! groups = ["admins"]
+//! actions = ["pull", "push", "delete"]
+//! ```
+//!
+//! In a repository pattern `*` matches a single path component (or part
+//! of one) and `**` matches any number of components. A user of `"*"`
+//! matches every authenticated user. Access is granted if any rule
+//! allows it.
+//!
+//! The `/admin` routes are only open to the users and groups listed
+//! under `[admin]`, nobody is an administrator by default.
+
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use failure::Error;
+
+use errors;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Pull,
+    Push,
+    Delete,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Action::Pull => "pull",
+            Action::Push => "push",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Action, Error> {
+        match s {
+            "pull" => Ok(Action::Pull),
+            "push" => Ok(Action::Push),
+            "delete" => Ok(Action::Delete),
+            _ => Err(errors::Server::Invalid("action").into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Rule {
+    repository: String,
+    #[serde(default)]
+    users: Vec<String>,
+    #[serde(default)]
+    groups: Vec<String>,
+    #[serde(default)]
+    anonymous: bool,
+    actions: Vec<String>,
+}
+
+/// Who may use the `/admin` routes
+#[derive(Debug, Default, Deserialize)]
+struct Admin {
+    #[serde(default)]
+    users: Vec<String>,
+    #[serde(default)]
+    groups: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Policy {
+    #[serde(default)]
+    groups: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    admin: Admin,
+    #[serde(default)]
+    rules: Vec<Rule>,
+}
+
+impl Policy {
+    fn from_file(path: &str) -> Result<Policy, Error> {
+        use cfg::{Config, File, FileFormat};
+        let mut s = Config::new();
+        s.merge(File::new(path, FileFormat::Toml))?;
+        s.try_into().map_err(|e| e.into())
+    }
+
+    fn allows(&self, user: Option<&str>, repository: &str, action: &str) -> bool {
+        self.rules.iter().any(|rule| {
+            rule.actions.iter().any(|a| a == action || a == "*")
+                && matches(&rule.repository, repository)
+                && self.applies_to(rule, user)
+        })
+    }
+
+    fn applies_to(&self, rule: &Rule, user: Option<&str>) -> bool {
+        match user {
+            None => rule.anonymous,
+            Some(user) => rule.anonymous || self.member(&rule.users, &rule.groups, user),
+        }
+    }
+
+    fn is_admin(&self, user: &str) -> bool {
+        self.member(&self.admin.users, &self.admin.groups, user)
+    }
+
+    /// Whether _user_ is one of _users_ or in one of _groups_.
+    fn member(&self, users: &[String], groups: &[String], user: &str) -> bool {
+        users.iter().any(|u| u == "*" || u == user) || groups.iter().any(|group| {
+            self.groups
+                .get(group)
+                .map(|members| members.iter().any(|m| m == user))
+                .unwrap_or(false)
+        })
+    }
+}
+
+/// Matches a repository name against a pattern, component by component.
+pub fn matches(pattern: &str, repository: &str) -> bool {
+    fn inner(pattern: &[&str], name: &[&str]) -> bool {
+        match (pattern.split_first(), name.split_first()) {
+            (None, None) => true,
+            (Some((&"**", rest)), _) => {
+                inner(rest, name) || (!name.is_empty() && inner(pattern, &name[1..]))
+            }
+            (Some((p, prest)), Some((n, nrest))) => glob(p, n) && inner(prest, nrest),
+            _ => false,
+        }
+    }
+
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let name: Vec<&str> = repository.split('/').collect();
+    inner(&pattern, &name)
+}
+
+/// Glob match of a single path component, only `*` is special.
+fn glob(pattern: &str, component: &str) -> bool {
+    match pattern.find('*') {
+        None => pattern == component,
+        Some(i) => {
+            let (prefix, rest) = (&pattern[..i], &pattern[i + 1..]);
+            component.starts_with(prefix)
+                && (0..component.len() - prefix.len() + 1)
+                    .any(|skip| glob(rest, &component[prefix.len() + skip..]))
+        }
+    }
+}
+
+/// The loaded policy and the file it was loaded from.
+///
+/// Without a policy file every authenticated user may do anything with
+/// repositories, anonymous users may do nothing and nobody is an
+/// administrator.
+pub struct Acl {
+    path: Option<String>,
+    policy: RwLock<(Option<SystemTime>, Policy)>,
+}
+
+impl Acl {
+    pub fn new(path: Option<String>) -> Result<Acl, Error> {
+        let policy = match path {
+            Some(ref path) => (modified(path), Policy::from_file(path)?),
+            None => (None, Policy::default()),
+        };
+        Ok(Acl {
+            path,
+            policy: RwLock::new(policy),
+        })
+    }
+
+    pub fn allows(&self, user: Option<&str>, repository: &str, action: Action) -> bool {
+        if self.path.is_none() {
+            return user.is_some();
+        }
+
+        self.reload_if_changed();
+        let policy = self.policy.read().unwrap();
+        policy.1.allows(user, repository, action.as_str())
+    }
+
+    /// Whether _user_ may use the `/admin` routes.
+    pub fn is_admin(&self, user: &str) -> bool {
+        if self.path.is_none() {
+            return false;
+        }
+
+        self.reload_if_changed();
+        let policy = self.policy.read().unwrap();
+        policy.1.is_admin(user)
+    }
+
+    /// Re-reads the policy file if it was modified since the last load.
+    /// A broken file keeps the previous policy in place.
+    fn reload_if_changed(&self) {
+        let path = match self.path {
+            Some(ref path) => path,
+            None => return,
+        };
+        let mtime = modified(path);
+        if self.policy.read().unwrap().0 == mtime {
+            return;
+        }
+
+        match Policy::from_file(path) {
+            Ok(policy) => {
+                info!("Reloaded access policy from {}", path);
+                *self.policy.write().unwrap() = (mtime, policy);
+            }
+            Err(e) => warn!("Failed to reload access policy {}: {}", path, e),
+        }
+    }
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn policy() -> Policy {
+        let mut groups = HashMap::new();
+        groups.insert("admins".to_owned(), vec!["moredhel".to_owned()]);
+        Policy {
+            groups,
+            admin: Admin {
+                users: vec![],
+                groups: vec!["admins".to_owned()],
+            },
+            rules: vec![
+                Rule {
+                    repository: "library/*".to_owned(),
+                    users: vec![],
+                    groups: vec![],
+                    anonymous: true,
+                    actions: vec!["pull".to_owned()],
+                },
+                Rule {
+                    repository: "**".to_owned(),
+                    users: vec![],
+                    groups: vec!["admins".to_owned()],
+                    anonymous: false,
+                    actions: vec!["*".to_owned()],
+                },
+                Rule {
+                    repository: "ci-*/*".to_owned(),
+                    users: vec!["jenkins".to_owned()],
+                    groups: vec![],
+                    anonymous: false,
+                    actions: vec!["pull".to_owned(), "push".to_owned()],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn pattern_matching() {
+        assert!(matches("library/*", "library/ubuntu"));
+        assert!(!matches("library/*", "library/ubuntu/sub"));
+        assert!(matches("library/**", "library/ubuntu/sub"));
+        assert!(matches("**", "alpine"));
+        assert!(matches("ci-*/*", "ci-nightly/build"));
+        assert!(!matches("ci-*/*", "nightly/build"));
+    }
+
+    #[test]
+    fn anonymous_public_read() {
+        let policy = policy();
+        assert!(policy.allows(None, "library/ubuntu", "pull"));
+        assert!(!policy.allows(None, "library/ubuntu", "push"));
+        assert!(!policy.allows(None, "moredhel/test", "pull"));
+    }
+
+    #[test]
+    fn users_and_groups() {
+        let policy = policy();
+        assert!(policy.allows(Some("moredhel"), "moredhel/test", "delete"));
+        assert!(policy.allows(Some("jenkins"), "ci-nightly/build", "push"));
+        assert!(!policy.allows(Some("jenkins"), "ci-nightly/build", "delete"));
+        assert!(!policy.allows(Some("jenkins"), "moredhel/test", "pull"));
+        assert!(policy.allows(Some("jenkins"), "library/ubuntu", "pull"));
+    }
+
+    #[test]
+    fn administrators() {
+        let policy = policy();
+        assert!(policy.is_admin("moredhel"));
+        assert!(!policy.is_admin("jenkins"));
+        assert!(!Policy::default().is_admin("moredhel"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Loading and checking users from an htpasswd-style file.
+//!
+//! Only bcrypt hashes (`htpasswd -B`) are supported, any other
+//! entries are skipped with a warning.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use bcrypt;
+use failure::Error;
+
+use errors;
+
+#[derive(Debug, Default)]
+pub struct Htpasswd {
+    users: HashMap<String, String>,
+}
+
+impl Htpasswd {
+    /// Reads the users out of the given file.
+    pub fn from_file(path: &str) -> Result<Htpasswd, Error> {
+        let mut contents = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .map_err(|_| errors::Server::FileNotFound(path.to_owned()))?;
+
+        Ok(Htpasswd::parse(&contents))
+    }
+
+    /// Parses `user:hash` lines, blank lines and comments are ignored.
+    pub fn parse(contents: &str) -> Htpasswd {
+        let users = contents
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(user), Some(hash)) if is_bcrypt(hash) => {
+                        Some((user.to_owned(), hash.to_owned()))
+                    }
+                    (Some(user), _) => {
+                        warn!("Skipping htpasswd entry for {}, only bcrypt is supported", user);
+                        None
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Htpasswd { users }
+    }
+
+    /// Checks the password of the given user against the stored hash.
+    pub fn verify(&self, username: &str, password: &str) -> bool {
+        self.users
+            .get(username)
+            .map(|hash| bcrypt::verify(password, hash).unwrap_or(false))
+            .unwrap_or(false)
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    hash.starts_with("$2y$") || hash.starts_with("$2a$") || hash.starts_with("$2b$")
+}
+
+#[cfg(test)]
+mod test {
+    use super::Htpasswd;
+    use bcrypt;
+
+    #[test]
+    fn parse_skips_comments_and_unsupported() {
+        let contents = "# users\n\
+                        moredhel:$2y$05$c4WoMPo3SXsafkva.HHa6uXQZWr7oboPiC2bT/r7q1BB8I2s0BRqC\n\
+                        \n\
+                        plain:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=\n";
+        let users = Htpasswd::parse(contents);
+        assert_eq!(users.len(), 1);
+    }
+
+    #[test]
+    fn verify_bcrypt() {
+        let hash = bcrypt::hash("hunter2", 4).unwrap();
+        let users = Htpasswd::parse(&format!("moredhel:{}", hash));
+        assert!(users.verify("moredhel", "hunter2"));
+        assert!(!users.verify("moredhel", "hunter3"));
+        assert!(!users.verify("nobody", "hunter2"));
+    }
+}
@@ -0,0 +1,318 @@
+//! Authentication for the registry.
+//!
+//! Lycaon can act as its own token server: users are checked against
+//! an htpasswd file with HTTP Basic auth on `/token`, which hands out
+//! short-lived JWTs that are then presented as Bearer tokens to the
+//! rest of the API.
+//!
+//! What a user may do with a repository is decided by the policies in
+//! the `acl` module, both when a token is issued and on every request.
+
+use base64;
+use failure::Error;
+use rocket::Outcome;
+use rocket::State;
+use rocket::http::{Method, Status};
+use rocket::request::{self, FromRequest, Request};
+
+use config::AuthConfig;
+use errors;
+
+pub mod acl;
+pub mod htpasswd;
+pub mod token;
+
+use self::acl::{Acl, Action};
+use self::htpasswd::Htpasswd;
+use self::token::{Claims, Scope, Token};
+
+/// Managed state holding everything needed to issue and check tokens.
+///
+/// When no `[auth]` section is configured the registry is open and
+/// every check passes.
+pub struct Authenticator {
+    config: Option<AuthConfig>,
+    users: Htpasswd,
+    acl: Acl,
+}
+
+impl Authenticator {
+    pub fn new(config: Option<AuthConfig>) -> Result<Authenticator, Error> {
+        let users = match config {
+            Some(ref config) => Htpasswd::from_file(&config.htpasswd())?,
+            None => Htpasswd::default(),
+        };
+        debug!("Loaded {} users", users.len());
+        let acl = Acl::new(config.as_ref().and_then(|config| config.acl()))?;
+
+        Ok(Authenticator { config, users, acl })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    pub fn authenticate(&self, credentials: &BasicAuth) -> bool {
+        self.users
+            .verify(&credentials.username, &credentials.password)
+    }
+
+    /// Whether _user_ (`None` when anonymous) may perform _action_ on
+    /// _repository_.
+    pub fn authorize(&self, user: Option<&str>, repository: &str, action: Action) -> bool {
+        !self.enabled() || self.acl.allows(user, repository, action)
+    }
+
+    /// Whether _user_ may use the `/admin` routes.
+    pub fn is_admin(&self, user: Option<&str>) -> bool {
+        !self.enabled() || user.map(|user| self.acl.is_admin(user)).unwrap_or(false)
+    }
+
+    /// Narrows the requested scopes down to the actions the policy allows.
+    pub fn grant(&self, user: Option<&str>, scopes: Vec<Scope>) -> Vec<Scope> {
+        scopes
+            .into_iter()
+            .filter(|scope| scope.kind == "repository")
+            .filter_map(|scope| {
+                let actions: Vec<String> = scope
+                    .actions
+                    .iter()
+                    .filter(|action| {
+                        action
+                            .parse()
+                            .map(|action| self.authorize(user, &scope.name, action))
+                            .unwrap_or(false)
+                    })
+                    .cloned()
+                    .collect();
+                match actions.is_empty() {
+                    true => None,
+                    false => Some(Scope::new(&scope.kind, &scope.name, actions)),
+                }
+            })
+            .collect()
+    }
+
+    /// Issues a token granting _scopes_ to _subject_.
+    /// An empty subject represents an anonymous user.
+    pub fn issue(&self, subject: &str, scopes: Vec<Scope>) -> Result<Token, Error> {
+        let config = self.config
+            .as_ref()
+            .ok_or(errors::Server::Invalid("auth configuration"))?;
+
+        token::issue(
+            &config.secret(),
+            &config.issuer(),
+            &config.service(),
+            subject,
+            config.token_ttl(),
+            scopes,
+        )
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, Error> {
+        let config = self.config
+            .as_ref()
+            .ok_or(errors::Server::Invalid("auth configuration"))?;
+
+        token::verify(&config.secret(), &config.issuer(), &config.service(), token)
+    }
+
+    /// The value of the `WWW-Authenticate` header telling clients
+    /// where to fetch a token from.
+    pub fn challenge(&self, scope: Option<&Scope>) -> Option<String> {
+        self.config.as_ref().map(|config| {
+            let challenge = format!(
+                "Bearer realm=\"{}\",service=\"{}\"",
+                config.realm(),
+                config.service()
+            );
+            match scope {
+                Some(scope) => format!("{},scope=\"{}\"", challenge, scope.to_string()),
+                None => challenge,
+            }
+        })
+    }
+}
+
+/// Credentials supplied with HTTP Basic authentication
+#[derive(Debug)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+impl BasicAuth {
+    fn parse(header: &str) -> Option<BasicAuth> {
+        if !header.starts_with("Basic ") {
+            return None;
+        }
+        let decoded = base64::decode(header["Basic ".len()..].trim()).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let mut parts = decoded.splitn(2, ':');
+
+        match (parts.next(), parts.next()) {
+            (Some(username), Some(password)) => Some(BasicAuth {
+                username: username.to_owned(),
+                password: password.to_owned(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for BasicAuth {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<BasicAuth, ()> {
+        match request.headers().get_one("Authorization") {
+            None => Outcome::Forward(()),
+            Some(header) => match BasicAuth::parse(header) {
+                Some(credentials) => Outcome::Success(credentials),
+                None => Outcome::Failure((Status::BadRequest, ())),
+            },
+        }
+    }
+}
+
+/// A valid Bearer token presented by the client
+#[derive(Debug)]
+pub struct AccessToken(pub Claims);
+
+impl<'a, 'r> FromRequest<'a, 'r> for AccessToken {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<AccessToken, ()> {
+        let auth = request.guard::<State<Authenticator>>()?;
+        let header = match request.headers().get_one("Authorization") {
+            Some(header) if header.starts_with("Bearer ") => header,
+            _ => return Outcome::Forward(()),
+        };
+
+        auth.verify(header["Bearer ".len()..].trim())
+            .map(|claims| Outcome::Success(AccessToken(claims)))
+            .unwrap_or_else(|e| {
+                debug!("Rejecting token: {}", e);
+                Outcome::Failure((Status::Unauthorized, ()))
+            })
+    }
+}
+
+/// Where the administration routes live
+const ADMIN_PATH: &'static str = "/admin/";
+
+/// The repository and action a registry API request operates on,
+/// taken from the request path and method.
+pub fn requested_access(request: &Request) -> Option<(String, Action)> {
+    let path = request.uri().path();
+    if !path.starts_with("/v2/") {
+        return None;
+    }
+    let path = &path["/v2/".len()..];
+    let end = ["/blobs/", "/manifests/", "/tags/", "/referrers/"]
+        .iter()
+        .filter_map(|marker| path.rfind(marker))
+        .max()?;
+
+    let action = match request.method() {
+        _ if path[end..].starts_with("/blobs/uploads") => Action::Push,
+        Method::Get | Method::Head => Action::Pull,
+        Method::Delete => Action::Delete,
+        _ => Action::Push,
+    };
+    Some((path[..end].to_owned(), action))
+}
+
+/// The scope a client should request a token for to access the
+/// repository in the request.
+pub fn requested_scope(request: &Request) -> Option<Scope> {
+    requested_access(request).map(|(repository, action)| {
+        let actions = match action {
+            Action::Push => vec!["pull".to_owned(), "push".to_owned()],
+            action => vec![action.as_str().to_owned()],
+        };
+        Scope::new("repository", &repository, actions)
+    })
+}
+
+/// Request guard checking that the caller may access the repository
+/// named in the request path, shared by every registry API handler.
+///
+/// Outside the registry API any authenticated caller is allowed, except
+/// on the `/admin` routes which need an administrator.
+///
+/// Anonymous callers that are not allowed get a 401 so they fetch a
+/// token, callers with a token that is not allowed get a 403.
+#[derive(Debug)]
+pub struct Authorized {
+    pub user: Option<String>,
+    claims: Option<Claims>,
+}
+
+impl Authorized {
+    /// Whether the caller may do _action_ on _repository_, e.g. also pull
+    /// from the repository a blob is mounted from.
+    pub fn allows(&self, auth: &Authenticator, repository: &str, action: Action) -> bool {
+        auth.authorize(self.user.as_ref().map(|u| u.as_str()), repository, action)
+            && self.claims
+                .as_ref()
+                .map(|claims| claims.allows("repository", repository, action.as_str()))
+                .unwrap_or(true)
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for Authorized {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Authorized, ()> {
+        let auth = request.guard::<State<Authenticator>>()?;
+        if !auth.enabled() {
+            return Outcome::Success(Authorized { user: None, claims: None });
+        }
+
+        let claims = match request.guard::<AccessToken>() {
+            Outcome::Success(AccessToken(claims)) => Some(claims),
+            Outcome::Forward(()) => None,
+            Outcome::Failure(failure) => return Outcome::Failure(failure),
+        };
+        let user = claims
+            .as_ref()
+            .map(|claims| claims.sub.clone())
+            .and_then(|sub| if sub.is_empty() { None } else { Some(sub) });
+
+        let authorized = Authorized { user, claims };
+
+        let allowed = match requested_access(request) {
+            Some((repository, action)) => authorized.allows(&auth, &repository, action),
+            None if request.uri().path().starts_with(ADMIN_PATH) => {
+                auth.is_admin(authorized.user.as_ref().map(|u| u.as_str()))
+            }
+            None => authorized.user.is_some(),
+        };
+
+        match (allowed, authorized.claims.is_some()) {
+            (true, _) => Outcome::Success(authorized),
+            (false, false) => Outcome::Failure((Status::Unauthorized, ())),
+            (false, true) => {
+                warn!("Denied {} {} for {:?}", request.method(), request.uri(), authorized.user);
+                Outcome::Failure((Status::Forbidden, ()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BasicAuth;
+
+    #[test]
+    fn parse_basic_auth() {
+        // moredhel:hunter2
+        let credentials = BasicAuth::parse("Basic bW9yZWRoZWw6aHVudGVyMg==").unwrap();
+        assert_eq!(credentials.username, "moredhel");
+        assert_eq!(credentials.password, "hunter2");
+
+        assert!(BasicAuth::parse("Bearer bW9yZWRoZWw6aHVudGVyMg==").is_none());
+        assert!(BasicAuth::parse("Basic !!!").is_none());
+    }
+}
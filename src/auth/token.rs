@@ -0,0 +1,165 @@
+//! Signed JWTs handed out by the `/token` endpoint.
+//!
+//! The claims follow the docker token specification, the granted
+//! scopes are carried in the `access` claim.
+
+use std::str::FromStr;
+
+use chrono::Utc;
+use failure::Error;
+use jsonwebtoken;
+use jsonwebtoken::{Header, Validation};
+use uuid::Uuid;
+
+use errors;
+
+/// A single resource and the actions requested/granted on it.
+///
+/// In its string form this looks like `repository:moredhel/test:pull,push`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scope {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    pub actions: Vec<String>,
+}
+
+impl Scope {
+    pub fn new(kind: &str, name: &str, actions: Vec<String>) -> Scope {
+        Scope {
+            kind: kind.to_owned(),
+            name: name.to_owned(),
+            actions,
+        }
+    }
+
+    pub fn allows(&self, kind: &str, name: &str, action: &str) -> bool {
+        self.kind == kind && self.name == name
+            && self.actions.iter().any(|a| a == action || a == "*")
+    }
+}
+
+impl FromStr for Scope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Scope, Error> {
+        let kind_end = s.find(':');
+        let actions_start = s.rfind(':');
+
+        match (kind_end, actions_start) {
+            (Some(k), Some(a)) if k < a => {
+                let actions = s[a + 1..]
+                    .split(',')
+                    .filter(|action| !action.is_empty())
+                    .map(|action| action.to_owned())
+                    .collect();
+                Ok(Scope::new(&s[..k], &s[k + 1..a], actions))
+            }
+            _ => Err(errors::Server::Invalid("scope").into()),
+        }
+    }
+}
+
+impl ToString for Scope {
+    fn to_string(&self) -> String {
+        format!("{}:{}:{}", self.kind, self.name, self.actions.join(","))
+    }
+}
+
+/// JWT claims of a registry token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub nbf: i64,
+    pub iat: i64,
+    pub jti: String,
+    pub access: Vec<Scope>,
+}
+
+impl Claims {
+    pub fn allows(&self, kind: &str, name: &str, action: &str) -> bool {
+        self.access.iter().any(|scope| scope.allows(kind, name, action))
+    }
+}
+
+/// A freshly issued token and its lifetime.
+#[derive(Debug)]
+pub struct Token {
+    pub token: String,
+    pub expires_in: i64,
+    pub issued_at: String,
+}
+
+/// Creates a token for _subject_ that is valid for _ttl_ seconds.
+pub fn issue(
+    secret: &str,
+    issuer: &str,
+    audience: &str,
+    subject: &str,
+    ttl: i64,
+    access: Vec<Scope>,
+) -> Result<Token, Error> {
+    let now = Utc::now();
+    let claims = Claims {
+        iss: issuer.to_owned(),
+        sub: subject.to_owned(),
+        aud: audience.to_owned(),
+        exp: now.timestamp() + ttl,
+        nbf: now.timestamp(),
+        iat: now.timestamp(),
+        jti: Uuid::new_v4().to_string(),
+        access,
+    };
+
+    let token = jsonwebtoken::encode(&Header::default(), &claims, secret.as_ref())
+        .map_err(|e| errors::Server::TokenError(e.to_string()))?;
+
+    Ok(Token {
+        token,
+        expires_in: ttl,
+        issued_at: now.to_rfc3339(),
+    })
+}
+
+/// Checks the signature, lifetime, issuer and audience of _token_.
+pub fn verify(secret: &str, issuer: &str, audience: &str, token: &str) -> Result<Claims, Error> {
+    let mut validation = Validation {
+        iss: Some(issuer.to_owned()),
+        ..Validation::default()
+    };
+    validation.set_audience(&audience);
+
+    jsonwebtoken::decode::<Claims>(token, secret.as_ref(), &validation)
+        .map(|data| data.claims)
+        .map_err(|e| errors::Server::TokenError(e.to_string()).into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_scope() {
+        let scope: Scope = "repository:moredhel/test:pull,push".parse().unwrap();
+        assert_eq!(scope.kind, "repository");
+        assert_eq!(scope.name, "moredhel/test");
+        assert_eq!(scope.actions, vec!["pull", "push"]);
+        assert!("repository".parse::<Scope>().is_err());
+    }
+
+    #[test]
+    fn issue_and_verify() {
+        let scope = Scope::new("repository", "moredhel/test", vec!["pull".to_owned()]);
+        let token = issue("secret", "lycaon", "registry", "moredhel", 60, vec![scope]).unwrap();
+
+        let claims = verify("secret", "lycaon", "registry", &token.token).unwrap();
+        assert_eq!(claims.sub, "moredhel");
+        assert!(claims.allows("repository", "moredhel/test", "pull"));
+        assert!(!claims.allows("repository", "moredhel/test", "push"));
+
+        assert!(verify("wrong", "lycaon", "registry", &token.token).is_err());
+    }
+}
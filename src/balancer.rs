@@ -0,0 +1,238 @@
+//! Balancing backend calls over several backend nodes.
+//!
+//! Frontends write blobs, manifests and uploads to their data directory,
+//! and backends read them from theirs, so all of them have to share one
+//! data directory, e.g. on a network volume. Each data directory has an
+//! id, made up by the first node using it. A frontend refuses to start
+//! while a backend answers with an id other than its own, and treats one
+//! that does later as down. Any number of frontends can share a set of
+//! backends this way.
+//!
+//! Calls go round-robin to the backends serving according to the
+//! standard gRPC health checks. A backend that stops serving is skipped
+//! until it does again; with none serving calls go out anyway, to fail
+//! the way they would with a single backend.
+//!
+//! Upload sessions and referrers are kept by the backend they were
+//! created on, so calls about a repository all go to the same backend,
+//! picked by hashing its name. Calls about a blob go to the one picked by
+//! hashing its digest. Every frontend picks the same one. If that backend
+//! is down they go to the next one serving, which doesn't know the
+//! uploads in progress.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use failure::{err_msg, Error};
+use grpc::backend::Empty;
+use grpc::backend_grpc::BackendClient;
+use grpc::health::{HealthCheckRequest, HealthCheckResponse_ServingStatus as ServingStatus};
+use grpc::health_grpc::HealthClient;
+use grpcio::EnvBuilder;
+
+use backend::auth::ClusterAuth;
+use backend::config::TlsConfig;
+use backend::tls;
+use metrics;
+
+/// Seconds a backend has to answer a health check
+const HEALTH_TIMEOUT: u64 = 2;
+
+struct Endpoint {
+    address: String,
+    client: BackendClient,
+    health: HealthClient,
+    healthy: AtomicBool,
+}
+
+#[derive(Clone)]
+pub struct Pool {
+    endpoints: Arc<Vec<Endpoint>>,
+    next: Arc<AtomicUsize>,
+    auth: ClusterAuth,
+    storage: Arc<String>,
+}
+
+impl Pool {
+    /// Connects to every backend in _addresses_ and starts checking
+    /// their health every _interval_. Backends have to store in the data
+    /// directory with the id _storage_.
+    pub fn connect(
+        addresses: Vec<String>,
+        tls: Option<&TlsConfig>,
+        auth: ClusterAuth,
+        interval: Duration,
+        storage: String,
+    ) -> Result<Pool, Error> {
+        let env = Arc::new(EnvBuilder::new().build());
+        let mut endpoints = Vec::new();
+        for address in addresses {
+            debug!("Connecting to backend: {}", address);
+            let ch = tls::connect(env.clone(), &address, tls)?;
+            endpoints.push(Endpoint {
+                address,
+                client: BackendClient::new(ch.clone()),
+                health: HealthClient::new(ch),
+                // until the first check says otherwise, so nothing is
+                // reported ready before it was asked
+                healthy: AtomicBool::new(false),
+            });
+        }
+        if endpoints.is_empty() {
+            return Err(err_msg("no backend endpoints configured"));
+        }
+
+        let pool = Pool {
+            endpoints: Arc::new(endpoints),
+            next: Arc::new(AtomicUsize::new(0)),
+            auth,
+            storage: Arc::new(storage),
+        };
+        let checked = pool.clone();
+        thread::Builder::new()
+            .name(String::from("backend-health"))
+            .spawn(move || loop {
+                check(&checked.endpoints, &checked.auth, &checked.storage);
+                thread::sleep(interval);
+            })?;
+        Ok(pool)
+    }
+
+    /// Fails if a backend stores in another data directory. Backends not
+    /// answering are left to the health checks.
+    pub fn check_storage(&self) -> Result<(), Error> {
+        for endpoint in self.endpoints.iter() {
+            if let Some(false) = same_storage(endpoint, &self.auth, &self.storage) {
+                return Err(err_msg(format!(
+                    "backend {} doesn't share this frontend's data directory",
+                    endpoint.address
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The next healthy backend, or just the next one if none are.
+    pub fn pick(&self) -> &BackendClient {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        self.pick_from(start)
+    }
+
+    /// The backend for calls about _repository_, the same one for as long
+    /// as it's healthy.
+    pub fn pick_for(&self, repository: &str) -> &BackendClient {
+        self.pick_from(hash(repository) as usize)
+    }
+
+    /// Every backend, healthy or not.
+    pub fn all(&self) -> Vec<&BackendClient> {
+        self.endpoints.iter().map(|endpoint| &endpoint.client).collect()
+    }
+
+    /// The first healthy backend from _start_ on.
+    fn pick_from(&self, start: usize) -> &BackendClient {
+        let count = self.endpoints.len();
+        let healthy = (0..count)
+            .map(|i| &self.endpoints[(start + i) % count])
+            .find(|endpoint| endpoint.healthy.load(Ordering::Relaxed));
+        match healthy {
+            Some(endpoint) => &endpoint.client,
+            None => &self.endpoints[start % count].client,
+        }
+    }
+
+    /// The addresses of all backends and whether they're healthy.
+    pub fn status(&self) -> Vec<(String, bool)> {
+        self.endpoints
+            .iter()
+            .map(|endpoint| (endpoint.address.clone(), endpoint.healthy.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+/// FNV-1a, which unlike the hasher of the standard library is the same
+/// for every frontend, whatever it was built with.
+fn hash(key: &str) -> u32 {
+    key.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+
+/// Whether _endpoint_ stores in the data directory _storage_, `None` if
+/// it didn't answer.
+fn same_storage(endpoint: &Endpoint, auth: &ClusterAuth, storage: &str) -> Option<bool> {
+    let options = auth.call_option()
+        .timeout(Duration::from_secs(HEALTH_TIMEOUT));
+    endpoint
+        .client
+        .storage_opt(&Empty::new(), options)
+        .ok()
+        .map(|reply| reply.get_text() == storage)
+}
+
+/// Checks the health of every backend, logging the ones going down or
+/// coming back. One storing elsewhere than _storage_ counts as down.
+fn check(endpoints: &[Endpoint], auth: &ClusterAuth, storage: &str) {
+    let mut healthy = 0;
+    for endpoint in endpoints {
+        let options = auth.call_option()
+            .timeout(Duration::from_secs(HEALTH_TIMEOUT));
+        let serving = match endpoint.health.check_opt(&HealthCheckRequest::new(), options) {
+            Ok(reply) => reply.get_status() == ServingStatus::SERVING,
+            Err(_) => false,
+        };
+        let up = serving && match same_storage(endpoint, auth, storage) {
+            Some(true) => true,
+            Some(false) => {
+                log::error!("Backend {} doesn't share this frontend's data directory", endpoint.address);
+                false
+            }
+            None => false,
+        };
+        let was = endpoint.healthy.swap(up, Ordering::Relaxed);
+        match (was, up) {
+            (true, false) => warn!("Backend {} is down, failing over", endpoint.address),
+            (false, true) => info!("Backend {} is back up", endpoint.address),
+            _ => {}
+        }
+        if up {
+            healthy += 1;
+        }
+    }
+    metrics::HEALTHY_BACKENDS.set(healthy as f64);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn repositories_stick_to_a_backend() {
+        let addresses = vec![String::from("127.0.0.1:1"), String::from("127.0.0.1:2")];
+        let pool = Pool::connect(
+            addresses,
+            None,
+            ClusterAuth::new(None),
+            Duration::from_secs(60),
+            String::from("storage"),
+        ).unwrap();
+        // nothing answers, so nothing is refused
+        assert!(pool.check_storage().is_ok());
+        let backend = pool.pick_for("library/alpine") as *const BackendClient;
+        for _ in 0..4 {
+            assert_eq!(pool.pick_for("library/alpine") as *const _, backend);
+        }
+        // other calls still go round-robin over both
+        let first = pool.pick() as *const BackendClient;
+        assert!(pool.pick() as *const _ != first);
+    }
+
+    #[test]
+    fn fnv_hash() {
+        assert_eq!(hash(""), 0x811c9dc5);
+        assert_eq!(hash("a"), 0xe40c292c);
+        assert_eq!(hash("library/alpine"), hash("library/alpine"));
+    }
+}
@@ -0,0 +1,212 @@
+//! The subcommands of the `lycaon` binary.
+//!
+//! Each returns the exit code of the process. Offline commands report
+//! to stdout and fail on stderr.
+
+use std::path::Path;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use clap::ArgMatches;
+use failure::{err_msg, Error};
+
+use archive;
+use backend;
+use config::{self, LycaonConfig};
+use fsck;
+use gc;
+use log;
+use shutdown;
+
+/// Everything went fine
+pub const EXIT_OK: i32 = 0;
+/// The command ran but found problems, or the server didn't start
+pub const EXIT_PROBLEMS: i32 = 1;
+/// The command couldn't run
+pub const EXIT_FAILED: i32 = 2;
+
+fn load_config(args: &ArgMatches) -> Result<LycaonConfig, Error> {
+    match args.value_of("config") {
+        Some(v) => LycaonConfig::new(&v),
+        None => LycaonConfig::default(),
+    }
+}
+
+/// Runs the backend server of _node_ in its own thread.
+fn grpc(cnfg: &LycaonConfig, node: backend::Node) -> shutdown::Server {
+    debug!("Setting up RPC Server");
+
+    let grpc = cnfg.grpc();
+    let stop = backend::Stop::new();
+    let stopped = stop.clone();
+    let thread = thread::spawn(move || {
+        if let Err(e) = backend::serve(grpc, node, stopped) {
+            log::error!("Backend server failed to start {}", e);
+            process::exit(EXIT_PROBLEMS);
+        }
+    });
+    (stop, thread)
+}
+
+fn data_dir<'a>(args: &'a ArgMatches) -> &'a Path {
+    Path::new(args.value_of("data-dir").unwrap_or(config::DEFAULT_DATA_DIR))
+}
+
+fn failed(command: &str, e: Error) -> i32 {
+    eprintln!("{} failed: {}", command, e);
+    EXIT_FAILED
+}
+
+/// `lycaon serve`, running both servers unless told to run only one.
+pub fn serve(args: &ArgMatches, serve: &ArgMatches) -> i32 {
+    config::main_logger(args).expect("Failed to init logging");
+
+    let cnfg = match load_config(args) {
+        Ok(cnfg) => cnfg,
+        Err(e) => {
+            log::error!("Failed to read the config {}", e);
+            return EXIT_PROBLEMS;
+        }
+    };
+    let grace = Duration::from_secs(cnfg.shutdown().grace_period());
+
+    // GRPC Backend thread, sharing its backend with the frontend when
+    // that's embedded.
+    let mut embedded = None;
+    let mut server = None;
+    if !serve.is_present("frontend-only") {
+        let node = match backend::Node::new(&cnfg.grpc(), data_dir(serve)) {
+            Ok(node) => node,
+            Err(e) => {
+                log::error!("Failed to set up the backend {}", e);
+                return EXIT_PROBLEMS;
+            }
+        };
+        if cnfg.embedded() {
+            embedded = Some(node.backend());
+        }
+        server = Some(grpc(&cnfg, node));
+    }
+
+    if serve.is_present("backend-only") {
+        if let Err(e) = shutdown::attach_sigterm(grace, None, server) {
+            log::error!("Failed to attach the SIGTERM handler {}", e);
+            return EXIT_PROBLEMS;
+        }
+        // until SIGTERM exits
+        loop {
+            thread::park();
+        }
+    }
+
+    //Rocket web stuff
+    let rocket = match config::rocket(args, embedded, data_dir(serve)) {
+        Ok(rocket) => rocket,
+        Err(e) => {
+            log::error!("Rocket failed to process arguments {}", e);
+            return EXIT_PROBLEMS;
+        }
+    };
+    let drain = shutdown::Drain::new();
+    if let Err(e) = shutdown::attach_sigterm(grace, Some(drain.clone()), server) {
+        log::error!("Failed to attach the SIGTERM handler {}", e);
+        return EXIT_PROBLEMS;
+    }
+    let rocket = rocket.attach(drain);
+    let e = rocket.launch();
+    log::error!("Rocket failed to launch {}", e);
+    EXIT_PROBLEMS
+}
+
+/// `lycaon gc`
+pub fn gc(args: &ArgMatches) -> i32 {
+    let collected = gc::collect(
+        data_dir(args),
+        args.is_present("delete-untagged"),
+        args.is_present("dry-run"),
+    );
+    match collected {
+        Ok(report) => {
+            print!("{}", report);
+            EXIT_OK
+        }
+        Err(e) => failed("gc", e),
+    }
+}
+
+/// `lycaon fsck`, problems remain if some couldn't be repaired.
+pub fn fsck(args: &ArgMatches) -> i32 {
+    match fsck::check(data_dir(args), args.is_present("repair")) {
+        Ok(report) => {
+            print!("{}", report);
+            match report.clean() {
+                true => EXIT_OK,
+                false => EXIT_PROBLEMS,
+            }
+        }
+        Err(e) => failed("fsck", e),
+    }
+}
+
+/// `lycaon import`
+pub fn import(args: &ArgMatches) -> i32 {
+    let layout = Path::new(args.value_of("layout").unwrap_or_default());
+    let repository = args.value_of("repository").unwrap_or_default();
+    match archive::import(data_dir(args), layout, repository) {
+        Ok(report) => {
+            println!(
+                "imported {} manifests and {} blobs, {} bytes, tagged {}",
+                report.manifests,
+                report.blobs,
+                report.bytes,
+                report.tags.join(", ")
+            );
+            EXIT_OK
+        }
+        Err(e) => failed("import", e),
+    }
+}
+
+/// `lycaon export`
+pub fn export(args: &ArgMatches) -> i32 {
+    let repository = args.value_of("repository").unwrap_or_default();
+    let tags: Vec<String> = args.values_of("tag")
+        .map(|tags| tags.map(|tag| tag.to_owned()).collect())
+        .unwrap_or_default();
+    let output = Path::new(args.value_of("output").unwrap_or_default());
+    match archive::export(data_dir(args), repository, &tags, output) {
+        Ok(report) => {
+            println!(
+                "exported {} manifests and {} blobs, {} bytes, tagged {}",
+                report.manifests,
+                report.blobs,
+                report.bytes,
+                report.tags.join(", ")
+            );
+            EXIT_OK
+        }
+        Err(e) => failed("export", e),
+    }
+}
+
+/// `lycaon peers`, problems if any peer is down.
+pub fn peers(args: &ArgMatches, peers: &ArgMatches) -> i32 {
+    let timeout = match peers.value_of("timeout").unwrap_or("5").parse() {
+        Ok(timeout) => Duration::from_secs(timeout),
+        Err(_) => return failed("peers", err_msg("timeout must be a number of seconds")),
+    };
+    let probed = load_config(args).and_then(|cnfg| backend::probe(&cnfg.grpc(), timeout));
+    match probed {
+        Ok(status) => {
+            for &(ref address, up) in &status {
+                println!("{} {}", address, if up { "up" } else { "down" });
+            }
+            match status.iter().all(|&(_, up)| up) {
+                true => EXIT_OK,
+                false => EXIT_PROBLEMS,
+            }
+        }
+        Err(e) => failed("peers", e),
+    }
+}
@@ -3,28 +3,40 @@
 //! system configuration.
 
 use std;
-use std::env;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::sync::mpsc;
 use std::fs;
 
 use clap;
-use clap::{Arg, ArgMatches};
-use env_logger;
+use clap::{Arg, ArgMatches, SubCommand};
 use failure::Error;
-use ctrlc;
-use log::{LogLevelFilter, LogRecord, SetLoggerError};
 use rocket;
 use rocket::fairing;
 
+use auth;
 use backend;
+use balancer;
+use datadir::DataDir;
 use errors;
-use grpc::backend_grpc::BackendClient;
+use logging;
+use metrics;
+use notifications;
+use proxy;
+use replication;
 use routes;
+use rpc;
+use scrub;
+use state;
+use trace;
 
-static DEFAULT_DATA_DIR: &'static str = "data";
-static SCRATCH_DIR: &'static str = "scratch";
-static LAYERS_DIR: &'static str = "layers";
+/// Where the registry keeps its data unless told otherwise, relative
+/// to where it's started
+pub static DEFAULT_DATA_DIR: &'static str = "data";
+pub static SCRATCH_DIR: &'static str = "scratch";
+pub static LAYERS_DIR: &'static str = "layers";
+pub static QUARANTINE_DIR: &'static str = "quarantine";
 
 const PROGRAM_NAME: &'static str = "Lycaon";
 const PROGRAM_DESC: &'static str = "\nThe King of Registries";
@@ -57,18 +69,565 @@ impl Service {
 #[derive(Clone, Debug, Deserialize)]
 pub struct HttpConfig {
     listen: Service,
+    tls: Option<HttpTlsConfig>,
 }
 
 impl HttpConfig {
     fn listen(&self) -> Service {
         self.listen.clone()
     }
+
+    fn tls(&self) -> Option<HttpTlsConfig> {
+        self.tls.clone()
+    }
+}
+
+/// Certificate chain and private key (PEM) for serving HTTPS.
+#[derive(Clone, Debug, Deserialize)]
+pub struct HttpTlsConfig {
+    certs: String,
+    key: String,
+}
+
+fn default_token_ttl() -> i64 {
+    300
+}
+
+/// Settings for the built-in token server.
+///
+/// _realm_ is the URL clients are sent to for a token, usually
+/// pointing back at this registry's `/token` endpoint.
+/// _acl_ optionally points at a file of repository access policies.
+#[derive(Clone, Debug, Deserialize)]
+pub struct AuthConfig {
+    realm: String,
+    service: String,
+    issuer: String,
+    htpasswd: String,
+    secret: String,
+    #[serde(default = "default_token_ttl")]
+    token_ttl: i64,
+    acl: Option<String>,
+}
+
+impl AuthConfig {
+    pub fn realm(&self) -> String {
+        self.realm.clone()
+    }
+
+    pub fn service(&self) -> String {
+        self.service.clone()
+    }
+
+    pub fn issuer(&self) -> String {
+        self.issuer.clone()
+    }
+
+    pub fn htpasswd(&self) -> String {
+        self.htpasswd.clone()
+    }
+
+    pub fn secret(&self) -> String {
+        self.secret.clone()
+    }
+
+    pub fn token_ttl(&self) -> i64 {
+        self.token_ttl
+    }
+
+    pub fn acl(&self) -> Option<String> {
+        self.acl.clone()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+fn default_log_level() -> String {
+    String::from("error")
+}
+
+fn default_log_format() -> LogFormat {
+    LogFormat::Text
+}
+
+fn default_log_output() -> String {
+    String::from("stdout")
+}
+
+fn default_log_max_size() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_log_max_files() -> u32 {
+    5
+}
+
+/// Settings for the `[logging]` section.
+///
+/// _output_ and _access_log_ are either `stdout`, `stderr` or the path
+/// of a file, which is rotated after _max_size_ bytes keeping
+/// _max_files_ old files. _modules_ sets the level of single modules.
+#[derive(Clone, Debug, Deserialize)]
+pub struct LoggingConfig {
+    #[serde(default = "default_log_level")]
+    level: String,
+    #[serde(default = "default_log_format")]
+    format: LogFormat,
+    #[serde(default = "default_log_output")]
+    output: String,
+    access_log: Option<String>,
+    #[serde(default = "default_log_max_size")]
+    max_size: u64,
+    #[serde(default = "default_log_max_files")]
+    max_files: u32,
+    #[serde(default)]
+    modules: HashMap<String, String>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> LoggingConfig {
+        LoggingConfig {
+            level: default_log_level(),
+            format: default_log_format(),
+            output: default_log_output(),
+            access_log: None,
+            max_size: default_log_max_size(),
+            max_files: default_log_max_files(),
+            modules: HashMap::new(),
+        }
+    }
+}
+
+impl LoggingConfig {
+    pub fn level(&self) -> String {
+        self.level.clone()
+    }
+
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    pub fn output(&self) -> String {
+        self.output.clone()
+    }
+
+    pub fn access_log(&self) -> Option<String> {
+        self.access_log.clone()
+    }
+
+    pub fn max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    pub fn max_files(&self) -> u32 {
+        self.max_files
+    }
+
+    pub fn modules(&self) -> HashMap<String, String> {
+        self.modules.clone()
+    }
+}
+
+/// Webhook endpoints registry events are sent to, see `notifications`.
+///
+/// _queue_ is the directory undelivered events are kept in,
+/// `notifications` in the data directory if not set.
+#[derive(Clone, Debug, Deserialize)]
+pub struct NotificationsConfig {
+    queue: Option<String>,
+    #[serde(default)]
+    endpoints: Vec<EndpointConfig>,
+}
+
+impl NotificationsConfig {
+    pub fn queue(&self) -> Option<PathBuf> {
+        self.queue.as_ref().map(PathBuf::from)
+    }
+
+    pub fn endpoints(&self) -> Vec<EndpointConfig> {
+        self.endpoints.clone()
+    }
+}
+
+fn default_endpoint_timeout() -> u64 {
+    5
+}
+
+fn default_endpoint_max_retries() -> u32 {
+    10
+}
+
+fn default_endpoint_backoff() -> u64 {
+    1
+}
+
+fn default_endpoint_max_backoff() -> u64 {
+    300
+}
+
+/// A single webhook.
+///
+/// Only events for _repositories_ matching one of the patterns (same
+/// syntax as the access policies) and with one of _actions_ are sent,
+/// either being empty means everything. Failed deliveries are retried
+/// after _backoff_ seconds, doubling up to _max_backoff_.
+#[derive(Clone, Debug, Deserialize)]
+pub struct EndpointConfig {
+    name: String,
+    url: String,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default = "default_endpoint_timeout")]
+    timeout: u64,
+    #[serde(default = "default_endpoint_max_retries")]
+    max_retries: u32,
+    #[serde(default = "default_endpoint_backoff")]
+    backoff: u64,
+    #[serde(default = "default_endpoint_max_backoff")]
+    max_backoff: u64,
+    #[serde(default)]
+    repositories: Vec<String>,
+    #[serde(default)]
+    actions: Vec<String>,
+}
+
+impl EndpointConfig {
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn headers(&self) -> HashMap<String, String> {
+        self.headers.clone()
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn backoff(&self) -> u64 {
+        self.backoff
+    }
+
+    pub fn max_backoff(&self) -> u64 {
+        self.max_backoff
+    }
+
+    pub fn repositories(&self) -> Vec<String> {
+        self.repositories.clone()
+    }
+
+    pub fn actions(&self) -> Vec<String> {
+        self.actions.clone()
+    }
+}
+
+/// Where finished spans are written to, as OTLP/JSON lines.
+/// Without an _export_ file spans go to the debug log.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TracingConfig {
+    export: Option<String>,
+}
+
+impl TracingConfig {
+    pub fn export(&self) -> Option<String> {
+        self.export.clone()
+    }
+}
+
+fn default_proxy_ttl() -> u64 {
+    300
+}
+
+fn default_proxy_timeout() -> u64 {
+    30
+}
+
+/// Upstream registry to act as a pull-through cache for, see `proxy`.
+///
+/// Manifests pulled by tag are checked against the upstream again
+/// once they are older than _ttl_ seconds.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ProxyConfig {
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default = "default_proxy_ttl")]
+    ttl: u64,
+    #[serde(default = "default_proxy_timeout")]
+    timeout: u64,
+}
+
+impl ProxyConfig {
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn username(&self) -> Option<String> {
+        self.username.clone()
+    }
+
+    pub fn password(&self) -> Option<String> {
+        self.password.clone()
+    }
+
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+}
+
+/// Registries images are copied to, see `replication`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReplicationConfig {
+    #[serde(default)]
+    rules: Vec<ReplicationRule>,
+}
+
+impl ReplicationConfig {
+    pub fn rules(&self) -> Vec<ReplicationRule> {
+        self.rules.clone()
+    }
+}
+
+fn default_replication_tags() -> String {
+    String::from(".*")
+}
+
+fn default_replication_timeout() -> u64 {
+    300
+}
+
+/// A single replication target.
+///
+/// Tags matching the _tags_ regex in _repositories_ matching one of
+/// the patterns (same syntax as the access policies, empty means all)
+/// are pushed to the registry at _url_. Failed pushes are retried
+/// after _backoff_ seconds, doubling up to _max_backoff_.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ReplicationRule {
+    name: String,
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+    #[serde(default)]
+    repositories: Vec<String>,
+    #[serde(default = "default_replication_tags")]
+    tags: String,
+    #[serde(default = "default_replication_timeout")]
+    timeout: u64,
+    #[serde(default = "default_endpoint_max_retries")]
+    max_retries: u32,
+    #[serde(default = "default_endpoint_backoff")]
+    backoff: u64,
+    #[serde(default = "default_endpoint_max_backoff")]
+    max_backoff: u64,
+}
+
+impl ReplicationRule {
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    pub fn url(&self) -> String {
+        self.url.clone()
+    }
+
+    pub fn username(&self) -> Option<String> {
+        self.username.clone()
+    }
+
+    pub fn password(&self) -> Option<String> {
+        self.password.clone()
+    }
+
+    pub fn repositories(&self) -> Vec<String> {
+        self.repositories.clone()
+    }
+
+    pub fn tags(&self) -> String {
+        self.tags.clone()
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn backoff(&self) -> u64 {
+        self.backoff
+    }
+
+    pub fn max_backoff(&self) -> u64 {
+        self.max_backoff
+    }
+}
+
+fn default_scrub_interval() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_scrub_rate() -> u64 {
+    32 * 1024 * 1024
+}
+
+/// Background re-hashing of stored blobs, see `scrub`.
+///
+/// A pass over every blob starts each _interval_ seconds and reads at
+/// most _rate_ bytes per second, 0 means unthrottled.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScrubConfig {
+    #[serde(default = "default_scrub_interval")]
+    interval: u64,
+    #[serde(default = "default_scrub_rate")]
+    rate: u64,
+}
+
+impl ScrubConfig {
+    pub fn interval(&self) -> u64 {
+        self.interval
+    }
+
+    pub fn rate(&self) -> u64 {
+        self.rate
+    }
+}
+
+fn default_grace_period() -> u64 {
+    30
+}
+
+/// What happens on SIGTERM, see `shutdown`.
+///
+/// _grace_period_: seconds requests in flight get to finish
+#[derive(Clone, Debug, Deserialize)]
+pub struct ShutdownConfig {
+    #[serde(default = "default_grace_period")]
+    grace_period: u64,
+}
+
+impl ShutdownConfig {
+    pub fn grace_period(&self) -> u64 {
+        self.grace_period
+    }
+}
+
+fn default_health_interval() -> u64 {
+    5
+}
+
+fn default_backend_timeout() -> u64 {
+    5
+}
+
+fn default_backend_max_retries() -> u32 {
+    2
+}
+
+fn default_backend_backoff() -> u64 {
+    100
+}
+
+fn default_breaker_threshold() -> u32 {
+    5
+}
+
+fn default_breaker_cooldown() -> u64 {
+    10
+}
+
+/// The backend nodes a frontend balances its calls over.
+///
+/// _endpoints_: the backends, `grpc.listen` if there are none
+/// _health_interval_: seconds between health checks of every backend
+/// _timeout_: seconds a call has to get an answer
+/// _max_retries_: retries of calls that are safe to repeat, after
+/// _backoff_ milliseconds doubling with every retry, while that still
+/// fits in _timeout_
+/// _breaker_threshold_: failures in a row after which calls fail fast
+/// for _breaker_cooldown_ seconds
+#[derive(Clone, Debug, Deserialize)]
+pub struct BackendsConfig {
+    #[serde(default)]
+    endpoints: Vec<Service>,
+    #[serde(default = "default_health_interval")]
+    health_interval: u64,
+    #[serde(default = "default_backend_timeout")]
+    timeout: u64,
+    #[serde(default = "default_backend_max_retries")]
+    max_retries: u32,
+    #[serde(default = "default_backend_backoff")]
+    backoff: u64,
+    #[serde(default = "default_breaker_threshold")]
+    breaker_threshold: u32,
+    #[serde(default = "default_breaker_cooldown")]
+    breaker_cooldown: u64,
+}
+
+impl BackendsConfig {
+    pub fn endpoints(&self) -> Vec<Service> {
+        self.endpoints.clone()
+    }
+
+    pub fn health_interval(&self) -> u64 {
+        self.health_interval
+    }
+
+    pub fn timeout(&self) -> u64 {
+        self.timeout
+    }
+
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    pub fn backoff(&self) -> u64 {
+        self.backoff
+    }
+
+    pub fn breaker_threshold(&self) -> u32 {
+        self.breaker_threshold
+    }
+
+    pub fn breaker_cooldown(&self) -> u64 {
+        self.breaker_cooldown
+    }
 }
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct LycaonConfig {
     grpc: backend::config::LycaonBackendConfig,
+    /// Calls the backend in this process rather than over gRPC
+    #[serde(default)]
+    embedded: bool,
+    backends: Option<BackendsConfig>,
     web: HttpConfig,
+    auth: Option<AuthConfig>,
+    tracing: Option<TracingConfig>,
+    logging: Option<LoggingConfig>,
+    notifications: Option<NotificationsConfig>,
+    proxy: Option<ProxyConfig>,
+    replication: Option<ReplicationConfig>,
+    scrub: Option<ScrubConfig>,
+    shutdown: Option<ShutdownConfig>,
 }
 
 impl LycaonConfig {
@@ -79,7 +638,13 @@ impl LycaonConfig {
         s.merge(File::with_name(&file))?;
         s.merge(Environment::with_prefix("lycaon"))?;
 
-        s.try_into().map_err(|e| e.into())
+        let config: LycaonConfig = s.try_into()?;
+        if let Some(token) = config.grpc.token() {
+            if !backend::auth::valid_token(&token) {
+                return Err(errors::Server::Invalid("cluster token").into());
+            }
+        }
+        Ok(config)
     }
 
     pub fn default() -> Result<Self, Error> {
@@ -98,6 +663,56 @@ impl LycaonConfig {
     pub fn grpc(&self) -> backend::config::LycaonBackendConfig {
         self.grpc.clone()
     }
+
+    pub fn embedded(&self) -> bool {
+        self.embedded
+    }
+
+    pub fn backends(&self) -> BackendsConfig {
+        self.backends.clone().unwrap_or_else(|| BackendsConfig {
+            endpoints: Vec::new(),
+            health_interval: default_health_interval(),
+            timeout: default_backend_timeout(),
+            max_retries: default_backend_max_retries(),
+            backoff: default_backend_backoff(),
+            breaker_threshold: default_breaker_threshold(),
+            breaker_cooldown: default_breaker_cooldown(),
+        })
+    }
+
+    pub fn auth(&self) -> Option<AuthConfig> {
+        self.auth.clone()
+    }
+
+    pub fn tracing(&self) -> Option<TracingConfig> {
+        self.tracing.clone()
+    }
+
+    pub fn logging(&self) -> LoggingConfig {
+        self.logging.clone().unwrap_or_default()
+    }
+
+    pub fn notifications(&self) -> Option<NotificationsConfig> {
+        self.notifications.clone()
+    }
+
+    pub fn proxy(&self) -> Option<ProxyConfig> {
+        self.proxy.clone()
+    }
+
+    pub fn replication(&self) -> Option<ReplicationConfig> {
+        self.replication.clone()
+    }
+
+    pub fn scrub(&self) -> Option<ScrubConfig> {
+        self.scrub.clone()
+    }
+
+    pub fn shutdown(&self) -> ShutdownConfig {
+        self.shutdown.clone().unwrap_or_else(|| {
+            ShutdownConfig { grace_period: default_grace_period() }
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -140,27 +755,14 @@ impl SocketHandler {
 }
 
 /// Build the logging agent with formatting.
-pub fn main_logger() -> Result<(), SetLoggerError> {
-    let mut builder = env_logger::LogBuilder::new();
-    builder
-        .format(|record: &LogRecord| {
-            format!("{}[{}] {}", record.target(), record.level(), record.args(),)
-        })
-        .filter(None, LogLevelFilter::Error);
-
-    if env::var("RUST_LOG").is_ok() {
-        builder.parse(&env::var("RUST_LOG").unwrap());
-    }
-
-    builder.init()
-}
+pub fn main_logger(args: &ArgMatches) -> Result<(), Error> {
+    let config = match args.value_of("config") {
+        Some(v) => LycaonConfig::new(&v),
+        None => LycaonConfig::default(),
+    };
+    let logging_config = config.map(|config| config.logging()).unwrap_or_default();
 
-/// Attaches SIGTERM handler
-fn attach_sigterm() -> Result<(), Error> {
-    ctrlc::set_handler(|| {
-        info!("SIGTERM caught, shutting down...");
-        std::process::exit(0);
-    }).map_err(|e| e.into())
+    logging::init(logging_config)
 }
 
 /// Creates needed directories under given path if they don't already exist.
@@ -175,8 +777,10 @@ fn create_data_dirs(data_path: &Path) -> Result<(), Error> {
 
     let scratch_path = data_path.join(SCRATCH_DIR);
     let layers_path = data_path.join(LAYERS_DIR);
+    let quarantine_path = data_path.join(QUARANTINE_DIR);
     setup_path(scratch_path)
         .and(setup_path(layers_path))
+        .and(setup_path(quarantine_path))
         .map_err(|e| errors::Server::ConfigError(e).into())
 }
 
@@ -198,60 +802,102 @@ pub(crate) fn extract_config(conf: &rocket::Config) -> Result<Config, Error> {
 
 /// Handle all code relating to bootstrapping the project
 ///
-/// - attach SIGTERM handler
-/// - Check necessary paths exist
 /// - Extract configuration values needed for runtime
 fn startup(rocket: rocket::Rocket) -> Result<rocket::Rocket, rocket::Rocket> {
-    attach_sigterm()
-        .and(create_data_dirs(Path::new(DEFAULT_DATA_DIR)))
-        .and(extract_config(rocket.config()))
+    extract_config(rocket.config())
         .and_then(|config| Ok(rocket.manage(config)))
         .map_err(|e| panic!("{}", e))
 }
 
+/// The backend of this frontend, see `rpc`.
+#[derive(Clone)]
 pub struct BackendHandler {
-    backend: BackendClient,
+    backend: Arc<rpc::Backend>,
 }
 
 impl BackendHandler {
-    fn new(backend: BackendClient) -> Self {
-        BackendHandler { backend }
+    fn new<B: rpc::Backend + 'static>(backend: B) -> Self {
+        BackendHandler { backend: Arc::new(backend) }
     }
 
-    pub fn backend(&self) -> &BackendClient {
-        &self.backend
+    pub fn backend(&self) -> &rpc::Backend {
+        &*self.backend
     }
-}
 
-fn build_handlers(config: &LycaonConfig) -> BackendHandler {
-    use std::sync::Arc;
-    use grpcio::{ChannelBuilder, EnvBuilder};
+    /// Every backend's address and whether it's healthy.
+    pub fn status(&self) -> Vec<(String, bool)> {
+        self.backend.status()
+    }
+}
 
-    let backend = config.grpc().listen();
-    debug!(
-        "Connecting to backend: {}:{}",
-        backend.host(),
-        backend.port()
-    );
-    let env = Arc::new(EnvBuilder::new().build());
-    let ch = ChannelBuilder::new(env).connect(&format!("{}:{}", backend.host(), backend.port()));
-    let client = BackendClient::new(ch);
-    BackendHandler::new(client)
+/// Calls _embedded_ directly if there is one, otherwise the backends
+/// over gRPC. Those have to store in the same data directory as this
+/// frontend, _data_.
+fn build_handlers(
+    config: &LycaonConfig,
+    embedded: Option<backend::BackendService>,
+    data: &DataDir,
+) -> Result<BackendHandler, Error> {
+    if let Some(service) = embedded {
+        debug!("Calling the embedded backend");
+        return Ok(BackendHandler::new(rpc::Embedded::new(service)));
+    }
+    let backends = config.backends();
+    let mut endpoints = backends.endpoints();
+    if endpoints.is_empty() {
+        // the backend of this process, which may listen on every address
+        let listen = config.grpc().listen();
+        let host = match listen.host().as_str() {
+            "0.0.0.0" | "::" | "[::]" => String::from("localhost"),
+            host => host.to_owned(),
+        };
+        endpoints.push(Service {
+            host,
+            port: listen.port(),
+        });
+    }
+    let addresses = endpoints
+        .iter()
+        .map(|backend| format!("{}:{}", backend.host(), backend.port()))
+        .collect();
+    let auth = backend::auth::ClusterAuth::new(config.grpc().token());
+    let pool = balancer::Pool::connect(
+        addresses,
+        config.grpc().tls().as_ref(),
+        auth.clone(),
+        std::time::Duration::from_secs(backends.health_interval()),
+        backend::storage_id(data.root())?,
+    )?;
+    pool.check_storage()?;
+    Ok(BackendHandler::new(rpc::Remote::new(pool, auth, &backends)))
 }
 
 fn build_rocket_config(config: &LycaonConfig) -> rocket::config::Config {
     debug!("Config: {:?}", config.web);
     let bind = config.web.listen();
-    rocket::config::Config::build(rocket::config::Environment::Production)
+    let builder = rocket::config::Config::build(rocket::config::Environment::Production)
         .address(bind.host())
-        .port(bind.port())
+        .port(bind.port());
+    let builder = match config.web.tls() {
+        Some(tls) => builder.tls(tls.certs, tls.key),
+        None => builder,
+    };
+    builder
         .finalize()
         .expect("Error building Rocket Config")
 }
 
-/// Construct the rocket instance and prepare for launch
-pub(crate) fn rocket(args: &ArgMatches) -> Result<rocket::Rocket, Error> {
-    
+/// Construct the rocket instance and prepare for launch, calling the
+/// _embedded_ backend if there is one and keeping its data in
+/// _data_dir_.
+pub(crate) fn rocket(
+    args: &ArgMatches,
+    embedded: Option<backend::BackendService>,
+    data_dir: &Path,
+) -> Result<rocket::Rocket, Error> {
+    create_data_dirs(data_dir)?;
+    let data = DataDir::open(data_dir)?;
+
     let f = args.value_of("config");
 
     let config = match f {
@@ -261,16 +907,55 @@ pub(crate) fn rocket(args: &ArgMatches) -> Result<rocket::Rocket, Error> {
 
     let rocket_config = build_rocket_config(&config);
     debug!("Config: {:?}", config);
+    let authenticator = auth::Authenticator::new(config.auth())?;
+    if let Some(path) = config.tracing().and_then(|tracing| tracing.export()) {
+        backend::trace::export_to(&path)?;
+    }
+    let notifier = notifications::Notifier::new(
+        config.notifications(),
+        notifications::source_addr(config.web.listen().port()),
+        &data,
+    )?;
+    let proxy = proxy::Proxy::new(config.proxy(), data.clone())?;
+    let replicator = replication::Replicator::new(config.replication(), data.clone())?;
+    let handler = build_handlers(&config, embedded, &data)?;
+    let scrubber = match config.scrub() {
+        Some(scrub) => scrub::Scrubber::start(scrub, handler.clone(), data.clone())?,
+        None => scrub::Scrubber::disabled(),
+    };
     Ok(rocket::custom(rocket_config, true)
-        .manage(build_handlers(&config))
+        .manage(handler)
+        .manage(authenticator)
+        .manage(notifier)
+        .manage(proxy)
+        .manage(replicator)
+        .manage(scrubber)
+        .manage(state::uploads::Uploads::new(data.clone()))
+        .manage(data)
         .manage(config)
         .attach(fairing::AdHoc::on_attach(startup))
+        .attach(trace::RequestId)
+        .attach(metrics::Metrics)
+        .attach(logging::AccessLog)
         .mount("/", routes::routes())
         .catch(routes::errors()))
 }
 
+/// Help of the offline commands' `--data-dir`
+const STOPPED_DATA_DIR: &'static str = "The data directory of a stopped registry";
+
+/// The data directory a command works on, see `datadir`.
+fn data_dir_arg<'a, 'b>(help: &'a str) -> Arg<'a, 'b> {
+    Arg::with_name("data-dir")
+        .long("data-dir")
+        .value_name("DIR")
+        .help(help)
+        .default_value(DEFAULT_DATA_DIR)
+}
+
 /*
   Parses command line arguments and returns ArgMatches object.
+  Without a subcommand the registry is served.
 */
 pub fn parse_args<'a>() -> ArgMatches<'a> {
     clap::App::new(PROGRAM_NAME)
@@ -285,5 +970,116 @@ pub fn parse_args<'a>() -> ArgMatches<'a> {
                 .help("Sets a custom config file")
                 .takes_value(true),
         )
+        .subcommand(
+            SubCommand::with_name("serve")
+                .about("Runs the registry, the default")
+                .arg(data_dir_arg("Where the registry keeps its data"))
+                .arg(
+                    Arg::with_name("frontend-only")
+                        .long("frontend-only")
+                        .conflicts_with("backend-only")
+                        .help("Only serves HTTP, calling the configured backends"),
+                )
+                .arg(
+                    Arg::with_name("backend-only")
+                        .long("backend-only")
+                        .help("Only runs the gRPC backend"),
+                )
+                .after_help("EXIT CODES:\n    0  shut down\n    1  failed to start"),
+        )
+        .subcommand(
+            SubCommand::with_name("gc")
+                .about("Deletes blobs no manifest refers to")
+                .arg(data_dir_arg(STOPPED_DATA_DIR))
+                .arg(
+                    Arg::with_name("delete-untagged")
+                        .long("delete-untagged")
+                        .help("Also deletes manifests no tag leads to"),
+                )
+                .arg(
+                    Arg::with_name("dry-run")
+                        .long("dry-run")
+                        .help("Only reports what would be deleted"),
+                )
+                .after_help("EXIT CODES:\n    0  collected\n    2  failed"),
+        )
+        .subcommand(
+            SubCommand::with_name("fsck")
+                .about("Checks the data directory of a stopped registry")
+                .arg(data_dir_arg(STOPPED_DATA_DIR))
+                .arg(
+                    Arg::with_name("repair")
+                        .long("repair")
+                        .help("Deletes orphans and quarantines corrupt content"),
+                )
+                .after_help(
+                    "EXIT CODES:\n    0  consistent or repaired\n    1  problems remain\n    \
+                     2  failed",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Imports an OCI image layout into a repository")
+                .arg(data_dir_arg(STOPPED_DATA_DIR))
+                .arg(
+                    Arg::with_name("layout")
+                        .value_name("LAYOUT")
+                        .help("The directory of the image layout")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("repository")
+                        .value_name("REPOSITORY")
+                        .help("The repository to import into")
+                        .required(true),
+                )
+                .after_help("EXIT CODES:\n    0  imported\n    2  failed"),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Exports tags of a repository as an OCI image layout")
+                .arg(data_dir_arg(STOPPED_DATA_DIR))
+                .arg(
+                    Arg::with_name("repository")
+                        .value_name("REPOSITORY")
+                        .help("The repository to export")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("tag")
+                        .short("t")
+                        .long("tag")
+                        .value_name("TAG")
+                        .help("Exports only this tag, may be repeated")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .short("o")
+                        .long("output")
+                        .value_name("DIR")
+                        .help("The directory to write the layout to")
+                        .takes_value(true)
+                        .required(true),
+                )
+                .after_help("EXIT CODES:\n    0  exported\n    2  failed"),
+        )
+        .subcommand(
+            SubCommand::with_name("peers")
+                .about("Checks the configured peers answer heartbeats")
+                .arg(
+                    Arg::with_name("timeout")
+                        .long("timeout")
+                        .value_name("SECONDS")
+                        .help("How long to wait for each peer")
+                        .default_value("5"),
+                )
+                .after_help(
+                    "EXIT CODES:\n    0  all peers answered\n    1  some peers are down\n    \
+                     2  failed",
+                ),
+        )
         .get_matches()
 }
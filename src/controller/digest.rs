@@ -0,0 +1,39 @@
+//! Digests given by clients, in routes and query strings.
+
+use errors;
+use types::Digest;
+
+/// Parses _digest_, which has to be `sha256:` or `sha512:` followed by
+/// the hash in lowercase hex.
+pub fn parse(digest: &str) -> Result<Digest, errors::Client> {
+    digest.parse().map_err(|e| {
+        debug!("{}", e);
+        errors::Client::DIGEST_INVALID
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use types::Algorithm;
+
+    use super::*;
+
+    #[test]
+    fn digest_parsing() {
+        let hex = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        let digest = parse(&format!("sha256:{}", hex)).unwrap();
+        assert_eq!(digest.algorithm(), Algorithm::Sha256);
+        assert_eq!(digest.hex(), hex);
+        assert_eq!(digest, Digest::of(Algorithm::Sha256, b""));
+        assert_eq!(digest.to_string(), format!("sha256:{}", hex));
+
+        let sha512 = Digest::of(Algorithm::Sha512, b"").to_string();
+        assert_eq!(parse(&sha512).unwrap().algorithm(), Algorithm::Sha512);
+
+        assert!(parse("latest").is_err());
+        assert!(parse("sha256:abc").is_err());
+        assert!(parse(&format!("md5:{}", hex)).is_err());
+        assert!(parse(&format!("sha256:{}", hex.to_uppercase())).is_err());
+        assert!(parse(&format!("sha512:{}", hex)).is_err());
+    }
+}
@@ -0,0 +1,9 @@
+/// Query parameters for changing a log level.
+///
+/// Without a _module_ the default level is changed.
+#[derive_FromForm]
+#[derive(Debug)]
+pub struct LogLevelParams {
+    pub module: Option<String>,
+    pub level: String,
+}
@@ -1 +1,7 @@
+pub mod digest;
+pub mod logging;
+pub mod referrers;
+pub mod repository;
+pub mod tag;
+pub mod token;
 pub mod uuid;
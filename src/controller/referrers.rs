@@ -0,0 +1,9 @@
+/// Query parameters of the referrers API.
+///
+/// With an _artifact_type_ only referrers of that type are listed.
+#[derive_FromForm]
+#[derive(Debug)]
+pub struct ReferrersParams {
+    #[form(field = "artifactType")]
+    pub artifact_type: Option<String>,
+}
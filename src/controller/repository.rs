@@ -0,0 +1,208 @@
+//! Repository paths of the registry API.
+//!
+//! Repository names have any number of path components, so the
+//! registry routes match `/v2/<path..>` and the types here tell them
+//! apart by how the path ends, e.g. `<name>/manifests/<reference>`.
+//! A path of another shape forwards to the next route. The name
+//! itself is only checked by the handler, so an invalid one gets
+//! `NAME_INVALID` rather than a 404.
+
+use regex::Regex;
+use rocket::http::RawStr;
+use rocket::http::uri::Segments;
+use rocket::request::FromSegments;
+
+use errors;
+
+/// Names are limited to 255 characters by the distribution spec
+const MAX_NAME_LENGTH: usize = 255;
+
+lazy_static! {
+    /// Path components of lowercase alphanumerics, separated by a
+    /// period, one or two underscores or any number of dashes
+    static ref NAME: Regex = Regex::new(
+        r"^[a-z0-9]+(?:(?:\.|_|__|-+)[a-z0-9]+)*(?:/[a-z0-9]+(?:(?:\.|_|__|-+)[a-z0-9]+)*)*$"
+    ).unwrap();
+}
+
+/// Checks _name_ is a valid repository name.
+pub fn validate(name: &str) -> Result<String, errors::Client> {
+    match name.len() <= MAX_NAME_LENGTH && NAME.is_match(name) {
+        true => Ok(name.to_owned()),
+        false => {
+            debug!("Invalid repository name {:?}", name);
+            Err(errors::Client::NAME_INVALID)
+        }
+    }
+}
+
+/// Splits _segments_ into the repository name and the segments
+/// captured by _pattern_, which the path has to end with. `None`
+/// captures a segment, `Some` matches it literally.
+fn split(segments: Segments, pattern: &[Option<&str>]) -> Result<(String, Vec<String>), ()> {
+    let segments = segments
+        .map(|segment| {
+            RawStr::from_str(segment)
+                .percent_decode()
+                .map(|segment| segment.into_owned())
+                .map_err(|_| ())
+        })
+        .collect::<Result<Vec<String>, ()>>()?;
+    if segments.len() <= pattern.len() {
+        return Err(());
+    }
+
+    let (name, tail) = segments.split_at(segments.len() - pattern.len());
+    let mut captures = Vec::new();
+    for (segment, expected) in tail.iter().zip(pattern) {
+        match *expected {
+            Some(literal) if literal != segment => return Err(()),
+            Some(_) => {}
+            None => captures.push(segment.clone()),
+        }
+    }
+    Ok((name.join("/"), captures))
+}
+
+/// `<name>/manifests/<reference>`
+#[derive(Debug)]
+pub struct ManifestPath {
+    pub name: String,
+    pub reference: String,
+}
+
+impl<'a> FromSegments<'a> for ManifestPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<ManifestPath, ()> {
+        let (name, mut captures) = split(segments, &[Some("manifests"), None])?;
+        Ok(ManifestPath {
+            name,
+            reference: captures.remove(0),
+        })
+    }
+}
+
+/// `<name>/blobs/<digest>`
+#[derive(Debug)]
+pub struct BlobPath {
+    pub name: String,
+    pub digest: String,
+}
+
+impl<'a> FromSegments<'a> for BlobPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<BlobPath, ()> {
+        let (name, mut captures) = split(segments, &[Some("blobs"), None])?;
+        Ok(BlobPath {
+            name,
+            digest: captures.remove(0),
+        })
+    }
+}
+
+/// `<name>/blobs/uploads`, where uploads are started
+#[derive(Debug)]
+pub struct UploadsPath {
+    pub name: String,
+}
+
+impl<'a> FromSegments<'a> for UploadsPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<UploadsPath, ()> {
+        let (name, _) = split(segments, &[Some("blobs"), Some("uploads")])?;
+        Ok(UploadsPath { name })
+    }
+}
+
+/// `<name>/blobs/uploads/<uuid>`
+#[derive(Debug)]
+pub struct UploadPath {
+    pub name: String,
+    pub uuid: String,
+}
+
+impl<'a> FromSegments<'a> for UploadPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<UploadPath, ()> {
+        let (name, mut captures) = split(segments, &[Some("blobs"), Some("uploads"), None])?;
+        Ok(UploadPath {
+            name,
+            uuid: captures.remove(0),
+        })
+    }
+}
+
+/// `<name>/tags/list`
+#[derive(Debug)]
+pub struct TagsPath {
+    pub name: String,
+}
+
+impl<'a> FromSegments<'a> for TagsPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<TagsPath, ()> {
+        let (name, _) = split(segments, &[Some("tags"), Some("list")])?;
+        Ok(TagsPath { name })
+    }
+}
+
+/// `<name>/referrers/<digest>`
+#[derive(Debug)]
+pub struct ReferrersPath {
+    pub name: String,
+    pub digest: String,
+}
+
+impl<'a> FromSegments<'a> for ReferrersPath {
+    type Error = ();
+
+    fn from_segments(segments: Segments<'a>) -> Result<ReferrersPath, ()> {
+        let (name, mut captures) = split(segments, &[Some("referrers"), None])?;
+        Ok(ReferrersPath {
+            name,
+            digest: captures.remove(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::uri::URI;
+    use rocket::request::FromSegments;
+
+    use super::*;
+
+    fn manifest(path: &str) -> Result<ManifestPath, ()> {
+        ManifestPath::from_segments(URI::new(path).segments())
+    }
+
+    #[test]
+    fn names_of_any_depth() {
+        let path = manifest("/alpine/manifests/latest").unwrap();
+        assert_eq!(path.name, "alpine");
+        assert_eq!(path.reference, "latest");
+
+        let path = manifest("/library/ubuntu/sub/manifests/sha256%3Aabc").unwrap();
+        assert_eq!(path.name, "library/ubuntu/sub");
+        assert_eq!(path.reference, "sha256:abc");
+
+        assert!(manifest("/manifests/latest").is_err());
+        assert!(manifest("/alpine/blobs/sha256:abc").is_err());
+        assert!(UploadPath::from_segments(URI::new("/a/b/blobs/uploads/u").segments()).is_ok());
+    }
+
+    #[test]
+    fn name_validation() {
+        assert!(validate("library/ubuntu").is_ok());
+        assert!(validate("a/b/c.d/e__f/g--h").is_ok());
+        assert!(validate("Upper").is_err());
+        assert!(validate("trailing-").is_err());
+        assert!(validate("double//slash").is_err());
+        assert!(validate(&"a".repeat(256)).is_err());
+    }
+}
@@ -0,0 +1,46 @@
+//! Tags given by clients, in routes and OCI layouts.
+
+use regex::Regex;
+
+use errors;
+
+lazy_static! {
+    static ref TAG: Regex = Regex::new(r"^[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}$").unwrap();
+}
+
+/// Whether _tag_ is a valid tag, which also keeps it a single path
+/// segment when it's stored.
+pub fn is_valid(tag: &str) -> bool {
+    TAG.is_match(tag)
+}
+
+/// Checks _tag_, see `is_valid`.
+pub fn parse(tag: &str) -> Result<&str, errors::Client> {
+    match is_valid(tag) {
+        true => Ok(tag),
+        false => {
+            debug!("Invalid tag {:?}", tag);
+            Err(errors::Client::TAG_INVALID)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tag_parsing() {
+        assert!(parse("latest").is_ok());
+        assert!(parse("v1.2.3-rc_1").is_ok());
+        assert!(parse("_private").is_ok());
+
+        assert!(parse("").is_err());
+        assert!(parse("..").is_err());
+        assert!(parse(".hidden").is_err());
+        assert!(parse("-flag").is_err());
+        assert!(parse("../../../etc/passwd").is_err());
+        assert!(parse("a/b").is_err());
+        assert!(parse(&"a".repeat(129)).is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use rocket::request::{FormItems, FromForm};
+
+use auth::token::Scope;
+
+/// Query parameters sent by clients requesting a token.
+///
+/// `scope` may be given several times, so this can't be derived.
+#[derive(Debug, Default)]
+pub struct TokenParams {
+    pub service: Option<String>,
+    pub account: Option<String>,
+    pub scopes: Vec<Scope>,
+}
+
+impl<'f> FromForm<'f> for TokenParams {
+    type Error = ();
+
+    fn from_form(items: &mut FormItems<'f>, _strict: bool) -> Result<TokenParams, ()> {
+        let mut params = TokenParams::default();
+
+        for (key, value) in items {
+            let value = value.url_decode().map_err(|_| ())?;
+            match key.as_str() {
+                "service" => params.service = Some(value),
+                "account" => params.account = Some(value),
+                "scope" => {
+                    let scope = value.parse().map_err(|_| ())?;
+                    params.scopes.push(scope);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(params)
+    }
+}
@@ -1,3 +1,7 @@
+use uuid::Uuid;
+
+use errors;
+
 // TODO: move me somewhere else
 #[derive_FromForm]
 #[derive(Debug)]
@@ -5,3 +9,38 @@ pub struct DigestStruct {
     pub query: bool,
     pub digest: String,
 }
+
+/// Query of a cross repository blob mount, _mount_ is the digest of
+/// the blob in the repository _from_.
+#[derive_FromForm]
+#[derive(Debug)]
+pub struct MountParams {
+    pub mount: String,
+    pub from: String,
+}
+
+/// Checks the upload _uuid_ of a route, which is then safe to use as a
+/// file name. Anything else can't be an upload.
+pub fn parse(uuid: &str) -> Result<String, errors::Client> {
+    match Uuid::parse_str(uuid) {
+        Ok(_) => Ok(uuid.to_owned()),
+        Err(e) => {
+            debug!("Invalid upload {:?}: {}", uuid, e);
+            Err(errors::Client::BLOB_UPLOAD_UNKNOWN)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn upload_uuids() {
+        let uuid = Uuid::new_v4().to_string();
+        assert_eq!(parse(&uuid).unwrap(), uuid);
+        assert!(parse("u").is_err());
+        assert!(parse("../../some/file").is_err());
+        assert!(parse(&format!("../{}", uuid)).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+//! The data directory of the registry.
+//!
+//! Every command takes the directory with `--data-dir`, `data` by
+//! default. `serve` passes it on to everything keeping files, the
+//! commands working offline, `fsck`, `gc`, `import` and `export`,
+//! expect the registry using it to be stopped.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use failure::{err_msg, Error};
+
+use manifest::{self, Parsed};
+use types::Digest;
+
+#[derive(Clone, Debug)]
+pub struct DataDir {
+    root: PathBuf,
+}
+
+impl DataDir {
+    /// Opens the data directory at _root_, which must have a layer
+    /// store.
+    pub fn open(root: &Path) -> Result<DataDir, Error> {
+        if !root.join("layers").is_dir() {
+            return Err(err_msg(format!("{} is not a data directory", root.display())));
+        }
+        Ok(DataDir { root: root.to_owned() })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn layers(&self) -> PathBuf {
+        self.root.join("layers")
+    }
+
+    pub fn scratch(&self) -> PathBuf {
+        self.root.join("scratch")
+    }
+
+    pub fn quarantine(&self) -> PathBuf {
+        self.root.join("quarantine")
+    }
+
+    pub fn blob(&self, digest: &Digest) -> PathBuf {
+        self.layers().join(digest.to_string())
+    }
+
+    /// The scratch file the upload _uuid_ is streamed to.
+    pub fn upload(&self, uuid: &str) -> PathBuf {
+        self.scratch().join(uuid)
+    }
+
+    /// Where undelivered notifications are queued unless configured.
+    pub fn notifications(&self) -> PathBuf {
+        self.root.join("notifications")
+    }
+
+    /// The link making the stored blob _digest_ part of _repository_.
+    /// Blobs are kept once in `layers`, a repository only has the ones
+    /// linked into it.
+    pub fn link(&self, repository: &str, digest: &Digest) -> PathBuf {
+        self.root
+            .join("repositories")
+            .join(repository)
+            .join("_layers")
+            .join(digest.to_string())
+    }
+
+    /// Links the stored blob _digest_ into _repository_.
+    pub fn link_blob(&self, repository: &str, digest: &Digest) -> Result<(), Error> {
+        write(&self.link(repository, digest), b"")
+    }
+
+    /// Removes the link of _digest_ from _repository_, returns whether
+    /// there was one. The blob itself stays until `lycaon gc`.
+    pub fn unlink_blob(&self, repository: &str, digest: &Digest) -> Result<bool, Error> {
+        match fs::remove_file(self.link(repository, digest)) {
+            Ok(()) => Ok(true),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether the blob _digest_ is stored and linked into _repository_.
+    pub fn has_blob(&self, repository: &str, digest: &Digest) -> bool {
+        self.link(repository, digest).is_file() && self.blob(digest).is_file()
+    }
+
+    /// The `_manifests` directory of _repository_.
+    pub fn manifests(&self, repository: &str) -> PathBuf {
+        self.root
+            .join("repositories")
+            .join(repository)
+            .join("_manifests")
+    }
+
+    /// Every repository, sorted.
+    pub fn repositories(&self) -> Vec<String> {
+        fn walk(dir: &Path, prefix: &str, found: &mut Vec<String>) {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(_) => return,
+            };
+            for path in entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()) {
+                let name = file_name(&path).to_owned();
+                if name == "_manifests" {
+                    found.push(prefix.to_owned());
+                } else if path.is_dir() && !name.starts_with('_') {
+                    let prefix = match prefix.is_empty() {
+                        true => name,
+                        false => format!("{}/{}", prefix, name),
+                    };
+                    walk(&path, &prefix, found);
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        walk(&self.root.join("repositories"), "", &mut found);
+        found.sort();
+        found
+    }
+
+    /// The content and media type of a stored manifest, if it's there.
+    pub fn manifest(&self, repository: &str, digest: &Digest) -> Option<(Vec<u8>, Option<String>)> {
+        let path = self.manifests(repository)
+            .join("revisions")
+            .join(digest.to_string());
+        let content = read(&path).ok()?;
+        let media_type = read(&path.with_extension("type"))
+            .ok()
+            .and_then(|media_type| String::from_utf8(media_type).ok())
+            .and_then(|media_type| match media_type.is_empty() {
+                true => None,
+                false => Some(media_type),
+            });
+        Some((content, media_type))
+    }
+
+    /// Parses a stored manifest, failing if it's missing or invalid.
+    pub fn parse_manifest(&self, repository: &str, digest: &Digest) -> Result<Parsed, Error> {
+        let (content, media_type) = self.manifest(repository, digest)
+            .ok_or_else(|| err_msg(format!("manifest {}@{} is missing", repository, digest)))?;
+        manifest::parse(media_type.as_ref().map(|t| t.as_str()), &content)
+            .map_err(|e| err_msg(format!("manifest {}@{}: {}", repository, digest, e)))
+    }
+
+    /// The tags of _repository_ with the digests they point at, sorted.
+    /// Tags not holding a valid digest are left out.
+    pub fn tags(&self, repository: &str) -> Result<Vec<(String, Digest)>, Error> {
+        let mut tags = Vec::new();
+        for path in files(&self.manifests(repository).join("tags"))? {
+            let name = file_name(&path).to_owned();
+            if name.starts_with('.') {
+                continue;
+            }
+            let digest = String::from_utf8(read(&path)?).unwrap_or_default();
+            if let Ok(digest) = digest.trim().parse() {
+                tags.push((name, digest));
+            }
+        }
+        Ok(tags)
+    }
+}
+
+/// Writes _path_ through a temporary file, like the server does.
+pub fn write(path: &Path, contents: &[u8]) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_file_name(format!(".{}.tmp", file_name(path)));
+    File::create(&tmp)?.write_all(contents)?;
+    fs::rename(tmp, path).map_err(|e| e.into())
+}
+
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    File::open(path)?.read_to_end(&mut contents)?;
+    Ok(contents)
+}
+
+/// The digest of the file at _path_, computed with the algorithm of
+/// _digest_.
+pub fn hash(path: &Path, digest: &Digest) -> io::Result<Digest> {
+    let mut hasher = digest.hasher();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finish())
+}
+
+pub fn file_name(path: &Path) -> &str {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+}
+
+/// The files directly in _dir_, sorted. A missing directory is empty.
+pub fn files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,354 @@
+//! Offline consistency check of a data directory.
+//!
+//! Meant for a stopped registry, `lycaon fsck` checks the layout set up
+//! by `create_data_dirs`:
+//!
+//! - every blob in `layers` matches the digest it's named by,
+//! - no files are left in `scratch`; upload sessions only live in a
+//!   running registry, so anything there belongs to an abandoned upload,
+//! - every manifest matches its digest, parses, and only references
+//!   blobs and manifests which exist, the blobs linked into its
+//!   repository,
+//! - every tag points at an existing manifest.
+//!
+//! With `--repair` orphans are deleted, corrupt content is moved to
+//! `quarantine` and missing links are added, e.g. to data directories
+//! written before repositories had them. Manifests referencing missing content are only
+//! reported, the content can't be recreated.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use failure::Error;
+
+use datadir::{self, files, file_name, hash, DataDir};
+use manifest;
+use types::Digest;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    /// A blob whose content doesn't match its digest
+    CorruptBlob,
+    /// A file in the layer store not named by a digest
+    StrayFile,
+    /// Scratch data of an upload that was never finished
+    OrphanedUpload,
+    /// A manifest whose content doesn't match its digest
+    CorruptManifest,
+    /// A manifest that can't be parsed
+    InvalidManifest,
+    /// A manifest referencing a blob that isn't stored
+    MissingBlob,
+    /// A manifest referencing a stored blob not linked into its repository
+    UnlinkedBlob,
+    /// An index referencing a manifest that isn't stored
+    MissingManifest,
+    /// A tag pointing at a manifest that isn't stored
+    DanglingTag,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Kind::CorruptBlob => "corrupt blob",
+            Kind::StrayFile => "stray file",
+            Kind::OrphanedUpload => "orphaned upload",
+            Kind::CorruptManifest => "corrupt manifest",
+            Kind::InvalidManifest => "invalid manifest",
+            Kind::MissingBlob => "missing blob",
+            Kind::UnlinkedBlob => "unlinked blob",
+            Kind::MissingManifest => "missing manifest",
+            Kind::DanglingTag => "dangling tag",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub kind: Kind,
+    pub path: PathBuf,
+    pub detail: String,
+    pub repaired: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub blobs: u64,
+    pub manifests: u64,
+    pub tags: u64,
+    pub problems: Vec<Problem>,
+}
+
+impl Report {
+    /// Whether anything is left to fix.
+    pub fn clean(&self) -> bool {
+        self.problems.iter().all(|problem| problem.repaired)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for problem in &self.problems {
+            write!(f, "{}: {}", problem.kind, problem.path.display())?;
+            if !problem.detail.is_empty() {
+                write!(f, " ({})", problem.detail)?;
+            }
+            if problem.repaired {
+                write!(f, " [repaired]")?;
+            }
+            writeln!(f)?;
+        }
+        let repaired = self.problems.iter().filter(|problem| problem.repaired).count();
+        writeln!(
+            f,
+            "checked {} blobs, {} manifests and {} tags: {} problems, {} repaired",
+            self.blobs,
+            self.manifests,
+            self.tags,
+            self.problems.len(),
+            repaired
+        )
+    }
+}
+
+/// Checks the data directory at _root_, fixing what it can if _repair_.
+pub fn check(root: &Path, repair: bool) -> Result<Report, Error> {
+    let mut fsck = Fsck {
+        data: DataDir::open(root)?,
+        repair,
+        report: Report::default(),
+    };
+    fsck.blobs()?;
+    fsck.scratch()?;
+    for repository in fsck.data.repositories() {
+        fsck.repository(&repository)?;
+    }
+    Ok(fsck.report)
+}
+
+struct Fsck {
+    data: DataDir,
+    repair: bool,
+    report: Report,
+}
+
+impl Fsck {
+    fn problem(&mut self, kind: Kind, path: &Path, detail: String, repaired: bool) {
+        self.report.problems.push(Problem {
+            kind,
+            path: path.to_owned(),
+            detail,
+            repaired,
+        });
+    }
+
+    /// Moves _path_ into the quarantine directory, if repairing.
+    fn quarantine(&self, path: &Path) -> Result<bool, Error> {
+        if !self.repair {
+            return Ok(false);
+        }
+        let quarantine = self.data.quarantine();
+        fs::create_dir_all(&quarantine)?;
+        let name = file_name(path);
+        // timestamped like the scrubber's, the same content may rot twice
+        fs::rename(path, quarantine.join(format!("{}.{}", name, Utc::now().timestamp())))?;
+        Ok(true)
+    }
+
+    /// Deletes _path_, if repairing.
+    fn delete(&self, path: &Path) -> Result<bool, Error> {
+        if !self.repair {
+            return Ok(false);
+        }
+        match path.is_dir() {
+            true => fs::remove_dir_all(path)?,
+            false => fs::remove_file(path)?,
+        }
+        Ok(true)
+    }
+
+    fn blobs(&mut self) -> Result<(), Error> {
+        for path in files(&self.data.layers())? {
+            let digest = match file_name(&path).parse::<Digest>() {
+                Ok(digest) => digest,
+                Err(e) => {
+                    let repaired = self.delete(&path)?;
+                    self.problem(Kind::StrayFile, &path, e.to_string(), repaired);
+                    continue;
+                }
+            };
+            self.report.blobs += 1;
+            let actual = hash(&path, &digest)?;
+            if actual != digest {
+                let repaired = self.quarantine(&path)?;
+                self.problem(Kind::CorruptBlob, &path, format!("hashes to {}", actual), repaired);
+            }
+        }
+        Ok(())
+    }
+
+    fn scratch(&mut self) -> Result<(), Error> {
+        let scratch = self.data.scratch();
+        let entries = match fs::read_dir(&scratch) {
+            Ok(entries) => entries,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let repaired = self.delete(&path)?;
+            self.problem(Kind::OrphanedUpload, &path, String::new(), repaired);
+        }
+        Ok(())
+    }
+
+    fn repository(&mut self, repository: &str) -> Result<(), Error> {
+        let manifests = self.data.manifests(repository);
+        let revisions = manifests.join("revisions");
+
+        for path in files(&revisions)? {
+            let name = file_name(&path);
+            if name.ends_with(".type") {
+                continue;
+            }
+            let digest = match name.parse::<Digest>() {
+                Ok(digest) => digest,
+                Err(e) => {
+                    // e.g. a temporary file left by a crash mid-write
+                    let repaired = self.delete(&path)?;
+                    self.problem(Kind::StrayFile, &path, e.to_string(), repaired);
+                    continue;
+                }
+            };
+            self.report.manifests += 1;
+
+            let (content, media_type) = match self.data.manifest(repository, &digest) {
+                Some(manifest) => manifest,
+                None => continue,
+            };
+            let actual = Digest::of(digest.algorithm(), &content);
+            if actual != digest {
+                let repaired = self.quarantine(&path)?;
+                self.problem(Kind::CorruptManifest, &path, format!("hashes to {}", actual), repaired);
+                continue;
+            }
+
+            let parsed = match manifest::parse(media_type.as_ref().map(|t| t.as_str()), &content) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    let repaired = self.quarantine(&path)?;
+                    self.problem(Kind::InvalidManifest, &path, e.to_string(), repaired);
+                    continue;
+                }
+            };
+            for blob in &parsed.blobs {
+                if !self.data.blob(blob).is_file() {
+                    self.problem(Kind::MissingBlob, &path, blob.to_string(), false);
+                } else if !self.data.has_blob(repository, blob) {
+                    if self.repair {
+                        self.data.link_blob(repository, blob)?;
+                    }
+                    let repaired = self.repair;
+                    self.problem(Kind::UnlinkedBlob, &path, blob.to_string(), repaired);
+                }
+            }
+            for child in &parsed.manifests {
+                if !revisions.join(child.to_string()).is_file() {
+                    self.problem(Kind::MissingManifest, &path, child.to_string(), false);
+                }
+            }
+        }
+
+        for path in files(&manifests.join("tags"))? {
+            if file_name(&path).starts_with('.') {
+                let repaired = self.delete(&path)?;
+                self.problem(Kind::StrayFile, &path, String::new(), repaired);
+                continue;
+            }
+            self.report.tags += 1;
+            let digest = String::from_utf8(datadir::read(&path)?).unwrap_or_default();
+            let digest = digest.trim();
+            let exists = digest.parse::<Digest>().is_ok() && revisions.join(digest).is_file();
+            if !exists {
+                let repaired = self.delete(&path)?;
+                self.problem(Kind::DanglingTag, &path, digest.to_owned(), repaired);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs::File;
+    use std::io::Write;
+
+    use uuid::Uuid;
+
+    use types::Algorithm;
+
+    use super::*;
+
+    fn write(path: &Path, content: &[u8]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(content).unwrap();
+    }
+
+    #[test]
+    fn finds_and_repairs_problems() {
+        let root = env::temp_dir().join(format!("lycaon-fsck-{}", Uuid::new_v4()));
+        let layer = Digest::of(Algorithm::Sha256, b"layer");
+        let config = Digest::of(Algorithm::Sha256, b"config");
+        write(&root.join("layers").join(layer.to_string()), b"layer");
+        write(&root.join("layers").join(config.to_string()), b"bit rot");
+        write(&root.join("scratch").join("abandoned"), b"half an upload");
+
+        let image = format!(
+            r#"{{"schemaVersion": 2, "config": {{"digest": "{}"}}, "layers": [{{"digest": "{}"}}, {{"digest": "{}"}}]}}"#,
+            config,
+            layer,
+            Digest::of(Algorithm::Sha256, b"missing")
+        );
+        let digest = Digest::of(Algorithm::Sha256, image.as_bytes());
+        let manifests = root.join("repositories/library/alpine/_manifests");
+        write(&manifests.join("revisions").join(digest.to_string()), image.as_bytes());
+        write(&manifests.join("tags/latest"), digest.to_string().as_bytes());
+        write(&manifests.join("tags/gone"), layer.to_string().as_bytes());
+
+        let report = check(&root, false).unwrap();
+        let kinds: Vec<Kind> = report.problems.iter().map(|problem| problem.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::CorruptBlob,
+                Kind::OrphanedUpload,
+                Kind::UnlinkedBlob,
+                Kind::UnlinkedBlob,
+                Kind::MissingBlob,
+                Kind::DanglingTag,
+            ]
+        );
+        assert_eq!((report.blobs, report.manifests, report.tags), (2, 1, 2));
+        assert!(!report.clean());
+
+        let report = check(&root, true).unwrap();
+        assert!(report.problems.iter().filter(|p| p.kind != Kind::MissingBlob).all(|p| p.repaired));
+        assert!(!root.join("scratch/abandoned").exists());
+        assert!(!manifests.join("tags/gone").exists());
+        assert!(manifests.join("tags/latest").exists());
+        assert_eq!(fs::read_dir(root.join("quarantine")).unwrap().count(), 1);
+        assert!(root.join("repositories/library/alpine/_layers").join(layer.to_string()).is_file());
+
+        // the quarantined config now counts as missing too
+        let report = check(&root, false).unwrap();
+        assert!(report.problems.iter().all(|p| p.kind == Kind::MissingBlob));
+        assert_eq!(report.problems.len(), 2);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,232 @@
+//! Offline garbage collection of a data directory.
+//!
+//! Blobs no stored manifest refers to are deleted from the layer
+//! store, along with their links into repositories. With `--delete-untagged` manifests go first: only tagged
+//! manifests, the manifests of their indexes and anything referring
+//! to them through `subject` are kept, and links to the removed ones
+//! are dropped from `referrers.json`.
+//!
+//! Blobs are only removed once every manifest could be read, a
+//! manifest `gc` can't parse may refer to anything. `fsck` reports
+//! which ones are at fault.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use failure::Error;
+use serde_json::{self, Value};
+
+use datadir::{self, files, file_name, DataDir};
+use manifest::Parsed;
+use types::Digest;
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub dry_run: bool,
+    /// Removed manifests as `<repository>@<digest>`
+    pub manifests: Vec<String>,
+    pub blobs: Vec<Digest>,
+    pub bytes: u64,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let verb = match self.dry_run {
+            true => "would remove",
+            false => "removed",
+        };
+        for manifest in &self.manifests {
+            writeln!(f, "{} manifest {}", verb, manifest)?;
+        }
+        for blob in &self.blobs {
+            writeln!(f, "{} blob {}", verb, blob)?;
+        }
+        writeln!(
+            f,
+            "{} {} manifests and {} blobs, {} bytes",
+            verb,
+            self.manifests.len(),
+            self.blobs.len(),
+            self.bytes
+        )
+    }
+}
+
+/// Collects garbage in the data directory at _root_, only reporting
+/// what would go if _dry_run_.
+pub fn collect(root: &Path, delete_untagged: bool, dry_run: bool) -> Result<Report, Error> {
+    let data = DataDir::open(root)?;
+    let mut report = Report {
+        dry_run,
+        ..Report::default()
+    };
+
+    let mut referenced = BTreeSet::new();
+    let mut removed = BTreeSet::new();
+    for repository in data.repositories() {
+        let manifests = revisions(&data, &repository)?;
+        let kept = match delete_untagged {
+            true => reachable(&data, &repository, &manifests)?,
+            false => manifests.keys().cloned().collect(),
+        };
+        for (digest, parsed) in &manifests {
+            if kept.contains(digest) {
+                referenced.extend(parsed.blobs.iter().cloned());
+                continue;
+            }
+            if !dry_run {
+                let path = data.manifests(&repository)
+                    .join("revisions")
+                    .join(digest.to_string());
+                fs::remove_file(&path)?;
+                let _ = fs::remove_file(path.with_extension("type"));
+            }
+            report.manifests.push(format!("{}@{}", repository, digest));
+            removed.insert((repository.clone(), digest.to_string()));
+        }
+    }
+    if !dry_run && !removed.is_empty() {
+        forget_referrers(&data, &removed)?;
+    }
+
+    for path in files(&data.layers())? {
+        // anything not named by a digest is left for fsck
+        let digest = match file_name(&path).parse::<Digest>() {
+            Ok(digest) => digest,
+            Err(_) => continue,
+        };
+        if referenced.contains(&digest) {
+            continue;
+        }
+        report.bytes += fs::metadata(&path)?.len();
+        if !dry_run {
+            fs::remove_file(&path)?;
+        }
+        report.blobs.push(digest);
+    }
+    if !dry_run {
+        for repository in data.repositories() {
+            for digest in &report.blobs {
+                let _ = fs::remove_file(data.link(&repository, digest));
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Every manifest of _repository_, parsed.
+fn revisions(data: &DataDir, repository: &str) -> Result<BTreeMap<Digest, Parsed>, Error> {
+    let mut manifests = BTreeMap::new();
+    for path in files(&data.manifests(repository).join("revisions"))? {
+        // skips media types and temporary files
+        if let Ok(digest) = file_name(&path).parse::<Digest>() {
+            let parsed = data.parse_manifest(repository, &digest)?;
+            manifests.insert(digest, parsed);
+        }
+    }
+    Ok(manifests)
+}
+
+/// The manifests of _repository_ reachable from its tags.
+fn reachable(
+    data: &DataDir,
+    repository: &str,
+    manifests: &BTreeMap<Digest, Parsed>,
+) -> Result<BTreeSet<Digest>, Error> {
+    let mut kept: BTreeSet<Digest> = data.tags(repository)?
+        .into_iter()
+        .map(|(_, digest)| digest)
+        .filter(|digest| manifests.contains_key(digest))
+        .collect();
+    loop {
+        let mut found = Vec::new();
+        for (digest, parsed) in manifests {
+            if kept.contains(digest) {
+                let children = parsed.manifests.iter();
+                found.extend(children.filter(|child| manifests.contains_key(child)).cloned());
+            } else if parsed.subject.as_ref().map_or(false, |subject| kept.contains(subject)) {
+                found.push(digest.clone());
+            }
+        }
+        let before = kept.len();
+        kept.extend(found);
+        if kept.len() == before {
+            return Ok(kept);
+        }
+    }
+}
+
+/// Drops the links from and to _removed_ manifests in `referrers.json`.
+fn forget_referrers(data: &DataDir, removed: &BTreeSet<(String, String)>) -> Result<(), Error> {
+    let path = data.root().join("referrers.json");
+    let mut links: Vec<Value> = match datadir::read(&path) {
+        Ok(contents) => serde_json::from_slice(&contents)?,
+        Err(_) => return Ok(()),
+    };
+    let gone = |link: &Value, field: &str| {
+        let repository = link["repository"].as_str().unwrap_or_default().to_owned();
+        let digest = link[field].as_str().unwrap_or_default().to_owned();
+        removed.contains(&(repository, digest))
+    };
+    links.retain(|link| !gone(link, "digest") && !gone(link, "subject"));
+    datadir::write(&path, &serde_json::to_vec(&links)?)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use uuid::Uuid;
+
+    use types::Algorithm;
+
+    use super::*;
+
+    #[test]
+    fn keeps_what_tags_reach() {
+        let root = env::temp_dir().join(format!("lycaon-gc-{}", Uuid::new_v4()));
+        let data = |content: &[u8]| {
+            let digest = Digest::of(Algorithm::Sha256, content);
+            datadir::write(&root.join("layers").join(digest.to_string()), content).unwrap();
+            digest
+        };
+        let used = data(b"used");
+        let untagged = data(b"untagged");
+        let unused = data(b"unused");
+        let link = root.join("repositories/library/alpine/_layers").join(unused.to_string());
+        datadir::write(&link, b"").unwrap();
+
+        let manifests = root.join("repositories/library/alpine/_manifests");
+        let image = |blob: &Digest| {
+            let content = format!(
+                r#"{{"schemaVersion": 2, "config": {{"digest": "{}"}}, "layers": []}}"#,
+                blob
+            );
+            let digest = Digest::of(Algorithm::Sha256, content.as_bytes());
+            let path = manifests.join("revisions").join(digest.to_string());
+            datadir::write(&path, content.as_bytes()).unwrap();
+            digest
+        };
+        let tagged = image(&used);
+        image(&untagged);
+        datadir::write(&manifests.join("tags/latest"), tagged.to_string().as_bytes()).unwrap();
+
+        let report = collect(&root, false, true).unwrap();
+        assert_eq!(report.blobs, vec![unused.clone()]);
+        assert!(root.join("layers").join(unused.to_string()).exists());
+        assert!(link.exists());
+
+        let report = collect(&root, true, false).unwrap();
+        assert_eq!(report.manifests.len(), 1);
+        let mut blobs = vec![untagged, unused];
+        blobs.sort();
+        assert_eq!(report.blobs, blobs);
+        assert_eq!(files(&root.join("layers")).unwrap().len(), 1);
+        assert!(root.join("layers").join(used.to_string()).exists());
+        assert!(!link.exists());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
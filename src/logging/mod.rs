@@ -0,0 +1,369 @@
+//! Logging setup.
+//!
+//! Log records are written as text or JSON lines to stdout or to a
+//! rotating file, as configured in the `[logging]` section. Requests
+//! are additionally recorded by the `AccessLog` fairing under the
+//! `lycaon::access` target, optionally to a file of their own.
+//!
+//! Levels are looked up at runtime for every record, so they can be
+//! changed per module through `set_level` without a restart. Access
+//! lines are logged whatever the default level, unless `lycaon::access`
+//! itself is given one.
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{self, Write};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+use std::time::Instant;
+
+use chrono::Utc;
+use failure::Error;
+use fern;
+use log::{LogLevel, LogLevelFilter, LogMetadata, LogRecord};
+use rocket::{Data, Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::response::Body;
+use serde_json;
+
+use backend;
+use config::{LogFormat, LoggingConfig};
+use errors;
+
+pub mod rotate;
+
+use self::rotate::RotatingFile;
+
+pub const ACCESS_TARGET: &'static str = "lycaon::access";
+
+lazy_static! {
+    static ref LEVELS: RwLock<Levels> = RwLock::new(Levels::default());
+}
+
+static JSON: AtomicBool = ATOMIC_BOOL_INIT;
+
+/// The default level and the per-module overrides.
+#[derive(Debug)]
+struct Levels {
+    default: LogLevelFilter,
+    modules: BTreeMap<String, LogLevelFilter>,
+}
+
+impl Default for Levels {
+    fn default() -> Levels {
+        Levels {
+            default: LogLevelFilter::Error,
+            modules: BTreeMap::new(),
+        }
+    }
+}
+
+impl Levels {
+    /// The level of the most specific module _target_ belongs to.
+    fn level_for(&self, target: &str) -> LogLevelFilter {
+        self.modules
+            .iter()
+            .filter(|&(module, _)| {
+                target == module || target.starts_with(&format!("{}::", module))
+            })
+            .max_by_key(|&(module, _)| module.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// Whether records of _level_ from _target_ are logged.
+    fn allows(&self, level: LogLevel, target: &str) -> bool {
+        let filter = match target {
+            ACCESS_TARGET => self.modules
+                .get(ACCESS_TARGET)
+                .cloned()
+                .unwrap_or(LogLevelFilter::Info),
+            target => self.level_for(target),
+        };
+        level <= filter
+    }
+
+    /// Applies `RUST_LOG` style directives, e.g. `info,lycaon::auth=debug`.
+    fn parse(&mut self, directives: &str) {
+        for directive in directives.split(',').map(|d| d.trim()) {
+            let mut parts = directive.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(level), None) => match level.parse() {
+                    Ok(level) => self.default = level,
+                    Err(_) => {
+                        self.modules.insert(level.to_owned(), LogLevelFilter::Trace);
+                    }
+                },
+                (Some(module), Some(level)) => {
+                    if let Ok(level) = level.parse() {
+                        self.modules.insert(module.to_owned(), level);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn enabled(metadata: &LogMetadata) -> bool {
+    LEVELS.read().unwrap().allows(metadata.level(), metadata.target())
+}
+
+/// Changes the level of _module_, or the default level if `None`.
+pub fn set_level(module: Option<&str>, level: &str) -> Result<(), Error> {
+    let level: LogLevelFilter = level
+        .parse()
+        .map_err(|_| errors::Server::Invalid("log level"))?;
+    let mut levels = LEVELS.write().unwrap();
+    match module {
+        Some(module) => {
+            levels.modules.insert(module.to_owned(), level);
+        }
+        None => levels.default = level,
+    }
+    info!("Log level of {} set to {}", module.unwrap_or("default"), level);
+    Ok(())
+}
+
+/// The default level and every module override.
+pub fn levels() -> (String, BTreeMap<String, String>) {
+    let levels = LEVELS.read().unwrap();
+    let modules = levels
+        .modules
+        .iter()
+        .map(|(module, level)| (module.clone(), level.to_string()))
+        .collect();
+    (levels.default.to_string(), modules)
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    timestamp: String,
+    level: String,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
+    message: String,
+}
+
+fn format_record(record: &LogRecord) -> String {
+    let request_id = backend::trace::current_request_id();
+    if JSON.load(Ordering::Relaxed) {
+        let json = JsonRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target(),
+            request_id,
+            message: record.args().to_string(),
+        };
+        return serde_json::to_string(&json).unwrap_or_default();
+    }
+
+    match request_id {
+        Some(request_id) => format!(
+            "{} {}[{}] [{}] {}",
+            Utc::now().to_rfc3339(),
+            record.target(),
+            record.level(),
+            request_id,
+            record.args()
+        ),
+        None => format!(
+            "{} {}[{}] {}",
+            Utc::now().to_rfc3339(),
+            record.target(),
+            record.level(),
+            record.args()
+        ),
+    }
+}
+
+fn output(destination: &str, config: &LoggingConfig) -> Result<fern::Output, Error> {
+    Ok(match destination {
+        "stdout" => io::stdout().into(),
+        "stderr" => io::stderr().into(),
+        path => {
+            let file = RotatingFile::open(path, config.max_size(), config.max_files())?;
+            let file: Box<Write + Send> = Box::new(file);
+            file.into()
+        }
+    })
+}
+
+/// Installs the global logger.
+///
+/// `RUST_LOG` is still honoured and overrides the configured levels.
+pub fn init(config: LoggingConfig) -> Result<(), Error> {
+    {
+        let mut levels = LEVELS.write().unwrap();
+        levels.default = config
+            .level()
+            .parse()
+            .map_err(|_| errors::Server::Invalid("log level"))?;
+        for (module, level) in config.modules() {
+            let level = level
+                .parse()
+                .map_err(|_| errors::Server::Invalid("log level"))?;
+            levels.modules.insert(module, level);
+        }
+        if let Ok(directives) = env::var("RUST_LOG") {
+            levels.parse(&directives);
+        }
+    }
+    JSON.store(config.format() == LogFormat::Json, Ordering::Relaxed);
+
+    let separate_access = config.access_log().is_some();
+    let main = fern::Dispatch::new()
+        .filter(move |metadata| !separate_access || metadata.target() != ACCESS_TARGET)
+        .format(|out, _, record| out.finish(format_args!("{}", format_record(record))))
+        .chain(output(&config.output(), &config)?);
+
+    let mut dispatch = fern::Dispatch::new()
+        .level(LogLevelFilter::Trace)
+        .filter(enabled)
+        .chain(main);
+    if let Some(access_log) = config.access_log() {
+        let access = fern::Dispatch::new()
+            .filter(|metadata| metadata.target() == ACCESS_TARGET)
+            .format(|out, message, _| out.finish(format_args!("{}", message)))
+            .chain(output(&access_log, &config)?);
+        dispatch = dispatch.chain(access);
+    }
+
+    dispatch.apply().map_err(|e| e.into())
+}
+
+thread_local! {
+    static REQUEST_START: Cell<Option<Instant>> = Cell::new(None);
+}
+
+#[derive(Serialize)]
+struct AccessRecord<'a> {
+    timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    request_id: Option<&'a str>,
+    remote: String,
+    method: &'a str,
+    path: &'a str,
+    status: u16,
+    bytes: Option<u64>,
+    duration_ms: f64,
+}
+
+/// Fairing logging one line per request to the access log.
+pub struct AccessLog;
+
+impl Fairing for AccessLog {
+    fn info(&self) -> Info {
+        Info {
+            name: "Access log",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, _: &mut Request, _: &Data) {
+        REQUEST_START.with(|start| start.set(Some(Instant::now())));
+    }
+
+    fn on_response(&self, request: &Request, response: &mut Response) {
+        let duration_ms = REQUEST_START
+            .with(|start| start.take())
+            .map(|start| {
+                let elapsed = start.elapsed();
+                elapsed.as_secs() as f64 * 1e3 + elapsed.subsec_nanos() as f64 / 1e6
+            })
+            .unwrap_or(0.0);
+        let bytes = match response.body() {
+            Some(Body::Sized(_, size)) => Some(size),
+            _ => None,
+        };
+        let request_id = response.headers().get_one("X-Request-ID").map(|id| id.to_owned());
+
+        let record = AccessRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            request_id: request_id.as_ref().map(|id| id.as_str()),
+            remote: request
+                .remote()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_else(|| String::from("-")),
+            method: request.method().as_str(),
+            path: request.uri().as_str(),
+            status: response.status().code,
+            bytes,
+            duration_ms,
+        };
+
+        if JSON.load(Ordering::Relaxed) {
+            if let Ok(line) = serde_json::to_string(&record) {
+                info!(target: ACCESS_TARGET, "{}", line);
+            }
+        } else {
+            info!(
+                target: ACCESS_TARGET,
+                "{} - \"{} {}\" {} {} {:.3}ms {}",
+                record.remote,
+                record.method,
+                record.path,
+                record.status,
+                record
+                    .bytes
+                    .map(|b| b.to_string())
+                    .unwrap_or_else(|| String::from("-")),
+                record.duration_ms,
+                record.request_id.unwrap_or("-")
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Read;
+
+    use log::{LogLevel, LogLevelFilter};
+    use serde_json;
+
+    use config::LoggingConfig;
+    use super::*;
+
+    #[test]
+    fn most_specific_module_wins() {
+        let mut levels = Levels::default();
+        levels.parse("warn,lycaon=info,lycaon::auth=debug");
+
+        assert_eq!(levels.level_for("rocket::launch"), LogLevelFilter::Warn);
+        assert_eq!(levels.level_for("lycaon::routes"), LogLevelFilter::Info);
+        assert_eq!(levels.level_for("lycaon::auth::acl"), LogLevelFilter::Debug);
+        assert_eq!(levels.level_for("lycaon_backend"), LogLevelFilter::Warn);
+    }
+
+    #[test]
+    fn access_lines_whatever_the_default_level() {
+        let mut levels = Levels::default();
+        assert!(levels.allows(LogLevel::Info, ACCESS_TARGET));
+        assert!(!levels.allows(LogLevel::Info, "lycaon::routes"));
+
+        levels.parse("lycaon=warn");
+        assert!(levels.allows(LogLevel::Info, ACCESS_TARGET));
+        levels.parse("lycaon::access=warn");
+        assert!(!levels.allows(LogLevel::Info, ACCESS_TARGET));
+    }
+
+    #[test]
+    fn access_log_at_the_default_level() {
+        let path = env::temp_dir().join("lycaon-access.log");
+        let _ = fs::remove_file(&path);
+        let config: LoggingConfig =
+            serde_json::from_value(json!({ "output": path.to_str().unwrap() })).unwrap();
+        init(config).unwrap();
+
+        info!(target: ACCESS_TARGET, "127.0.0.1 - \"GET /v2/\" 200");
+        let mut written = String::new();
+        File::open(&path).unwrap().read_to_string(&mut written).unwrap();
+        assert!(written.contains("\"GET /v2/\" 200"));
+        fs::remove_file(&path).unwrap();
+    }
+}
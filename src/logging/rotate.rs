@@ -0,0 +1,93 @@
+//! A log file that is rotated once it grows past a size limit.
+//!
+//! `lycaon.log` is renamed to `lycaon.log.1`, older files are shifted
+//! up by one and the oldest beyond _max_files_ is removed.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+
+pub struct RotatingFile {
+    path: String,
+    max_size: u64,
+    max_files: u32,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn open(path: &str, max_size: u64, max_files: u32) -> io::Result<RotatingFile> {
+        let file = open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile {
+            path: path.to_owned(),
+            max_size,
+            max_files,
+            file,
+            size,
+        })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            for n in (1..self.max_files).rev() {
+                let from = format!("{}.{}", self.path, n);
+                if fs::metadata(&from).is_ok() {
+                    fs::rename(&from, format!("{}.{}", self.path, n + 1))?;
+                }
+            }
+            fs::rename(&self.path, format!("{}.1", self.path))?;
+        }
+        self.file = open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open(path: &str) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+
+    use super::RotatingFile;
+
+    #[test]
+    fn rotates_past_max_size() {
+        let dir = env::temp_dir().join(format!("lycaon-rotate-{}", ::uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.log");
+        let path = path.to_str().unwrap();
+
+        let mut file = RotatingFile::open(path, 10, 2).unwrap();
+        for _ in 0..4 {
+            file.write_all(b"12345678\n").unwrap();
+        }
+
+        assert!(fs::metadata(format!("{}.1", path)).is_ok());
+        assert!(fs::metadata(format!("{}.2", path)).is_ok());
+        assert!(fs::metadata(format!("{}.3", path)).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
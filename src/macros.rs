@@ -6,3 +6,36 @@ macro_rules! not_implemented {
         Err(util::std_err("Not implemented"))
     }}
 }
+
+/// The validated repository name of a registry route's _path_, the
+/// handler returns `NAME_INVALID` if it isn't one.
+macro_rules! repository {
+    ($path:expr) => {{
+        match ::controller::repository::validate(&$path.name) {
+            Ok(repository) => repository,
+            Err(e) => return MaybeResponse::build(Err(e)),
+        }
+    }}
+}
+
+/// The checked upload _uuid_ of a registry route, the handler returns
+/// `BLOB_UPLOAD_UNKNOWN` if it isn't one.
+macro_rules! upload {
+    ($uuid:expr) => {{
+        match ::controller::uuid::parse(&$uuid) {
+            Ok(uuid) => uuid,
+            Err(e) => return MaybeResponse::build(Err(e)),
+        }
+    }}
+}
+
+/// The parsed _digest_ of a registry route, the handler returns
+/// `DIGEST_INVALID` if it isn't one.
+macro_rules! digest {
+    ($digest:expr) => {{
+        match ::controller::digest::parse(&$digest) {
+            Ok(digest) => digest,
+            Err(e) => return MaybeResponse::build(Err(e)),
+        }
+    }}
+}
@@ -14,6 +14,9 @@
 #![feature(use_extern_macros)]
 #![plugin(rocket_codegen)]
 
+extern crate base64;
+extern crate bcrypt;
+extern crate chrono;
 extern crate clap;
 extern crate config as cfg;
 extern crate ctrlc;
@@ -23,12 +26,20 @@ extern crate futures;
 extern crate getopts;
 extern crate grpcio;
 extern crate hostname;
+extern crate jsonwebtoken;
+#[macro_use]
+extern crate lazy_static;
 extern crate orset;
+#[macro_use]
+extern crate prometheus;
 extern crate protobuf;
+extern crate regex;
+extern crate reqwest;
 extern crate ring;
 extern crate rocket;
 extern crate rocket_contrib;
 extern crate serde;
+#[macro_use]
 extern crate serde_json;
 extern crate tokio_core;
 extern crate tokio_io;
@@ -38,7 +49,6 @@ extern crate lycaon_backend as backend;
 extern crate lycaon_protobuf as grpc;
 extern crate lycaon_errors as errors;
 
-extern crate env_logger;
 extern crate failure_derive;
 #[macro_use(log, warn, info, debug)]
 extern crate log;
@@ -48,48 +58,48 @@ extern crate serde_derive;
 #[cfg(test)]
 extern crate quickcheck;
 
-use std::thread;
-use failure::Error;
 use clap::ArgMatches;
 
 #[macro_use]
 mod macros;
+mod archive;
+mod auth;
+mod balancer;
+mod commands;
+mod datadir;
+mod logging;
+mod manifest;
 pub mod controller;
 pub mod config;
+mod fsck;
+mod gc;
+mod metrics;
+mod notifications;
+mod proxy;
+mod remote;
+mod replication;
+mod rpc;
 pub mod response;
 mod routes;
+mod scrub;
+mod shutdown;
 mod state;
+mod trace;
 mod types;
 mod util;
 
-fn grpc(args: &ArgMatches) -> Result<std::thread::JoinHandle<()>, Error> {
-    debug!("Setting up RPC Server");
-
-    let f = args.value_of("config");
-
-    let cnfg = match f {
-        Some(v) => config::LycaonConfig::new(&v)?,
-        None => config::LycaonConfig::default()?,
-    };
-
-    Ok(thread::spawn(move || {
-        backend::server(cnfg.grpc());
-    }))
-}
-
 fn main() {
-    config::main_logger().expect("Failed to init logging");
-
     // Parse command line
     let args = config::parse_args();
 
-    // GRPC Backend thread.
-    let _grpc_thread = grpc(&args).expect("Failed to start GRPC");
-
-    //Rocket web stuff
-    let rocket = config::rocket(&args).unwrap_or_else(|e| {
-        log::error!("Rocket failed to process arguments {}", e);
-        std::process::exit(1);
-    });
-    rocket.launch();
+    let code = match args.subcommand() {
+        ("serve", Some(sub)) => commands::serve(&args, sub),
+        ("gc", Some(sub)) => commands::gc(sub),
+        ("fsck", Some(sub)) => commands::fsck(sub),
+        ("import", Some(sub)) => commands::import(sub),
+        ("export", Some(sub)) => commands::export(sub),
+        ("peers", Some(sub)) => commands::peers(&args, sub),
+        _ => commands::serve(&args, &ArgMatches::default()),
+    };
+    std::process::exit(code);
 }
@@ -0,0 +1,293 @@
+//! Manifest formats.
+//!
+//! Docker schema2 images and manifest lists are accepted along with
+//! their OCI counterparts. A pushed manifest is parsed to find the
+//! blobs and child manifests it references, all of which have to exist
+//! in the repository already: blobs pushed or mounted into it, and
+//! manifests pushed to it. Clients say which formats they understand
+//! with `Accept` headers, usually several of them.
+
+use failure::Error;
+use rocket::Outcome;
+use rocket::request::{self, FromRequest, Request};
+use serde_json::{self, Value};
+
+use datadir::DataDir;
+use errors;
+use state::manifests;
+use types::Digest;
+
+pub const DOCKER_MANIFEST: &'static str = "application/vnd.docker.distribution.manifest.v2+json";
+pub const DOCKER_MANIFEST_LIST: &'static str =
+    "application/vnd.docker.distribution.manifest.list.v2+json";
+pub const OCI_MANIFEST: &'static str = "application/vnd.oci.image.manifest.v1+json";
+pub const OCI_INDEX: &'static str = "application/vnd.oci.image.index.v1+json";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    /// A single image, a config and layers
+    Image,
+    /// A list of images, e.g. one per platform
+    Index,
+}
+
+/// What kind of manifest _media_type_ is, if it's supported.
+pub fn kind(media_type: &str) -> Option<Kind> {
+    match media_type {
+        DOCKER_MANIFEST | OCI_MANIFEST => Some(Kind::Image),
+        DOCKER_MANIFEST_LIST | OCI_INDEX => Some(Kind::Index),
+        _ => None,
+    }
+}
+
+/// The parts of a manifest the registry cares about.
+#[derive(Debug)]
+pub struct Parsed {
+    pub media_type: String,
+    pub kind: Kind,
+    /// Config and layers of an image
+    pub blobs: Vec<Digest>,
+    /// Child manifests of an index
+    pub manifests: Vec<Digest>,
+    /// The manifest this one refers to, e.g. the image a signature is for
+    pub subject: Option<Digest>,
+    /// What kind of artifact this is, the config media type of images
+    /// without an explicit artifactType
+    pub artifact_type: String,
+    /// The annotations as a JSON object, empty without any
+    pub annotations: String,
+}
+
+/// Parses a manifest pushed with _content_type_.
+///
+/// The media type comes from the manifest itself or the Content-Type,
+/// they have to agree if both are given.
+pub fn parse(content_type: Option<&str>, content: &[u8]) -> Result<Parsed, Error> {
+    let value: Value =
+        serde_json::from_slice(content).map_err(|_| errors::Client::MANIFEST_INVALID)?;
+    if value["schemaVersion"].as_u64() != Some(2) {
+        return Err(errors::Client::MANIFEST_INVALID.into());
+    }
+
+    // a missing Content-Type often arrives as an empty one
+    let content_type = match content_type.map(|ct| ct.split(';').next().unwrap_or_default().trim()) {
+        Some(ct) if !ct.is_empty() => Some(ct),
+        _ => None,
+    };
+    let declared = value["mediaType"].as_str();
+    let media_type = match (declared, content_type) {
+        (Some(declared), Some(ct)) if declared != ct => {
+            debug!("Manifest declares {} but was sent as {}", declared, ct);
+            return Err(errors::Client::MANIFEST_INVALID.into());
+        }
+        (Some(media_type), _) | (None, Some(media_type)) => media_type.to_owned(),
+        // OCI makes mediaType optional, go by the content
+        (None, None) => match value["manifests"].is_array() {
+            true => OCI_INDEX.to_owned(),
+            false => OCI_MANIFEST.to_owned(),
+        },
+    };
+    let kind = kind(&media_type).ok_or(errors::Client::MANIFEST_INVALID)?;
+
+    let subject = match value["subject"]["digest"].as_str() {
+        Some(digest) => Some(descriptor_digest(digest)?),
+        None => None,
+    };
+    let artifact_type = value["artifactType"]
+        .as_str()
+        .or_else(|| value["config"]["mediaType"].as_str())
+        .unwrap_or_default()
+        .to_owned();
+    let annotations = match value["annotations"].is_object() {
+        true => value["annotations"].to_string(),
+        false => String::new(),
+    };
+
+    let mut parsed = Parsed {
+        media_type,
+        kind,
+        blobs: Vec::new(),
+        manifests: Vec::new(),
+        subject,
+        artifact_type,
+        annotations,
+    };
+    match kind {
+        Kind::Image => {
+            let config = value["config"]["digest"]
+                .as_str()
+                .ok_or(errors::Client::MANIFEST_INVALID)?;
+            parsed.blobs.push(descriptor_digest(config)?);
+            parsed.blobs.extend(digests(&value["layers"])?);
+        }
+        Kind::Index => parsed.manifests = digests(&value["manifests"])?,
+    }
+    Ok(parsed)
+}
+
+/// The digest of every descriptor in _descriptors_.
+fn digests(descriptors: &Value) -> Result<Vec<Digest>, Error> {
+    let descriptors = descriptors
+        .as_array()
+        .ok_or(errors::Client::MANIFEST_INVALID)?;
+    descriptors
+        .iter()
+        .map(|descriptor| match descriptor["digest"].as_str() {
+            Some(digest) => descriptor_digest(digest),
+            None => Err(errors::Client::MANIFEST_INVALID.into()),
+        })
+        .collect()
+}
+
+/// A malformed digest in a descriptor makes the whole manifest invalid.
+fn descriptor_digest(digest: &str) -> Result<Digest, Error> {
+    digest.parse().map_err(|e| {
+        debug!("Manifest descriptor: {}", e);
+        errors::Client::MANIFEST_INVALID.into()
+    })
+}
+
+/// Checks everything _parsed_ references exists in _repository_, blobs
+/// linked into it and manifests stored in it.
+pub fn validate(data: &DataDir, repository: &str, parsed: &Parsed) -> Result<(), Error> {
+    for digest in &parsed.blobs {
+        if !data.has_blob(repository, digest) {
+            debug!("{} references unknown blob {}", repository, digest);
+            return Err(errors::Client::MANIFEST_BLOB_UNKNOWN.into());
+        }
+    }
+    for digest in &parsed.manifests {
+        if manifests::get(data, repository, &digest.to_string()).is_none() {
+            debug!("{} references unknown manifest {}", repository, digest);
+            return Err(errors::Client::MANIFEST_BLOB_UNKNOWN.into());
+        }
+    }
+    Ok(())
+}
+
+/// The media types a client accepts, from all of its Accept headers.
+/// No Accept header means anything goes.
+#[derive(Debug)]
+pub struct Accepted(Vec<String>);
+
+impl Accepted {
+    pub fn new(headers: Vec<&str>) -> Accepted {
+        let types = headers
+            .iter()
+            .flat_map(|header| header.split(','))
+            .map(|media_type| media_type.split(';').next().unwrap_or_default().trim())
+            .filter(|media_type| !media_type.is_empty())
+            .map(|media_type| media_type.to_owned())
+            .collect();
+        Accepted(types)
+    }
+
+    /// Whether a manifest of _media_type_ can be returned. Clients
+    /// predating manifest lists, e.g., don't understand them.
+    pub fn accepts(&self, media_type: &str) -> bool {
+        self.0.is_empty()
+            || self.0
+                .iter()
+                .any(|accepted| accepted == media_type || accepted == "*/*")
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for Accepted {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Accepted, ()> {
+        Outcome::Success(Accepted::new(request.headers().get("Accept").collect()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+
+    use uuid::Uuid;
+
+    use datadir;
+    use types::Algorithm;
+
+    use super::*;
+
+    #[test]
+    fn parse_image_and_index() {
+        let digest = |name: &str| Digest::of(Algorithm::Sha256, name.as_bytes());
+        let image = format!(
+            r#"{{
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.docker.distribution.manifest.v2+json",
+            "config": {{"digest": "{}"}},
+            "layers": [{{"digest": "{}"}}]
+        }}"#,
+            digest("c0"),
+            digest("l1")
+        );
+        let parsed = parse(None, image.as_bytes()).unwrap();
+        assert_eq!(parsed.kind, Kind::Image);
+        assert_eq!(parsed.blobs, vec![digest("c0"), digest("l1")]);
+        assert!(parse(Some(OCI_MANIFEST), image.as_bytes()).is_err());
+
+        let index = format!(
+            r#"{{"schemaVersion": 2, "manifests": [{{"digest": "{}"}}]}}"#,
+            digest("m1")
+        );
+        let parsed = parse(None, index.as_bytes()).unwrap();
+        assert_eq!(parsed.media_type, OCI_INDEX);
+        assert_eq!(parsed.manifests, vec![digest("m1")]);
+
+        let signature = format!(
+            r#"{{
+            "schemaVersion": 2,
+            "artifactType": "application/vnd.example.signature",
+            "config": {{"mediaType": "application/vnd.oci.empty.v1+json", "digest": "{}"}},
+            "layers": [],
+            "subject": {{"digest": "{}"}}
+        }}"#,
+            digest("e0"),
+            digest("s1")
+        );
+        let parsed = parse(None, signature.as_bytes()).unwrap();
+        assert_eq!(parsed.subject, Some(digest("s1")));
+        assert_eq!(parsed.artifact_type, "application/vnd.example.signature");
+
+        let malformed = br#"{"schemaVersion": 2, "manifests": [{"digest": "sha256:m1"}]}"#;
+        assert!(parse(None, malformed).is_err());
+        assert!(parse(None, br#"{"schemaVersion": 1}"#).is_err());
+        assert!(parse(None, b"not json").is_err());
+    }
+
+    #[test]
+    fn accept_negotiation() {
+        let accepted = Accepted::new(vec![
+            "application/vnd.docker.distribution.manifest.v2+json; q=0.5",
+            "application/vnd.oci.image.manifest.v1+json, application/json",
+        ]);
+        assert!(accepted.accepts(DOCKER_MANIFEST));
+        assert!(accepted.accepts(OCI_MANIFEST));
+        assert!(!accepted.accepts(DOCKER_MANIFEST_LIST));
+        assert!(Accepted::new(vec![]).accepts(OCI_INDEX));
+    }
+
+    #[test]
+    fn blobs_have_to_be_linked() {
+        let root = env::temp_dir().join(format!("lycaon-manifest-{}", Uuid::new_v4()));
+        let config = Digest::of(Algorithm::Sha256, b"config");
+        datadir::write(&root.join("layers").join(config.to_string()), b"config").unwrap();
+        let data = DataDir::open(&root).unwrap();
+        let image = format!(
+            r#"{{"schemaVersion": 2, "config": {{"digest": "{}"}}, "layers": []}}"#,
+            config
+        );
+        let parsed = parse(None, image.as_bytes()).unwrap();
+
+        // stored for another repository only
+        data.link_blob("library/debian", &config).unwrap();
+        assert!(validate(&data, "library/alpine", &parsed).is_err());
+        data.link_blob("library/alpine", &config).unwrap();
+        assert!(validate(&data, "library/alpine", &parsed).is_ok());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
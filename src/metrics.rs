@@ -0,0 +1,198 @@
+//! Prometheus metrics for the HTTP frontend.
+//!
+//! Every request is counted and timed by the `Metrics` fairing, labelled
+//! with the route it matched rather than the raw path to keep the
+//! number of series bounded. The backend registers its own collectors
+//! in the same registry, see `lycaon_backend::metrics`.
+
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use failure::Error;
+use prometheus::{self, Counter, CounterVec, Encoder, Gauge, GaugeVec, HistogramVec, TextEncoder};
+use rocket::{Data, Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::response::Body;
+
+use config::{LAYERS_DIR, QUARANTINE_DIR, SCRATCH_DIR};
+use datadir::DataDir;
+
+lazy_static! {
+    static ref HTTP_REQUESTS: CounterVec = register_counter_vec!(
+        "lycaon_http_requests_total",
+        "Number of HTTP requests handled",
+        &["route", "method", "status"]
+    ).unwrap();
+
+    static ref HTTP_DURATION: HistogramVec = register_histogram_vec!(
+        "lycaon_http_request_duration_seconds",
+        "Time spent handling HTTP requests",
+        &["route", "method"]
+    ).unwrap();
+
+    pub static ref BYTES_UPLOADED: Counter = register_counter!(
+        "lycaon_uploaded_bytes_total",
+        "Bytes of blob data received from clients"
+    ).unwrap();
+
+    pub static ref BYTES_DOWNLOADED: Counter = register_counter!(
+        "lycaon_downloaded_bytes_total",
+        "Bytes of blob and manifest data sent to clients"
+    ).unwrap();
+
+    pub static ref SCRUBBED_BLOBS: Counter = register_counter!(
+        "lycaon_scrub_blobs_total",
+        "Blobs re-hashed by the scrubber"
+    ).unwrap();
+
+    pub static ref SCRUBBED_BYTES: Counter = register_counter!(
+        "lycaon_scrub_bytes_total",
+        "Bytes of blob data re-hashed by the scrubber"
+    ).unwrap();
+
+    pub static ref CORRUPT_BLOBS: Counter = register_counter!(
+        "lycaon_scrub_corrupt_blobs_total",
+        "Blobs found not to match their digest and quarantined"
+    ).unwrap();
+
+    pub static ref REPAIRED_BLOBS: Counter = register_counter!(
+        "lycaon_scrub_repaired_blobs_total",
+        "Corrupt blobs replaced by a healthy copy from a peer"
+    ).unwrap();
+
+    pub static ref SCRUB_COMPLETED: Gauge = register_gauge!(
+        "lycaon_scrub_last_completed_timestamp_seconds",
+        "When the last scrub pass over all blobs finished"
+    ).unwrap();
+
+    pub static ref HEALTHY_BACKENDS: Gauge = register_gauge!(
+        "lycaon_backends_healthy",
+        "Backend nodes answering health checks"
+    ).unwrap();
+
+    pub static ref BACKEND_RETRIES: Counter = register_counter!(
+        "lycaon_backend_retries_total",
+        "Backend calls retried after failing"
+    ).unwrap();
+
+    pub static ref BACKEND_CIRCUIT_OPEN: Gauge = register_gauge!(
+        "lycaon_backend_circuit_open",
+        "1 while backend calls fail fast after too many failures"
+    ).unwrap();
+
+    static ref STORAGE: GaugeVec = register_gauge_vec!(
+        "lycaon_storage_bytes",
+        "Bytes used on disk",
+        &["directory"]
+    ).unwrap();
+}
+
+thread_local! {
+    // Rocket handles a request on a single thread from start to finish.
+    static REQUEST_START: Cell<Option<Instant>> = Cell::new(None);
+}
+
+/// Fairing recording request counts and latencies per route.
+pub struct Metrics;
+
+impl Fairing for Metrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Prometheus metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, _: &mut Request, _: &Data) {
+        REQUEST_START.with(|start| start.set(Some(Instant::now())));
+    }
+
+    fn on_response(&self, request: &Request, response: &mut Response) {
+        let route = request
+            .route()
+            .map(|route| match route.uri.path() {
+                "/v2/<path..>" => endpoint(request.uri().path()).to_owned(),
+                path => path.to_owned(),
+            })
+            .unwrap_or_else(|| String::from("unmatched"));
+        let method = request.method().as_str();
+        let status = response.status().code.to_string();
+
+        HTTP_REQUESTS
+            .with_label_values(&[&route, method, &status])
+            .inc();
+        if let Some(start) = REQUEST_START.with(|start| start.take()) {
+            let elapsed = start.elapsed();
+            let seconds = elapsed.as_secs() as f64 + elapsed.subsec_nanos() as f64 / 1e9;
+            HTTP_DURATION
+                .with_label_values(&[&route, method])
+                .observe(seconds);
+        }
+
+        let path = request.uri().path();
+        let is_content = path.contains("/blobs/") || path.contains("/manifests/");
+        if request.method() == Method::Get && is_content {
+            if let Some(Body::Sized(_, size)) = response.body() {
+                BYTES_DOWNLOADED.inc_by(size as f64);
+            }
+        }
+    }
+}
+
+/// The registry routes all match `/v2/<path..>` as repository names
+/// have any depth, so they are labelled by API endpoint instead.
+fn endpoint(path: &str) -> &'static str {
+    let path = path.trim_right_matches('/');
+    if path.ends_with("/tags/list") {
+        "/v2/<name>/tags/list"
+    } else if path.ends_with("/blobs/uploads") {
+        "/v2/<name>/blobs/uploads/"
+    } else if path.contains("/blobs/uploads/") {
+        "/v2/<name>/blobs/uploads/<uuid>"
+    } else if path.contains("/blobs/") {
+        "/v2/<name>/blobs/<digest>"
+    } else if path.contains("/manifests/") {
+        "/v2/<name>/manifests/<reference>"
+    } else if path.contains("/referrers/") {
+        "/v2/<name>/referrers/<digest>"
+    } else {
+        "/v2/<path..>"
+    }
+}
+
+/// Renders every registered metric in the Prometheus text format,
+/// measuring the storage used in _data_ first.
+pub fn render(data: &DataDir) -> Result<(String, String), Error> {
+    let directories = [
+        (LAYERS_DIR, data.layers()),
+        (SCRATCH_DIR, data.scratch()),
+        (QUARANTINE_DIR, data.quarantine()),
+    ];
+    for &(label, ref directory) in &directories {
+        STORAGE.with_label_values(&[label]).set(disk_usage(directory) as f64);
+    }
+
+    let encoder = TextEncoder::new();
+    let mut buffer = Vec::new();
+    encoder.encode(&prometheus::gather(), &mut buffer)?;
+    Ok((encoder.format_type().to_owned(), String::from_utf8(buffer)?))
+}
+
+/// Total size of the files in _path_, including subdirectories.
+fn disk_usage(path: &Path) -> u64 {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| match entry.metadata() {
+                    Ok(ref metadata) if metadata.is_dir() => disk_usage(&entry.path()),
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
@@ -0,0 +1,286 @@
+//! Registry event notifications.
+//!
+//! Events are sent to the configured webhook endpoints as envelopes in
+//! the format of the docker distribution notification system. Each
+//! endpoint has its own durable queue on disk, so events survive
+//! restarts and are retried with backoff while an endpoint is down.
+
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Sender};
+
+use chrono::Utc;
+use failure::Error;
+use hostname;
+use rocket::Outcome;
+use rocket::State;
+use rocket::request::{self, FromRequest, Request};
+use uuid::Uuid;
+
+use auth::acl;
+use backend;
+use config::{EndpointConfig, NotificationsConfig};
+use datadir::DataDir;
+
+mod queue;
+
+use self::queue::Queue;
+
+pub const EVENTS_MEDIA_TYPE: &'static str = "application/vnd.docker.distribution.events.v1+json";
+pub const BLOB_MEDIA_TYPE: &'static str = "application/octet-stream";
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Push,
+    Pull,
+    Mount,
+    Delete,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Action::Push => "push",
+            Action::Pull => "pull",
+            Action::Mount => "mount",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+/// The blob or manifest an event is about.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Target {
+    pub media_type: String,
+    pub size: u64,
+    pub digest: String,
+    pub length: u64,
+    pub repository: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    /// The repository a mounted blob came from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_repository: Option<String>,
+}
+
+impl Target {
+    pub fn blob(repository: &str, digest: &str, size: u64) -> Target {
+        Target {
+            media_type: BLOB_MEDIA_TYPE.to_owned(),
+            size,
+            digest: digest.to_owned(),
+            length: size,
+            repository: repository.to_owned(),
+            url: format!("/v2/{}/blobs/{}", repository, digest),
+            tag: None,
+            from_repository: None,
+        }
+    }
+
+    /// The blob _digest_ mounted into _repository_ from _from_.
+    pub fn mount(repository: &str, digest: &str, size: u64, from: &str) -> Target {
+        Target {
+            from_repository: Some(from.to_owned()),
+            ..Target::blob(repository, digest, size)
+        }
+    }
+
+    pub fn manifest(
+        repository: &str,
+        media_type: &str,
+        digest: &str,
+        size: u64,
+        tag: Option<&str>,
+    ) -> Target {
+        Target {
+            media_type: media_type.to_owned(),
+            size,
+            digest: digest.to_owned(),
+            length: size,
+            repository: repository.to_owned(),
+            url: format!("/v2/{}/manifests/{}", repository, digest),
+            tag: tag.map(|tag| tag.to_owned()),
+            from_repository: None,
+        }
+    }
+}
+
+/// The HTTP request that caused an event.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RequestRecord {
+    pub id: String,
+    pub addr: String,
+    pub host: String,
+    pub method: String,
+    pub useragent: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Actor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Source {
+    pub addr: String,
+    #[serde(rename = "instanceID")]
+    pub instance_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Event {
+    pub id: String,
+    pub timestamp: String,
+    pub action: Action,
+    pub target: Target,
+    pub request: RequestRecord,
+    pub actor: Actor,
+    pub source: Source,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Envelope {
+    pub events: Vec<Event>,
+}
+
+/// An endpoint's filters and the queue feeding its delivery thread.
+struct Endpoint {
+    config: EndpointConfig,
+    queue: Queue,
+    wake: Mutex<Sender<()>>,
+}
+
+impl Endpoint {
+    fn accepts(&self, event: &Event) -> bool {
+        let repositories = self.config.repositories();
+        let actions = self.config.actions();
+        (repositories.is_empty()
+            || repositories
+                .iter()
+                .any(|pattern| acl::matches(pattern, &event.target.repository)))
+            && (actions.is_empty() || actions.iter().any(|a| a == event.action.as_str()))
+    }
+}
+
+/// Managed state queueing events for every interested endpoint.
+pub struct Notifier {
+    endpoints: Vec<Endpoint>,
+    source: Source,
+}
+
+impl Notifier {
+    /// Queues undelivered events in _data_ unless configured otherwise.
+    pub fn new(
+        config: Option<NotificationsConfig>,
+        addr: String,
+        data: &DataDir,
+    ) -> Result<Notifier, Error> {
+        let config = match config {
+            Some(config) => config,
+            None => return Ok(Notifier::disabled()),
+        };
+
+        let queues = config.queue().unwrap_or_else(|| data.notifications());
+        let mut endpoints = Vec::new();
+        for endpoint in config.endpoints() {
+            let queue = Queue::open(queues.join(endpoint.name()))?;
+            let (wake, woken) = mpsc::channel();
+            queue::spawn_worker(endpoint.clone(), queue.clone(), woken)?;
+            info!("Sending notifications to {} ({})", endpoint.name(), endpoint.url());
+            endpoints.push(Endpoint {
+                config: endpoint,
+                queue,
+                wake: Mutex::new(wake),
+            });
+        }
+
+        Ok(Notifier {
+            endpoints,
+            source: Source {
+                addr,
+                instance_id: Uuid::new_v4().to_string(),
+            },
+        })
+    }
+
+    pub fn disabled() -> Notifier {
+        Notifier {
+            endpoints: Vec::new(),
+            source: Source::default(),
+        }
+    }
+
+    /// Queues an event for every endpoint whose filters match it.
+    pub fn notify(&self, action: Action, target: Target, request: RequestRecord, actor: Option<String>) {
+        if self.endpoints.is_empty() {
+            return;
+        }
+
+        let event = Event {
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            action,
+            target,
+            request,
+            actor: Actor { name: actor },
+            source: self.source.clone(),
+        };
+        let envelope = Envelope { events: vec![event] };
+
+        for endpoint in self.endpoints.iter().filter(|e| e.accepts(&envelope.events[0])) {
+            match endpoint.queue.push(&envelope) {
+                Ok(()) => {
+                    let _ = endpoint.wake.lock().unwrap().send(());
+                }
+                Err(e) => warn!("Failed to queue event for {}: {}", endpoint.config.name(), e),
+            }
+        }
+    }
+}
+
+/// Request guard used by handlers to emit events about the request.
+pub struct Notify<'r> {
+    notifier: State<'r, Notifier>,
+    request: RequestRecord,
+}
+
+impl<'r> Notify<'r> {
+    pub fn emit(&self, action: Action, target: Target, actor: Option<String>) {
+        debug!("Event: {} {}", action.as_str(), target.url);
+        self.notifier
+            .notify(action, target, self.request.clone(), actor);
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for Notify<'r> {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Notify<'r>, ()> {
+        let notifier = request.guard::<State<Notifier>>()?;
+        let headers = request.headers();
+        let record = RequestRecord {
+            id: backend::trace::current_request_id().unwrap_or_default(),
+            addr: request
+                .remote()
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+            host: headers.get_one("Host").unwrap_or_default().to_owned(),
+            method: request.method().as_str().to_owned(),
+            useragent: headers.get_one("User-Agent").unwrap_or_default().to_owned(),
+        };
+
+        Outcome::Success(Notify {
+            notifier,
+            request: record,
+        })
+    }
+}
+
+/// The address this instance is reachable at, used as the event source.
+pub fn source_addr(port: u16) -> String {
+    let host = hostname::get_hostname().unwrap_or_else(|| String::from("localhost"));
+    format!("{}:{}", host, port)
+}
@@ -0,0 +1,188 @@
+//! Durable per-endpoint delivery queue.
+//!
+//! Every queued envelope is a JSON file in the endpoint's queue
+//! directory, along with the number of failed attempts and when to try
+//! next. A worker thread per endpoint delivers due entries in order and
+//! removes them once the endpoint accepted them, or after too many
+//! attempts.
+
+use std::cmp;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use failure::Error;
+use reqwest;
+use reqwest::header::Headers;
+use serde_json;
+use uuid::Uuid;
+
+use config::EndpointConfig;
+use errors;
+use notifications::{Envelope, EVENTS_MEDIA_TYPE};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Delivery {
+    attempts: u32,
+    next_attempt: i64,
+    envelope: Envelope,
+}
+
+#[derive(Clone, Debug)]
+pub struct Queue {
+    dir: PathBuf,
+}
+
+impl Queue {
+    pub fn open(dir: PathBuf) -> Result<Queue, Error> {
+        fs::create_dir_all(&dir)?;
+        Ok(Queue { dir })
+    }
+
+    /// Stores _envelope_ for delivery as soon as possible.
+    pub fn push(&self, envelope: &Envelope) -> Result<(), Error> {
+        let delivery = Delivery {
+            attempts: 0,
+            next_attempt: 0,
+            envelope: envelope.clone(),
+        };
+        // the timestamp prefix keeps the entries in the order they were queued
+        let name = format!(
+            "{:020}-{}.json",
+            Utc::now().timestamp_nanos(),
+            Uuid::new_v4().simple()
+        );
+        self.write(&self.dir.join(name), &delivery)
+    }
+
+    /// Writes to a temporary file first so a crash never leaves a
+    /// partial entry behind.
+    fn write(&self, path: &PathBuf, delivery: &Delivery) -> Result<(), Error> {
+        let tmp = path.with_extension("tmp");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&serde_json::to_vec(delivery)?)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, path).map_err(|e| e.into())
+    }
+
+    fn read(&self, path: &PathBuf) -> Result<Delivery, Error> {
+        let mut contents = Vec::new();
+        File::open(path)?.read_to_end(&mut contents)?;
+        serde_json::from_slice(&contents).map_err(|e| e.into())
+    }
+
+    /// Queued entries, oldest first.
+    fn pending(&self) -> Vec<PathBuf> {
+        let mut entries: Vec<PathBuf> = fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().map(|e| e == "json").unwrap_or(false))
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort();
+        entries
+    }
+}
+
+/// Starts the thread delivering _queue_ to _endpoint_. It wakes up
+/// whenever something is sent on _woken_, and once a second to retry.
+pub fn spawn_worker(
+    endpoint: EndpointConfig,
+    queue: Queue,
+    woken: Receiver<()>,
+) -> Result<(), Error> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(endpoint.timeout()))
+        .build()?;
+
+    thread::Builder::new()
+        .name(format!("notify-{}", endpoint.name()))
+        .spawn(move || loop {
+            process(&client, &endpoint, &queue);
+            match woken.recv_timeout(Duration::from_secs(1)) {
+                Ok(()) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => thread::sleep(Duration::from_secs(1)),
+            }
+        })?;
+    Ok(())
+}
+
+fn process(client: &reqwest::Client, endpoint: &EndpointConfig, queue: &Queue) {
+    for path in queue.pending() {
+        let mut delivery = match queue.read(&path) {
+            Ok(delivery) => delivery,
+            Err(e) => {
+                warn!("Dropping unreadable notification {:?}: {}", path, e);
+                let _ = fs::remove_file(&path);
+                continue;
+            }
+        };
+        if delivery.next_attempt > Utc::now().timestamp() {
+            continue;
+        }
+
+        match deliver(client, endpoint, &delivery.envelope) {
+            Ok(()) => {
+                debug!("Delivered notification to {}", endpoint.name());
+                let _ = fs::remove_file(&path);
+            }
+            Err(e) if delivery.attempts + 1 >= endpoint.max_retries() => {
+                warn!(
+                    "Giving up on notification to {} after {} attempts: {}",
+                    endpoint.name(),
+                    delivery.attempts + 1,
+                    e
+                );
+                let _ = fs::remove_file(&path);
+            }
+            Err(e) => {
+                delivery.attempts += 1;
+                delivery.next_attempt = Utc::now().timestamp() + backoff(endpoint, delivery.attempts);
+                info!(
+                    "Notification to {} failed ({}), retry {} in {}s",
+                    endpoint.name(),
+                    e,
+                    delivery.attempts,
+                    delivery.next_attempt - Utc::now().timestamp()
+                );
+                if let Err(e) = queue.write(&path, &delivery) {
+                    warn!("Failed to update notification {:?}: {}", path, e);
+                }
+            }
+        }
+    }
+}
+
+/// Exponential backoff, doubling with every attempt up to the maximum.
+fn backoff(endpoint: &EndpointConfig, attempts: u32) -> i64 {
+    let factor = 1i64 << cmp::min(attempts - 1, 20);
+    cmp::min(endpoint.backoff() as i64 * factor, endpoint.max_backoff() as i64)
+}
+
+fn deliver(client: &reqwest::Client, endpoint: &EndpointConfig, envelope: &Envelope) -> Result<(), Error> {
+    let mut headers = Headers::new();
+    headers.set_raw("Content-Type", EVENTS_MEDIA_TYPE);
+    for (name, value) in endpoint.headers() {
+        headers.set_raw(name, value);
+    }
+
+    let response = client
+        .post(&endpoint.url())
+        .headers(headers)
+        .body(serde_json::to_vec(envelope)?)
+        .send()?;
+
+    match response.status().is_success() {
+        true => Ok(()),
+        false => Err(errors::Server::GenericError(format!("endpoint returned {}", response.status())).into()),
+    }
+}
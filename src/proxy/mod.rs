@@ -0,0 +1,350 @@
+//! Pull-through cache of an upstream registry.
+//!
+//! With a `[proxy]` section configured, manifests and blobs missing
+//! locally are fetched from the upstream registry, verified against
+//! their digest and stored before being served. Manifests referenced
+//! by tag are checked against the upstream again once they are older
+//! than the configured TTL; if the upstream can't be reached the
+//! cached copy is served.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::time::Duration;
+
+use failure::Error;
+use reqwest;
+use reqwest::header::Headers;
+use uuid::Uuid;
+
+use config::ProxyConfig;
+use datadir::DataDir;
+use errors;
+use remote::{self, Remote};
+use state::manifests::{self, Manifest};
+use state::uuid;
+use types::Digest;
+
+/// Manifest media types asked for from the upstream.
+const MANIFEST_ACCEPT: &'static str = "application/vnd.docker.distribution.manifest.v2+json, \
+                                       application/vnd.docker.distribution.manifest.list.v2+json, \
+                                       application/vnd.oci.image.manifest.v1+json, \
+                                       application/vnd.oci.image.index.v1+json";
+
+/// Managed state for talking to the upstream, a no-op when no proxy
+/// is configured.
+pub struct Proxy {
+    config: Option<ProxyConfig>,
+    upstream: Option<Remote>,
+    data: DataDir,
+}
+
+impl Proxy {
+    /// Caches in _data_, which is also where manifests and blobs are
+    /// looked up without a proxy configured.
+    pub fn new(config: Option<ProxyConfig>, data: DataDir) -> Result<Proxy, Error> {
+        let upstream = match config {
+            Some(ref config) => {
+                info!("Caching upstream registry {}", config.url());
+                Some(Remote::new(
+                    config.url(),
+                    config.username(),
+                    config.password(),
+                    config.timeout(),
+                )?)
+            }
+            None => None,
+        };
+
+        Ok(Proxy {
+            config,
+            upstream,
+            data,
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// The manifest _reference_ points to, fetched from the upstream if
+    /// it isn't cached or the cached tag is older than the TTL.
+    pub fn manifest(&self, repository: &str, reference: &str) -> Result<Option<Manifest>, Error> {
+        let local = manifests::get(&self.data, repository, reference);
+        let config = match self.config {
+            Some(ref config) => config,
+            None => return Ok(local),
+        };
+
+        let fresh = manifests::is_digest(reference)
+            || manifests::tag_age(&self.data, repository, reference)
+                .map(|age| age < Duration::from_secs(config.ttl()))
+                .unwrap_or(false);
+        match local {
+            Some(manifest) if fresh => Ok(Some(manifest)),
+            Some(manifest) => match self.revalidate(repository, reference, manifest.clone()) {
+                Ok(manifest) => Ok(Some(manifest)),
+                Err(e) => {
+                    warn!("Serving stale {}:{}, upstream failed: {}", repository, reference, e);
+                    Ok(Some(manifest))
+                }
+            },
+            None => self.fetch_manifest(repository, reference).map(Some),
+        }
+    }
+
+    /// Checks whether the upstream tag still points at _cached_, and
+    /// fetches the new manifest if it doesn't.
+    fn revalidate(&self, repository: &str, tag: &str, cached: Manifest) -> Result<Manifest, Error> {
+        let path = format!("/v2/{}/manifests/{}", repository, tag);
+        let response = self.send(reqwest::Method::Head, &path, repository, Some(MANIFEST_ACCEPT))?;
+        match remote::header(&response, "Docker-Content-Digest") {
+            Some(ref digest) if *digest == cached.digest => {
+                debug!("{}:{} is up to date", repository, tag);
+                manifests::touch_tag(&self.data, repository, tag)?;
+                Ok(cached)
+            }
+            _ => self.fetch_manifest(repository, tag),
+        }
+    }
+
+    fn fetch_manifest(&self, repository: &str, reference: &str) -> Result<Manifest, Error> {
+        let path = format!("/v2/{}/manifests/{}", repository, reference);
+        let mut response = self.send(reqwest::Method::Get, &path, repository, Some(MANIFEST_ACCEPT))?;
+        if response.status().as_u16() == 404 {
+            return Err(errors::Client::MANIFEST_UNKNOWN.into());
+        }
+
+        let mut content = Vec::new();
+        response.read_to_end(&mut content)?;
+        let expected = match manifests::is_digest(reference) {
+            true => Some(reference.to_owned()),
+            false => remote::header(&response, "Docker-Content-Digest"),
+        };
+        // the manifest is stored under the digest of what was received,
+        // whatever the upstream claims; a claim that can't be parsed can't
+        // be checked either
+        let digest = match expected.map(|expected| expected.parse::<Digest>()) {
+            Some(Ok(expected)) => {
+                let digest = Digest::of(expected.algorithm(), &content);
+                if digest != expected {
+                    warn!("Upstream manifest {}:{} doesn't match its digest", repository, reference);
+                    return Err(errors::Server::DigestMismatch.into());
+                }
+                digest
+            }
+            Some(Err(_)) => {
+                warn!("Upstream manifest {}:{} has a malformed digest", repository, reference);
+                return Err(errors::Server::DigestMismatch.into());
+            }
+            None => manifests::digest_of(&content),
+        };
+
+        let media_type = remote::header(&response, "Content-Type").unwrap_or_default();
+        let stored =
+            manifests::store(&self.data, repository, reference, &media_type, &digest, content)?;
+        info!("Cached manifest {}:{} ({})", repository, reference, stored.digest);
+        Ok(stored)
+    }
+
+    /// Makes sure the blob is stored locally and linked into
+    /// _repository_, fetching it from the upstream if needed. Returns
+    /// false if it exists nowhere.
+    pub fn blob(&self, repository: &str, digest: &Digest) -> Result<bool, Error> {
+        if self.data.has_blob(repository, digest) {
+            return Ok(true);
+        }
+        if !self.enabled() {
+            return Ok(false);
+        }
+
+        let path = format!("/v2/{}/blobs/{}", repository, digest);
+        let mut response = self.send(reqwest::Method::Get, &path, repository, None)?;
+        if response.status().as_u16() == 404 {
+            return Ok(false);
+        }
+
+        // hash while streaming to a scratch file, only move it into the
+        // layer store once the digest is verified
+        let upload = format!("proxy-{}", Uuid::new_v4());
+        let scratch = self.data.upload(&upload);
+        let actual = {
+            let mut file = File::create(&scratch)?;
+            let mut hasher = digest.hasher();
+            let mut buffer = [0; 64 * 1024];
+            loop {
+                let read = response.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                hasher.update(&buffer[..read]);
+                file.write_all(&buffer[..read])?;
+            }
+            hasher.finish()
+        };
+
+        if actual != *digest {
+            let _ = fs::remove_file(&scratch);
+            warn!("Upstream blob {} hashed to {}", digest, actual);
+            return Err(errors::Server::DigestMismatch.into());
+        }
+        uuid::save_layer(&self.data, &upload, digest)?;
+        self.data.link_blob(repository, digest)?;
+        info!("Cached blob {}@{}", repository, digest);
+        Ok(true)
+    }
+
+    /// Sends a request to the upstream. Anything but success or a 404
+    /// is an error.
+    fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        repository: &str,
+        accept: Option<&str>,
+    ) -> Result<reqwest::Response, Error> {
+        let upstream = self.upstream
+            .as_ref()
+            .ok_or(errors::Server::Invalid("proxy configuration"))?;
+        let mut headers = Headers::new();
+        if let Some(accept) = accept {
+            headers.set_raw("Accept", accept.to_owned());
+        }
+
+        let scope = format!("repository:{}:pull", repository);
+        let response = upstream.send(method, path, &scope, headers, None)?;
+        let status = response.status().as_u16();
+        match status == 404 || response.status().is_success() {
+            true => Ok(response),
+            false => Err(errors::Server::GenericError(format!("upstream returned {}", status)).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use serde_json;
+
+    use super::*;
+
+    /// An upstream serving _content_ at _path_, counting the requests.
+    fn upstream(path: String, content: Vec<u8>) -> (String, Arc<AtomicUsize>) {
+        let digest = manifests::digest_of(&content).to_string();
+        claiming(path, content, Some(digest))
+    }
+
+    /// An upstream serving _content_ at _path_ as having the digest
+    /// _claimed_, if any.
+    fn claiming(
+        path: String,
+        content: Vec<u8>,
+        claimed: Option<String>,
+    ) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counted = requests.clone();
+        thread::spawn(move || for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut request = String::new();
+            {
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut request).unwrap();
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+            }
+            counted.fetch_add(1, Ordering::SeqCst);
+            let found = request.split(' ').nth(1) == Some(path.as_str());
+            let body: &[u8] = if found { &content } else { b"" };
+            let digest = match claimed {
+                Some(ref claimed) => format!("Docker-Content-Digest: {}\r\n", claimed),
+                None => String::new(),
+            };
+            let head = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\n{}\
+                 Content-Type: application/vnd.oci.image.manifest.v1+json\r\n\
+                 Connection: close\r\n\r\n",
+                if found { "200 OK" } else { "404 Not Found" },
+                body.len(),
+                digest
+            );
+            let _ = stream.write_all(head.as_bytes());
+            let _ = stream.write_all(body);
+        });
+        (url, requests)
+    }
+
+    /// A proxy caching in a new temporary data directory.
+    fn proxy(url: &str) -> Proxy {
+        let root = env::temp_dir().join(format!("lycaon-proxy-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("scratch")).unwrap();
+        fs::create_dir_all(root.join("layers")).unwrap();
+        let config = serde_json::from_value(json!({ "url": url, "ttl": 3600 })).unwrap();
+        Proxy::new(Some(config), DataDir::open(&root).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn manifests_are_fetched_once() {
+        let repository = String::from("library/alpine");
+        let content = br#"{"schemaVersion": 2, "layers": []}"#.to_vec();
+        let path = format!("/v2/{}/manifests/latest", repository);
+        let (url, requests) = upstream(path, content.clone());
+        let proxy = proxy(&url);
+
+        let fetched = proxy.manifest(&repository, "latest").unwrap().unwrap();
+        assert_eq!(fetched.content, content);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let cached = proxy.manifest(&repository, "latest").unwrap().unwrap();
+        assert_eq!(cached.digest, fetched.digest);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert!(proxy.manifest(&repository, "missing").is_err());
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+        fs::remove_dir_all(proxy.data.root()).unwrap();
+    }
+
+    #[test]
+    fn manifests_are_stored_under_their_digest() {
+        let repository = String::from("library/alpine");
+        let content = br#"{"schemaVersion": 2, "layers": []}"#.to_vec();
+        let path = format!("/v2/{}/manifests/latest", repository);
+
+        let (url, _) = claiming(path.clone(), content.clone(), None);
+        let proxy = proxy(&url);
+        let fetched = proxy.manifest(&repository, "latest").unwrap().unwrap();
+        assert_eq!(fetched.digest, manifests::digest_of(&content).to_string());
+        fs::remove_dir_all(proxy.data.root()).unwrap();
+
+        let wrong = manifests::digest_of(b"something else").to_string();
+        let (url, _) = claiming(path, content, Some(wrong));
+        let proxy = proxy(&url);
+        assert!(proxy.manifest(&repository, "latest").is_err());
+        assert!(manifests::get(&proxy.data, &repository, "latest").is_none());
+        fs::remove_dir_all(proxy.data.root()).unwrap();
+    }
+
+    #[test]
+    fn blobs_are_fetched_once() {
+        let content = format!("layer {}", Uuid::new_v4()).into_bytes();
+        let digest = manifests::digest_of(&content);
+        let (url, requests) = upstream(format!("/v2/library/alpine/blobs/{}", digest), content);
+        let proxy = proxy(&url);
+
+        assert!(proxy.blob("library/alpine", &digest).unwrap());
+        assert!(proxy.data.has_blob("library/alpine", &digest));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        assert!(proxy.blob("library/alpine", &digest).unwrap());
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        fs::remove_dir_all(proxy.data.root()).unwrap();
+    }
+}
@@ -0,0 +1,83 @@
+//! Parsing of `WWW-Authenticate: Bearer ...` challenges.
+
+use std::str::FromStr;
+
+use failure::Error;
+
+use errors;
+
+/// Where to get a token from, as sent by a registry rejecting a request.
+#[derive(Debug, PartialEq)]
+pub struct Challenge {
+    pub realm: String,
+    pub service: Option<String>,
+    pub scope: Option<String>,
+}
+
+impl FromStr for Challenge {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Challenge, Error> {
+        let s = s.trim();
+        if !s.starts_with("Bearer ") {
+            return Err(errors::Server::Invalid("challenge").into());
+        }
+
+        let mut realm = None;
+        let mut service = None;
+        let mut scope = None;
+        let mut rest = &s["Bearer ".len()..];
+        while let Some(eq) = rest.find('=') {
+            let key = rest[..eq].trim().trim_left_matches(',').trim();
+            let value_start = &rest[eq + 1..];
+            // values are quoted and may contain commas, e.g. in a scope
+            let (value, remainder) = match value_start.starts_with('"') {
+                true => {
+                    let end = value_start[1..]
+                        .find('"')
+                        .ok_or(errors::Server::Invalid("challenge"))?;
+                    (&value_start[1..end + 1], &value_start[end + 2..])
+                }
+                false => {
+                    let end = value_start.find(',').unwrap_or(value_start.len());
+                    (&value_start[..end], &value_start[end..])
+                }
+            };
+            match key {
+                "realm" => realm = Some(value.to_owned()),
+                "service" => service = Some(value.to_owned()),
+                "scope" => scope = Some(value.to_owned()),
+                _ => {}
+            }
+            rest = remainder;
+        }
+
+        Ok(Challenge {
+            realm: realm.ok_or(errors::Server::Invalid("challenge"))?,
+            service,
+            scope,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Challenge;
+
+    #[test]
+    fn parse_bearer_challenge() {
+        let challenge: Challenge = "Bearer realm=\"http://localhost:8003/token\",service=\"lycaon\",\
+                                    scope=\"repository:library/ubuntu:pull,push\""
+            .parse()
+            .unwrap();
+        assert_eq!(challenge.realm, "http://localhost:8003/token");
+        assert_eq!(challenge.service, Some(String::from("lycaon")));
+        assert_eq!(
+            challenge.scope,
+            Some(String::from("repository:library/ubuntu:pull,push"))
+        );
+
+        assert!("Basic realm=\"x\"".parse::<Challenge>().is_err());
+        assert!("Bearer service=\"x\"".parse::<Challenge>().is_err());
+    }
+}
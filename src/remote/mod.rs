@@ -0,0 +1,157 @@
+//! Client for the v2 API of another registry.
+//!
+//! Used to pull from the upstream of a pull-through cache and to push
+//! to replication targets. Registries using token authentication answer
+//! with a `WWW-Authenticate: Bearer ...` challenge; a token is then
+//! fetched from the realm it names, cached per scope and the request
+//! retried once.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use failure::Error;
+use reqwest;
+use reqwest::header::Headers;
+
+use errors;
+
+mod challenge;
+
+use self::challenge::Challenge;
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    #[serde(default)]
+    token: String,
+    #[serde(default)]
+    access_token: String,
+}
+
+/// A request body that can be sent again after an authentication
+/// challenge.
+#[derive(Debug)]
+pub enum Payload {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+impl Payload {
+    fn body(&self) -> Result<reqwest::Body, Error> {
+        match *self {
+            Payload::Bytes(ref bytes) => Ok(bytes.clone().into()),
+            Payload::File(ref path) => Ok(File::open(path)?.into()),
+        }
+    }
+}
+
+pub struct Remote {
+    url: String,
+    username: Option<String>,
+    password: Option<String>,
+    client: reqwest::Client,
+    /// Bearer tokens handed out by the registry, by scope
+    tokens: Mutex<HashMap<String, String>>,
+}
+
+impl Remote {
+    pub fn new(
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+        timeout: u64,
+    ) -> Result<Remote, Error> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout))
+            .build()?;
+        Ok(Remote {
+            url: url.trim_right_matches('/').to_owned(),
+            username,
+            password,
+            client,
+            tokens: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends a request for _path_, relative to the registry or an
+    /// absolute URL such as an upload location. _scope_ is the token
+    /// scope asked for if the registry wants one, e.g.
+    /// `repository:library/ubuntu:pull`.
+    pub fn send(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        scope: &str,
+        headers: Headers,
+        body: Option<&Payload>,
+    ) -> Result<reqwest::Response, Error> {
+        let url = match path.starts_with("http://") || path.starts_with("https://") {
+            true => path.to_owned(),
+            false => format!("{}{}", self.url, path),
+        };
+
+        let mut retried = false;
+        loop {
+            let mut headers = headers.clone();
+            if let Some(token) = self.tokens.lock().unwrap().get(scope) {
+                headers.set_raw("Authorization", format!("Bearer {}", token));
+            }
+
+            let mut request = self.client.request(method.clone(), &url);
+            request.headers(headers);
+            if let Some(body) = body {
+                request.body(body.body()?);
+            }
+            let response = request.send()?;
+            debug!("{} {}: {}", method, url, response.status());
+
+            if response.status().as_u16() == 401 && !retried {
+                let challenge = header(&response, "WWW-Authenticate")
+                    .and_then(|value| value.parse::<Challenge>().ok())
+                    .ok_or(errors::Client::UNAUTHORIZED)?;
+                let token = self.token(&challenge, scope)?;
+                self.tokens.lock().unwrap().insert(scope.to_owned(), token);
+                retried = true;
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// Fetches a token from the registry's token server.
+    fn token(&self, challenge: &Challenge, scope: &str) -> Result<String, Error> {
+        let mut url = format!("{}?scope={}", challenge.realm, scope);
+        if let Some(ref service) = challenge.service {
+            url = format!("{}&service={}", url, service);
+        }
+
+        let mut request = self.client.get(&url);
+        if let Some(ref username) = self.username {
+            request.basic_auth(username.clone(), self.password.clone());
+        }
+        let mut response = request.send()?;
+        if !response.status().is_success() {
+            return Err(errors::Client::UNAUTHORIZED.into());
+        }
+        let token: TokenResponse = response.json()?;
+        match token.token.is_empty() {
+            true => Ok(token.access_token),
+            false => Ok(token.token),
+        }
+    }
+}
+
+/// The value of header _name_ in _response_, if it is valid UTF-8.
+pub fn header(response: &reqwest::Response, name: &str) -> Option<String> {
+    response
+        .headers()
+        .get_raw(name)
+        .and_then(|raw| raw.one())
+        .and_then(|value| String::from_utf8(value.to_vec()).ok())
+}
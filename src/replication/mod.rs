@@ -0,0 +1,201 @@
+//! Push replication to other registries.
+//!
+//! Every rule names a target registry and the repositories and tags
+//! to copy there. When a matching tag is pushed a job is queued for
+//! the rule's worker, which pushes the blobs the target is missing
+//! followed by the manifest. Failed jobs are retried with exponential
+//! backoff. A backfill queues a job for every existing tag matching a
+//! rule, for example after adding a new target.
+
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Mutex};
+
+use chrono::Utc;
+use failure::Error;
+use regex::Regex;
+use uuid::Uuid;
+
+use auth::acl;
+use config::{ReplicationConfig, ReplicationRule};
+use datadir::DataDir;
+use errors;
+use state::manifests;
+
+mod worker;
+
+/// Finished jobs kept per rule for the admin endpoint
+const MAX_FINISHED: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Copying of a single tag to a rule's target.
+#[derive(Clone, Debug, Serialize)]
+pub struct Job {
+    pub id: String,
+    pub rule: String,
+    pub repository: String,
+    pub tag: String,
+    pub state: JobState,
+    pub attempts: u32,
+    #[serde(skip_serializing)]
+    pub next_attempt: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub created: String,
+    pub updated: String,
+}
+
+impl Job {
+    fn new(rule: &str, repository: &str, tag: &str) -> Job {
+        let now = Utc::now().to_rfc3339();
+        Job {
+            id: Uuid::new_v4().to_string(),
+            rule: rule.to_owned(),
+            repository: repository.to_owned(),
+            tag: tag.to_owned(),
+            state: JobState::Queued,
+            attempts: 0,
+            next_attempt: 0,
+            error: None,
+            created: now.clone(),
+            updated: now,
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.state == JobState::Succeeded || self.state == JobState::Failed
+    }
+}
+
+pub type Jobs = Arc<Mutex<VecDeque<Job>>>;
+
+struct Rule {
+    config: ReplicationRule,
+    tags: Regex,
+    jobs: Jobs,
+    wake: Mutex<mpsc::Sender<()>>,
+}
+
+impl Rule {
+    fn accepts(&self, repository: &str, tag: &str) -> bool {
+        let repositories = self.config.repositories();
+        (repositories.is_empty() || repositories.iter().any(|p| acl::matches(p, repository)))
+            && self.tags.is_match(tag)
+    }
+
+    /// Queues a job unless one for the same tag is already waiting.
+    fn queue(&self, repository: &str, tag: &str) -> bool {
+        let mut jobs = self.jobs.lock().unwrap();
+        let waiting = jobs.iter().any(|job| {
+            job.state == JobState::Queued && job.repository == repository && job.tag == tag
+        });
+        if waiting {
+            return false;
+        }
+
+        jobs.push_back(Job::new(&self.config.name(), repository, tag));
+        while jobs.iter().filter(|job| job.finished()).count() > MAX_FINISHED {
+            match jobs.iter().position(|job| job.finished()) {
+                Some(oldest) => {
+                    jobs.remove(oldest);
+                }
+                None => break,
+            }
+        }
+        let _ = self.wake.lock().unwrap().send(());
+        true
+    }
+}
+
+/// Managed state queueing replication jobs.
+pub struct Replicator {
+    rules: Vec<Rule>,
+    data: Option<DataDir>,
+}
+
+impl Replicator {
+    /// Replicates what's stored in _data_.
+    pub fn new(config: Option<ReplicationConfig>, data: DataDir) -> Result<Replicator, Error> {
+        let config = match config {
+            Some(config) => config,
+            None => return Ok(Replicator::disabled()),
+        };
+
+        let mut rules = Vec::new();
+        for rule in config.rules() {
+            // anchored, a tag has to match the whole expression
+            let tags = Regex::new(&format!("^(?:{})$", rule.tags()))?;
+            let jobs = Arc::new(Mutex::new(VecDeque::new()));
+            let (wake, woken) = mpsc::channel();
+            worker::spawn_worker(rule.clone(), jobs.clone(), woken, data.clone())?;
+            info!("Replicating to {} ({})", rule.name(), rule.url());
+            rules.push(Rule {
+                config: rule,
+                tags,
+                jobs,
+                wake: Mutex::new(wake),
+            });
+        }
+
+        Ok(Replicator {
+            rules,
+            data: Some(data),
+        })
+    }
+
+    pub fn disabled() -> Replicator {
+        Replicator {
+            rules: Vec::new(),
+            data: None,
+        }
+    }
+
+    /// Queues a job for every rule matching the pushed tag.
+    pub fn tag_pushed(&self, repository: &str, tag: &str) {
+        for rule in self.rules.iter().filter(|r| r.accepts(repository, tag)) {
+            debug!("Replicating {}:{} to {}", repository, tag, rule.config.name());
+            rule.queue(repository, tag);
+        }
+    }
+
+    /// Queues a job for every stored tag matching _rule_, or every rule.
+    /// Returns the number of jobs queued.
+    pub fn backfill(&self, rule: Option<&str>) -> Result<usize, Error> {
+        let rules: Vec<&Rule> = self.rules
+            .iter()
+            .filter(|r| rule.map(|name| r.config.name() == name).unwrap_or(true))
+            .collect();
+        let data = match self.data {
+            Some(ref data) if !rules.is_empty() => data,
+            _ => return Err(errors::Server::Invalid("replication rule").into()),
+        };
+
+        let mut queued = 0;
+        for repository in data.repositories() {
+            for tag in manifests::tags(data, &repository) {
+                for rule in rules.iter().filter(|r| r.accepts(&repository, &tag)) {
+                    if rule.queue(&repository, &tag) {
+                        queued += 1;
+                    }
+                }
+            }
+        }
+        info!("Backfill queued {} replication jobs", queued);
+        Ok(queued)
+    }
+
+    /// Current and recently finished jobs of every rule.
+    pub fn jobs(&self) -> Vec<Job> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.jobs.lock().unwrap().clone().into_iter())
+            .collect()
+    }
+}
@@ -0,0 +1,194 @@
+//! Worker thread pushing the queued jobs of a rule.
+
+use std::cmp;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use chrono::Utc;
+use failure::Error;
+use reqwest::Method;
+use reqwest::header::Headers;
+
+use config::ReplicationRule;
+use datadir::DataDir;
+use errors;
+use manifest::parse;
+use remote::{self, Payload, Remote};
+use replication::{JobState, Jobs};
+use state::manifests::{self, Manifest};
+use types::Digest;
+
+/// Starts the thread working through _jobs_. It wakes up whenever
+/// something is sent on _woken_, and once a second to retry.
+pub fn spawn_worker(
+    rule: ReplicationRule,
+    jobs: Jobs,
+    woken: Receiver<()>,
+    data: DataDir,
+) -> Result<(), Error> {
+    let target = Remote::new(rule.url(), rule.username(), rule.password(), rule.timeout())?;
+
+    thread::Builder::new()
+        .name(format!("replicate-{}", rule.name()))
+        .spawn(move || loop {
+            process(&target, &data, &rule, &jobs);
+            match woken.recv_timeout(Duration::from_secs(1)) {
+                Ok(()) | Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => thread::sleep(Duration::from_secs(1)),
+            }
+        })?;
+    Ok(())
+}
+
+/// Runs every due job, one at a time and oldest first.
+fn process(target: &Remote, data: &DataDir, rule: &ReplicationRule, jobs: &Jobs) {
+    loop {
+        let job = {
+            let mut jobs = jobs.lock().unwrap();
+            let now = Utc::now().timestamp();
+            match jobs
+                .iter_mut()
+                .find(|job| job.state == JobState::Queued && job.next_attempt <= now)
+            {
+                Some(job) => {
+                    job.state = JobState::Running;
+                    job.updated = Utc::now().to_rfc3339();
+                    job.clone()
+                }
+                None => return,
+            }
+        };
+
+        let result = replicate(target, data, &job.repository, &job.tag);
+
+        let mut jobs = jobs.lock().unwrap();
+        let job = match jobs.iter_mut().find(|j| j.id == job.id) {
+            Some(job) => job,
+            None => continue,
+        };
+        job.attempts += 1;
+        job.updated = Utc::now().to_rfc3339();
+        match result {
+            Ok(()) => {
+                info!("Replicated {}:{} to {}", job.repository, job.tag, rule.name());
+                job.state = JobState::Succeeded;
+                job.error = None;
+            }
+            Err(e) => {
+                job.error = Some(e.to_string());
+                if job.attempts >= rule.max_retries() {
+                    warn!(
+                        "Giving up replicating {}:{} to {} after {} attempts: {}",
+                        job.repository,
+                        job.tag,
+                        rule.name(),
+                        job.attempts,
+                        e
+                    );
+                    job.state = JobState::Failed;
+                } else {
+                    let delay = backoff(rule, job.attempts);
+                    info!(
+                        "Replicating {}:{} to {} failed ({}), retry {} in {}s",
+                        job.repository,
+                        job.tag,
+                        rule.name(),
+                        e,
+                        job.attempts,
+                        delay
+                    );
+                    job.state = JobState::Queued;
+                    job.next_attempt = Utc::now().timestamp() + delay;
+                }
+            }
+        }
+    }
+}
+
+/// Exponential backoff, doubling with every attempt up to the maximum.
+fn backoff(rule: &ReplicationRule, attempts: u32) -> i64 {
+    let factor = 1i64 << cmp::min(attempts - 1, 20);
+    cmp::min(rule.backoff() as i64 * factor, rule.max_backoff() as i64)
+}
+
+/// Pushes _tag_ of _repository_ as it is stored now.
+fn replicate(target: &Remote, data: &DataDir, repository: &str, tag: &str) -> Result<(), Error> {
+    let manifest = manifests::get(data, repository, tag).ok_or(errors::Client::MANIFEST_UNKNOWN)?;
+    push_manifest(target, data, repository, tag, &manifest)
+}
+
+/// Pushes what _manifest_ references before the manifest itself, as
+/// registries refuse manifests referencing unknown content.
+fn push_manifest(
+    target: &Remote,
+    data: &DataDir,
+    repository: &str,
+    reference: &str,
+    manifest: &Manifest,
+) -> Result<(), Error> {
+    let media_type = match manifest.media_type.is_empty() {
+        true => None,
+        false => Some(manifest.media_type.as_str()),
+    };
+    let parsed = parse(media_type, &manifest.content)?;
+    for digest in &parsed.manifests {
+        let digest = digest.to_string();
+        let child = manifests::get(data, repository, &digest)
+            .ok_or(errors::Client::MANIFEST_BLOB_UNKNOWN)?;
+        push_manifest(target, data, repository, &digest, &child)?;
+    }
+    for digest in &parsed.blobs {
+        push_blob(target, data, repository, digest)?;
+    }
+
+    let mut headers = Headers::new();
+    headers.set_raw("Content-Type", parsed.media_type.clone());
+    let path = format!("/v2/{}/manifests/{}", repository, reference);
+    let payload = Payload::Bytes(manifest.content.clone());
+    let response = target.send(Method::Put, &path, &scope(repository), headers, Some(&payload))?;
+    expect(&response, "manifest upload")
+}
+
+/// Monolithic upload of a blob, unless the target already has it.
+fn push_blob(target: &Remote, data: &DataDir, repository: &str, digest: &Digest) -> Result<(), Error> {
+    let scope = scope(repository);
+    let path = format!("/v2/{}/blobs/{}", repository, digest);
+    let response = target.send(Method::Head, &path, &scope, Headers::new(), None)?;
+    if response.status().is_success() {
+        debug!("Target has {}@{}", repository, digest);
+        return Ok(());
+    }
+
+    let layer = data.blob(digest);
+    if !layer.exists() {
+        return Err(errors::Client::BLOB_UNKNOWN.into());
+    }
+
+    let path = format!("/v2/{}/blobs/uploads/", repository);
+    let response = target.send(Method::Post, &path, &scope, Headers::new(), None)?;
+    expect(&response, "upload start")?;
+    let location = remote::header(&response, "Location")
+        .ok_or(errors::Server::GenericError(String::from("upload without location")))?;
+
+    let separator = match location.contains('?') {
+        true => '&',
+        false => '?',
+    };
+    let location = format!("{}{}digest={}", location, separator, digest);
+    let mut headers = Headers::new();
+    headers.set_raw("Content-Type", "application/octet-stream");
+    let response = target.send(Method::Put, &location, &scope, headers, Some(&Payload::File(layer)))?;
+    expect(&response, "blob upload")
+}
+
+fn scope(repository: &str) -> String {
+    format!("repository:{}:pull,push", repository)
+}
+
+fn expect(response: &::reqwest::Response, what: &str) -> Result<(), Error> {
+    match response.status().is_success() {
+        true => Ok(()),
+        false => Err(errors::Server::GenericError(format!("{} returned {}", what, response.status())).into()),
+    }
+}
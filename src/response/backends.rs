@@ -0,0 +1,37 @@
+use rocket::State;
+use rocket::http::Status;
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use config::BackendHandler;
+
+use response::json_response;
+
+#[derive(Debug, Serialize)]
+pub struct Backend {
+    address: String,
+    healthy: bool,
+}
+
+/// The backends this frontend balances over and their health.
+#[derive(Debug, Serialize)]
+pub struct Backends {
+    backends: Vec<Backend>,
+}
+
+impl Backends {
+    pub fn get(handler: State<BackendHandler>) -> Backends {
+        let backends = handler
+            .status()
+            .into_iter()
+            .map(|(address, healthy)| Backend { address, healthy })
+            .collect();
+        Backends { backends }
+    }
+}
+
+impl<'r> Responder<'r> for Backends {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
@@ -0,0 +1,96 @@
+use std::fs::File;
+
+use failure::Error;
+use rocket::State;
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use datadir::DataDir;
+use errors;
+use proxy::Proxy;
+use types::Digest;
+
+/// The content of a blob from the layer store.
+#[derive(Debug)]
+pub struct Blob {
+    digest: Digest,
+    file: File,
+}
+
+impl Blob {
+    /// Opens the blob if it's linked into _repository_, fetching it from
+    /// the upstream registry first if this is a pull-through cache.
+    pub fn handle(
+        proxy: State<Proxy>,
+        data: &DataDir,
+        repository: &str,
+        digest: &Digest,
+    ) -> Result<Blob, Error> {
+        if !proxy.blob(repository, digest)? || !data.has_blob(repository, digest) {
+            return Err(errors::Client::BLOB_UNKNOWN.into());
+        }
+        let file = File::open(data.blob(digest))
+            .map_err(|_| errors::Client::BLOB_UNKNOWN)?;
+        Ok(Blob {
+            digest: digest.clone(),
+            file,
+        })
+    }
+}
+
+/// A blob no longer linked into a repository.
+#[derive(Debug)]
+pub struct DeletedBlob {
+    pub digest: Digest,
+}
+
+impl DeletedBlob {
+    /// Unlinks the blob from _repository_, other repositories keep it.
+    pub fn handle(data: &DataDir, repository: &str, digest: &Digest) -> Result<DeletedBlob, Error> {
+        if !data.unlink_blob(repository, digest)? {
+            return Err(errors::Client::BLOB_UNKNOWN.into());
+        }
+        debug!("Unlinked {} from {}", digest, repository);
+        Ok(DeletedBlob {
+            digest: digest.clone(),
+        })
+    }
+}
+
+impl<'r> Responder<'r> for DeletedBlob {
+    fn respond_to(self, _req: &Request) -> Result<Response<'r>, Status> {
+        Response::build()
+            .status(Status::Accepted)
+            .header(Header::new("Docker-Content-Digest", self.digest.to_string()))
+            .ok()
+    }
+}
+
+impl<'r> Responder<'r> for Blob {
+    fn respond_to(self, _req: &Request) -> Result<Response<'r>, Status> {
+        Response::build()
+            .header(ContentType::Binary)
+            .header(Header::new("Docker-Content-Digest", self.digest.to_string()))
+            .sized_body(self.file)
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+    use response::blob::DeletedBlob;
+
+    use response::test_helper::test_route;
+
+    #[test]
+    fn blob_deleted() {
+        let digest = "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+        let response = test_route(DeletedBlob {
+            digest: digest.parse().unwrap(),
+        });
+        assert_eq!(response.status(), Status::Accepted);
+        assert_eq!(response.headers().get_one("Docker-Content-Digest"), Some(digest));
+    }
+}
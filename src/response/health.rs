@@ -0,0 +1,71 @@
+use std::io::Cursor;
+
+use rocket::State;
+use rocket::http::{ContentType, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+use serde_json;
+
+use backend::health;
+use config::BackendHandler;
+use datadir::DataDir;
+use response::json_response;
+
+/// The process is up and answering requests.
+#[derive(Debug, Serialize)]
+pub struct Alive {
+    status: &'static str,
+}
+
+impl Alive {
+    pub fn get() -> Alive {
+        Alive { status: "ok" }
+    }
+}
+
+impl<'r> Responder<'r> for Alive {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
+
+/// Whether this frontend can take requests, and why not.
+///
+/// A backend is ready once its storage is writable and it synced with
+/// its peers, see `backend::health`.
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    ready: bool,
+    problems: Vec<String>,
+}
+
+impl Readiness {
+    pub fn check(handler: State<BackendHandler>, data: &DataDir) -> Readiness {
+        let mut problems = Vec::new();
+        if !handler.status().iter().any(|&(_, healthy)| healthy) {
+            problems.push(String::from("no backend is ready"));
+        }
+        if let Err(e) = health::writable(&data.scratch()) {
+            problems.push(format!("storage isn't writable: {}", e));
+        }
+        Readiness {
+            ready: problems.is_empty(),
+            problems,
+        }
+    }
+}
+
+impl<'r> Responder<'r> for Readiness {
+    fn respond_to(self, _req: &Request) -> Result<Response<'r>, Status> {
+        let status = match self.ready {
+            true => Status::Ok,
+            false => Status::ServiceUnavailable,
+        };
+        let body = serde_json::to_string(&self).map_err(|_| Status::InternalServerError)?;
+        Response::build()
+            .status(status)
+            .header(ContentType::JSON)
+            .sized_body(Cursor::new(body))
+            .ok()
+    }
+}
@@ -9,7 +9,6 @@ use config;
 use types::Layer;
 
 use grpc::backend;
-use util;
 
 #[derive(Debug, Clone)]
 pub enum LayerExists {
@@ -26,12 +25,9 @@ impl LayerExists {
 
         let mut proto_layer = backend::Layer::new();
         proto_layer.set_name(layer.name);
-        proto_layer.set_repo(layer.repo);
         proto_layer.set_digest(layer.digest.clone());
 
-        let reply = backend.layer_exists(proto_layer).expect(
-            "layerexists RPC failed",
-        );
+        let reply = backend.layer_exists(&proto_layer)?;
         debug!("Client received: {:?}", reply);
 
         match reply.get_success() {
@@ -41,7 +37,7 @@ impl LayerExists {
                     length: reply.get_length(),
                 })
             }
-            false => Err(util::std_err("blob doesn't exist")),
+            false => Ok(LayerExists::False),
         }
     }
 }
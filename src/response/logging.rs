@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use failure::Error;
+use rocket::http::Status;
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use controller::logging::LogLevelParams;
+use errors;
+use logging;
+
+use response::json_response;
+
+/// The current log levels, the default and any per-module overrides.
+#[derive(Debug, Serialize)]
+pub struct LogLevels {
+    default: String,
+    modules: BTreeMap<String, String>,
+}
+
+impl LogLevels {
+    pub fn get() -> LogLevels {
+        let (default, modules) = logging::levels();
+        LogLevels { default, modules }
+    }
+
+    pub fn handle(params: LogLevelParams) -> Result<LogLevels, Error> {
+        logging::set_level(params.module.as_ref().map(|m| m.as_str()), &params.level)
+            .map_err(|_| errors::Client::UNSUPPORTED)?;
+        Ok(LogLevels::get())
+    }
+}
+
+impl<'r> Responder<'r> for LogLevels {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
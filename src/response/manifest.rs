@@ -0,0 +1,247 @@
+use std::io::{Cursor, Read};
+
+use failure::Error;
+use rocket::State;
+use rocket::data::Data;
+use rocket::http::{ContentType, Header, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use backend::events;
+use config;
+use controller;
+use datadir::DataDir;
+use errors;
+use grpc;
+use grpc::backend::EventKind;
+use manifest;
+use proxy::Proxy;
+use state::manifests::{self, Manifest};
+use types::Digest;
+
+/// Manifests bigger than this are refused
+const MAX_MANIFEST_SIZE: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum ManifestResponse {
+    Manifest(Manifest),
+    Created {
+        repository: String,
+        digest: String,
+        subject: Option<String>,
+    },
+    Deleted,
+}
+
+impl ManifestResponse {
+    /// The manifest _reference_ points to, from the upstream registry
+    /// if this is a pull-through cache and it isn't cached yet.
+    ///
+    /// A manifest in a format the client doesn't accept is unknown to it.
+    pub fn get(
+        proxy: State<Proxy>,
+        accepted: &manifest::Accepted,
+        repository: &str,
+        reference: &str,
+    ) -> Result<ManifestResponse, Error> {
+        reference_digest(reference)?;
+        let mut found = proxy
+            .manifest(repository, reference)?
+            .ok_or(errors::Client::MANIFEST_UNKNOWN)?;
+        if found.media_type.is_empty() {
+            found.media_type = manifest::parse(None, &found.content)?.media_type;
+        }
+        if !accepted.accepts(&found.media_type) {
+            debug!("Client doesn't accept {} for {}", found.media_type, reference);
+            return Err(errors::Client::MANIFEST_UNKNOWN.into());
+        }
+        Ok(ManifestResponse::Manifest(found))
+    }
+
+    pub fn put(
+        handler: State<config::BackendHandler>,
+        store: &DataDir,
+        repository: &str,
+        reference: &str,
+        content_type: Option<ContentType>,
+        data: Data,
+    ) -> Result<ManifestResponse, Error> {
+        let mut content = Vec::new();
+        data.open()
+            .take(MAX_MANIFEST_SIZE + 1)
+            .read_to_end(&mut content)?;
+        if content.is_empty() || content.len() as u64 > MAX_MANIFEST_SIZE {
+            return Err(errors::Client::MANIFEST_INVALID.into());
+        }
+        if let Some(expected) = reference_digest(reference)? {
+            if Digest::of(expected.algorithm(), &content) != expected {
+                return Err(errors::Client::DIGEST_INVALID.into());
+            }
+        }
+
+        let content_type = content_type.map(|ct| ct.to_string());
+        let parsed = manifest::parse(content_type.as_ref().map(|ct| ct.as_str()), &content)?;
+        manifest::validate(store, repository, &parsed)?;
+
+        let size = content.len() as u64;
+        let stored = manifests::put(store, repository, reference, &parsed.media_type, content)?;
+        if let Some(ref subject) = parsed.subject {
+            let mut referrer = grpc::backend::Referrer::new();
+            referrer.set_repository(repository.to_owned());
+            referrer.set_subject(subject.to_string());
+            referrer.set_digest(stored.digest.clone());
+            referrer.set_media_type(parsed.media_type.clone());
+            referrer.set_artifact_type(parsed.artifact_type.clone());
+            referrer.set_size(size);
+            referrer.set_annotations(parsed.annotations.clone());
+            let response = handler
+                .backend()
+                .add_referrer(&referrer)?;
+            if !response.get_success() {
+                return Err(errors::Server::GenericError(response.get_text().to_owned()).into());
+            }
+        }
+        if !manifests::is_digest(reference) {
+            let mut event = events::event(EventKind::TAG_UPDATED, repository);
+            event.set_tag(reference.to_owned());
+            event.set_digest(stored.digest.clone());
+            publish(&handler, event);
+        }
+
+        Ok(ManifestResponse::Created {
+            repository: repository.to_owned(),
+            digest: stored.digest,
+            subject: parsed.subject.map(|subject| subject.to_string()),
+        })
+    }
+
+    /// Deletes a manifest by digest, along with its tags and, in turn,
+    /// everything referring to it.
+    pub fn delete(
+        handler: State<config::BackendHandler>,
+        store: &DataDir,
+        repository: &str,
+        reference: &str,
+    ) -> Result<ManifestResponse, Error> {
+        let reference = match reference_digest(reference)? {
+            Some(digest) => digest.to_string(),
+            None => return Err(errors::Client::UNSUPPORTED.into()),
+        };
+        if manifests::get(store, repository, &reference).is_none() {
+            return Err(errors::Client::MANIFEST_UNKNOWN.into());
+        }
+
+        let mut pending = vec![reference];
+        while let Some(digest) = pending.pop() {
+            if let Some(tags) = manifests::delete(store, repository, &digest)? {
+                debug!("Deleted {}@{} and tags {:?}", repository, digest, tags);
+                let mut event = events::event(EventKind::MANIFEST_DELETED, repository);
+                event.set_digest(digest.clone());
+                publish(&handler, event);
+            }
+
+            let mut req = grpc::backend::ReferrersRequest::new();
+            req.set_repository(repository.to_owned());
+            req.set_subject(digest);
+            let referrers = handler
+                .backend()
+                .remove_referrers(&req)?;
+            pending.extend(
+                referrers
+                    .get_referrers()
+                    .iter()
+                    .map(|referrer| referrer.get_digest().to_owned()),
+            );
+        }
+        Ok(ManifestResponse::Deleted)
+    }
+}
+
+/// The digest _reference_ is, `None` if it's a tag. Either is checked
+/// before it's used as a path.
+fn reference_digest(reference: &str) -> Result<Option<Digest>, errors::Client> {
+    match manifests::is_digest(reference) {
+        true => controller::digest::parse(reference).map(Some),
+        false => controller::tag::parse(reference).map(|_| None),
+    }
+}
+
+/// Events are informational, a failure to publish one doesn't fail
+/// the request.
+fn publish(handler: &config::BackendHandler, event: grpc::backend::Event) {
+    let backend = handler.backend();
+    if let Err(e) = backend.publish(&event) {
+        warn!("Publishing {:?}: {}", event.get_kind(), e);
+    }
+}
+
+impl<'r> Responder<'r> for ManifestResponse {
+    fn respond_to(self, _req: &Request) -> Result<Response<'r>, Status> {
+        match self {
+            ManifestResponse::Manifest(manifest) => {
+                let content_type = manifest
+                    .media_type
+                    .parse::<ContentType>()
+                    .unwrap_or(ContentType::JSON);
+                Response::build()
+                    .header(content_type)
+                    .header(Header::new("Docker-Content-Digest", manifest.digest))
+                    .sized_body(Cursor::new(manifest.content))
+                    .ok()
+            }
+            ManifestResponse::Created {
+                repository,
+                digest,
+                subject,
+            } => {
+                let location = format!("/v2/{}/manifests/{}", repository, digest);
+                let mut response = Response::build();
+                response
+                    .status(Status::Created)
+                    .header(Header::new("Location", location))
+                    .header(Header::new("Docker-Content-Digest", digest));
+                // tells clients the registry supports the referrers API
+                if let Some(subject) = subject {
+                    response.header(Header::new("OCI-Subject", subject));
+                }
+                response.ok()
+            }
+            ManifestResponse::Deleted => Response::build().status(Status::Accepted).ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+    use response::manifest::{reference_digest, ManifestResponse};
+    use state::manifests::Manifest;
+
+    use response::test_helper::test_route;
+
+    #[test]
+    fn manifest_headers() {
+        let response = test_route(ManifestResponse::Manifest(Manifest {
+            media_type: String::from("application/vnd.docker.distribution.manifest.v2+json"),
+            digest: String::from("sha256:abc"),
+            content: b"{}".to_vec(),
+        }));
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("Docker-Content-Digest"),
+            Some("sha256:abc")
+        );
+        assert_eq!(
+            response.headers().get_one("Content-Type"),
+            Some("application/vnd.docker.distribution.manifest.v2+json")
+        );
+    }
+
+    #[test]
+    fn references_stay_in_the_repository() {
+        assert_eq!(reference_digest("latest").unwrap(), None);
+        assert!(reference_digest("..").is_err());
+        assert!(reference_digest("../../../evil").is_err());
+        assert!(reference_digest("sha256:../../evil").is_err());
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::Cursor;
+
+use failure::Error;
+use rocket::http::{ContentType, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use datadir::DataDir;
+use metrics;
+
+/// The current metrics in the Prometheus exposition format.
+#[derive(Debug)]
+pub struct Metrics {
+    content_type: String,
+    body: String,
+}
+
+impl Metrics {
+    pub fn handle(data: &DataDir) -> Result<Metrics, Error> {
+        let (content_type, body) = metrics::render(data)?;
+        Ok(Metrics { content_type, body })
+    }
+}
+
+impl<'r> Responder<'r> for Metrics {
+    fn respond_to(self, _: &Request) -> Result<Response<'r>, Status> {
+        let content_type = self.content_type.parse::<ContentType>().unwrap_or(ContentType::Plain);
+        Response::build()
+            .header(content_type)
+            .sized_body(Cursor::new(self.body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+    use response::metrics::Metrics;
+
+    use response::test_helper::test_route;
+
+    #[test]
+    fn metrics_text_format() {
+        let response = test_route(Metrics::handle().unwrap());
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
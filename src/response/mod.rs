@@ -9,10 +9,21 @@ use errors;
 
 /// Exporting all routes for the project
 pub mod admin;
+pub mod backends;
+pub mod blob;
 pub mod catalog;
 pub mod empty;
+pub mod health;
 pub mod html;
 pub mod layers;
+pub mod logging;
+pub mod manifest;
+pub mod metrics;
+pub mod referrers;
+pub mod replication;
+pub mod scrub;
+pub mod tags;
+pub mod token;
 pub mod uuid;
 pub mod uuidaccept;
 mod test_helper;
@@ -0,0 +1,120 @@
+use failure::Error;
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+use serde_json::{self, Value};
+
+use config;
+use grpc::backend::ReferrersRequest;
+use manifest;
+use types::Digest;
+
+use response::json_response;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    artifact_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    annotations: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImageIndex {
+    schema_version: u32,
+    media_type: &'static str,
+    manifests: Vec<Descriptor>,
+}
+
+/// The manifests referring to a subject, as an OCI image index.
+#[derive(Debug)]
+pub struct Referrers {
+    index: ImageIndex,
+    filtered: bool,
+}
+
+impl Referrers {
+    pub fn handle(
+        handler: State<config::BackendHandler>,
+        repository: &str,
+        subject: &Digest,
+        artifact_type: Option<String>,
+    ) -> Result<Referrers, Error> {
+        let mut req = ReferrersRequest::new();
+        req.set_repository(repository.to_owned());
+        req.set_subject(subject.to_string());
+        req.set_artifact_type(artifact_type.clone().unwrap_or_default());
+        let reply = handler
+            .backend()
+            .list_referrers(&req)?;
+
+        let manifests = reply
+            .get_referrers()
+            .iter()
+            .map(|referrer| Descriptor {
+                media_type: referrer.get_media_type().to_owned(),
+                digest: referrer.get_digest().to_owned(),
+                size: referrer.get_size(),
+                artifact_type: referrer.get_artifact_type().to_owned(),
+                annotations: serde_json::from_str(referrer.get_annotations()).ok(),
+            })
+            .collect();
+
+        Ok(Referrers {
+            index: ImageIndex {
+                schema_version: 2,
+                media_type: manifest::OCI_INDEX,
+                manifests,
+            },
+            filtered: artifact_type.is_some(),
+        })
+    }
+}
+
+impl<'r> Responder<'r> for Referrers {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        let mut response = Response::build_from(json_response(req, &self.index)?);
+        response.header(Header::new("Content-Type", manifest::OCI_INDEX));
+        if self.filtered {
+            response.header(Header::new("OCI-Filters-Applied", "artifactType"));
+        }
+        response.ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+    use response::referrers::{ImageIndex, Referrers};
+    use manifest;
+
+    use response::test_helper::test_route;
+
+    #[test]
+    fn referrers_index_headers() {
+        let referrers = Referrers {
+            index: ImageIndex {
+                schema_version: 2,
+                media_type: manifest::OCI_INDEX,
+                manifests: vec![],
+            },
+            filtered: true,
+        };
+        let response = test_route(referrers);
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(
+            response.headers().get_one("Content-Type"),
+            Some(manifest::OCI_INDEX)
+        );
+        assert_eq!(
+            response.headers().get_one("OCI-Filters-Applied"),
+            Some("artifactType")
+        );
+    }
+}
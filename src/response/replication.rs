@@ -0,0 +1,48 @@
+use failure::Error;
+use rocket::State;
+use rocket::http::Status;
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use replication::{Job, Replicator};
+
+use response::json_response;
+
+/// Queued, running and recently finished replication jobs.
+#[derive(Debug, Serialize)]
+pub struct ReplicationJobs {
+    jobs: Vec<Job>,
+}
+
+impl ReplicationJobs {
+    pub fn get(replicator: State<Replicator>) -> ReplicationJobs {
+        ReplicationJobs {
+            jobs: replicator.jobs(),
+        }
+    }
+}
+
+impl<'r> Responder<'r> for ReplicationJobs {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
+
+/// Number of jobs a backfill queued.
+#[derive(Debug, Serialize)]
+pub struct Backfill {
+    queued: usize,
+}
+
+impl Backfill {
+    pub fn handle(replicator: State<Replicator>, rule: Option<&str>) -> Result<Backfill, Error> {
+        let queued = replicator.backfill(rule)?;
+        Ok(Backfill { queued })
+    }
+}
+
+impl<'r> Responder<'r> for Backfill {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
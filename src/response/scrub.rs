@@ -0,0 +1,30 @@
+use rocket::State;
+use rocket::http::Status;
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use scrub::{Report, Scrubber};
+
+use response::json_response;
+
+/// Progress of the running scrub pass, or the outcome of the last one.
+#[derive(Debug, Serialize)]
+pub struct ScrubReport {
+    enabled: bool,
+    report: Report,
+}
+
+impl ScrubReport {
+    pub fn get(scrubber: State<Scrubber>) -> ScrubReport {
+        ScrubReport {
+            enabled: scrubber.enabled(),
+            report: scrubber.report(),
+        }
+    }
+}
+
+impl<'r> Responder<'r> for ScrubReport {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
@@ -0,0 +1,49 @@
+use rocket::http::Status;
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use datadir::DataDir;
+use errors;
+use response::json_response;
+use state::manifests;
+
+/// The tags of a repository, sorted.
+#[derive(Debug, Serialize)]
+pub struct Tags {
+    name: String,
+    tags: Vec<String>,
+}
+
+impl Tags {
+    pub fn handle(data: &DataDir, name: String) -> Result<Tags, errors::Client> {
+        let tags = manifests::tags(data, &name);
+        if tags.is_empty() && !data.repositories().contains(&name) {
+            return Err(errors::Client::NAME_UNKNOWN);
+        }
+        Ok(Tags { name, tags })
+    }
+}
+
+impl<'r> Responder<'r> for Tags {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+
+    use response::test_helper::test_route;
+    use super::Tags;
+
+    #[test]
+    fn tags_ok() {
+        let tags = Tags {
+            name: String::from("moredhel/test"),
+            tags: vec![String::from("latest")],
+        };
+        let response = test_route(tags);
+        assert_eq!(response.status(), Status::Ok);
+    }
+}
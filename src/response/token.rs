@@ -0,0 +1,99 @@
+use failure::Error;
+use rocket::State;
+use rocket::http::{Header, Status};
+use rocket::response::{Responder, Response};
+use rocket::request::Request;
+
+use auth;
+use controller::token::TokenParams;
+use errors;
+
+use response::json_response;
+
+#[derive(Debug, Serialize)]
+pub struct Token {
+    token: String,
+    access_token: String,
+    expires_in: i64,
+    issued_at: String,
+}
+
+impl Token {
+    /// Authenticates the client and issues a token for the requested scopes.
+    ///
+    /// Only the actions allowed by the access policy are granted,
+    /// clients without credentials get an anonymous token.
+    pub fn handle(
+        auth: State<auth::Authenticator>,
+        credentials: Option<auth::BasicAuth>,
+        params: TokenParams,
+    ) -> Result<Token, Error> {
+        if !auth.enabled() {
+            return Err(errors::Client::UNSUPPORTED.into());
+        }
+
+        let subject = match credentials {
+            Some(credentials) => {
+                if !auth.authenticate(&credentials) {
+                    warn!("Failed login for {}", credentials.username);
+                    return Err(errors::Client::UNAUTHORIZED.into());
+                }
+                Some(credentials.username)
+            }
+            None => None,
+        };
+        let scopes = auth.grant(subject.as_ref().map(|s| s.as_str()), params.scopes);
+        let subject = subject.unwrap_or_default();
+
+        debug!("Issuing token for '{}' with scopes {:?}", subject, scopes);
+        let token = auth.issue(&subject, scopes)?;
+        Ok(Token {
+            access_token: token.token.clone(),
+            token: token.token,
+            expires_in: token.expires_in,
+            issued_at: token.issued_at,
+        })
+    }
+}
+
+impl<'r> Responder<'r> for Token {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        json_response(req, &self)
+    }
+}
+
+/// A 401 response pointing the client at the token server.
+#[derive(Debug)]
+pub struct Challenge(pub Option<String>);
+
+impl Challenge {
+    pub fn new(auth: &auth::Authenticator, scope: Option<&auth::token::Scope>) -> Challenge {
+        Challenge(auth.challenge(scope))
+    }
+}
+
+impl<'r> Responder<'r> for Challenge {
+    fn respond_to(self, req: &Request) -> Result<Response<'r>, Status> {
+        let mut response = Response::build_from(errors::Client::UNAUTHORIZED.respond_to(req)?);
+        if let Some(challenge) = self.0 {
+            response.header(Header::new("WWW-Authenticate", challenge));
+        }
+        response.status(Status::Unauthorized).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Status;
+    use response::token::Challenge;
+
+    use response::test_helper::test_route;
+
+    #[test]
+    fn challenge_unauthorized() {
+        let challenge = String::from("Bearer realm=\"http://localhost/token\"");
+        let response = test_route(Challenge(Some(challenge)));
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert!(response.headers().contains("WWW-Authenticate"));
+    }
+}
@@ -8,16 +8,25 @@ use uuid::Uuid;
 
 use grpc::backend;
 use config;
+use datadir::DataDir;
 use errors;
+use types::Digest;
 
 #[derive(Debug, Serialize)]
 pub enum UuidResponse {
+    /// An upload that received the bytes from _left_ up to, but not
+    /// including, _right_
     Uuid {
         uuid: String,
         name: String,
-        repo: String,
-        left: u32,
-        right: u32,
+        left: u64,
+        right: u64,
+    },
+    /// A blob linked in from the repository _from_
+    Mounted {
+        name: String,
+        digest: Digest,
+        from: String,
     },
     Empty,
 }
@@ -26,39 +35,60 @@ impl UuidResponse {
     pub fn handle(
         handler: State<config::BackendHandler>,
         name: String,
-        repo: String,
     ) -> Result<UuidResponse, Error> {
         let backend = handler.backend();
         let mut req = backend::Layer::new();
         req.set_name(name.clone());
-        req.set_repo(repo.clone());
 
-        let response = backend.gen_uuid(req)?;
+        let response = backend.gen_uuid(&req)?;
         debug!("Client received: {:?}", response);
 
 
         Ok(UuidResponse::Uuid {
             uuid: response.get_uuid().to_owned(),
             name: name,
-            repo: repo,
             left: 0,
             right: 0,
         })
     }
 
+    /// Links the blob _digest_ of _from_ into _name_ if it's there,
+    /// otherwise starts an upload to _name_.
+    pub fn mount(
+        handler: State<config::BackendHandler>,
+        data: &DataDir,
+        name: String,
+        digest: Digest,
+        from: Option<String>,
+    ) -> Result<UuidResponse, Error> {
+        match from {
+            Some(from) => match data.has_blob(&from, &digest) {
+                true => {
+                    data.link_blob(&name, &digest)?;
+                    debug!("Mounted {} from {} into {}", digest, from, name);
+                    Ok(UuidResponse::Mounted { name, digest, from })
+                }
+                false => UuidResponse::handle(handler, name),
+            },
+            _ => UuidResponse::handle(handler, name),
+        }
+    }
+
     pub fn uuid_exists(
         handler: State<config::BackendHandler>,
+        name: &str,
         uuid: &String,
     ) -> Result<bool, Error> {
         let backend = handler.backend();
         let mut req = backend::Layer::new();
+        req.set_name(name.to_owned());
         req.set_digest(uuid.to_owned());
 
-        let response = backend.uuid_exists(req)?;
+        let response = backend.uuid_exists(&req)?;
         debug!("UuidExists: {:?}", response.get_success());
         match response.get_success() {
             true => Ok(true),
-            false => Err(errors::Client::DIGEST_INVALID.into()),
+            false => Err(errors::Client::BLOB_UPLOAD_UNKNOWN.into()),
         }
     }
 }
@@ -90,24 +120,27 @@ impl<'r> Responder<'r> for UuidResponse {
             UuidResponse::Uuid {
                 ref uuid,
                 ref name,
-                ref repo,
                 ref left,
                 ref right,
             } => {
                 debug!("Uuid Ok");
                 let location_url = format!(
-                    "{}/v2/{}/{}/blobs/uploads/{}?query=true",
+                    "{}/v2/{}/blobs/uploads/{}?query=true",
                     get_base_url(req),
                     name,
-                    repo,
                     uuid
                 );
+                // the range includes its last byte, an empty upload is 0-0
+                let last = match *right > *left {
+                    true => right - 1,
+                    false => *left,
+                };
                 let upload_uuid = Header::new("Docker-Upload-UUID", uuid.clone());
-                let range = Header::new("Range", format!("{}-{}", left, right));
+                let range = Header::new("Range", format!("{}-{}", left, last));
                 let length = Header::new("X-Content-Length", format!("{}", right - left));
                 let location = Header::new("Location", location_url);
 
-                debug!("Range: {}-{}, Length: {}", left, right, right - left);
+                debug!("Range: {}-{}, Length: {}", left, last, right - left);
                 Response::build()
                 .header(upload_uuid)
                 .header(location)
@@ -117,6 +150,14 @@ impl<'r> Responder<'r> for UuidResponse {
                 .status(Status::Accepted)
                 .ok()
             },
+            UuidResponse::Mounted { name, digest, .. } => {
+                let location = format!("{}/v2/{}/blobs/{}", get_base_url(req), name, digest);
+                Response::build()
+                    .header(Header::new("Location", location))
+                    .header(Header::new("Docker-Content-Digest", digest.to_string()))
+                    .status(Status::Created)
+                    .ok()
+            }
             UuidResponse::Empty => {
                 debug!("Uuid Error");
                 Response::build().status(Status::NotFound).ok()
@@ -135,8 +176,7 @@ mod test {
         UuidResponse::Uuid {
             // TODO: keep this as a real Uuid!
             uuid: String::from("whatever"),
-            name: String::from("moredhel"),
-            repo: String::from("test"),
+            name: String::from("moredhel/test"),
             left: 0,
             right: 0,
         }
@@ -149,7 +189,31 @@ mod test {
         assert_eq!(response.status(), Status::Accepted);
         assert!(headers.contains("Docker-Upload-UUID"));
         assert!(headers.contains("Location"));
-        assert!(headers.contains("Range"));
+        assert_eq!(headers.get_one("Range"), Some("0-0"));
+    }
+
+    #[test]
+    fn uuid_range_includes_the_last_byte() {
+        let response = test_route(UuidResponse::Uuid {
+            uuid: String::from("whatever"),
+            name: String::from("moredhel/test"),
+            left: 0,
+            right: 1024,
+        });
+        assert_eq!(response.headers().get_one("Range"), Some("0-1023"));
+        assert_eq!(response.headers().get_one("X-Content-Length"), Some("1024"));
+    }
+
+    #[test]
+    fn uuid_mounted() {
+        let digest = "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+        let response = test_route(UuidResponse::Mounted {
+            name: String::from("moredhel/test"),
+            digest: digest.parse().unwrap(),
+            from: String::from("moredhel/base"),
+        });
+        assert_eq!(response.status(), Status::Created);
+        assert_eq!(response.headers().get_one("Docker-Content-Digest"), Some(digest));
     }
 
     #[test]
@@ -6,35 +6,71 @@ use rocket::request::Request;
 
 
 use config;
+use datadir::DataDir;
 use errors;
-use controller::uuid as cuuid;
 use grpc::backend;
+use state;
+use state::uploads::Uploads;
 use types;
+use types::Digest;
 
 const BASE_URL: &str = "http://localhost:8000";
 
 #[derive(Debug, Serialize)]
 pub enum UuidAcceptResponse {
-    DigestMismatch,
     UuidAccept {
         uuid: String,
-        digest: String,
+        digest: Digest,
         name: String,
-        repo: String,
     },
     UuidDelete,
-    UnknownError,
 }
 
 impl UuidAcceptResponse {
+    /// Completes an upload: the digest of the uploaded data, hashed as
+    /// it arrived, is checked against the one the client sent, the data
+    /// moved into the layer store and linked into the repository, and the
+    /// backend is told the upload is finished.
+    ///
+    /// Only uploads the backend started are completed.
     pub fn handle(
-        _handler: State<config::BackendHandler>,
-        _name: String,
-        _repo: String,
-        _uuid: String,
-        _digest: cuuid::DigestStruct,
+        handler: State<config::BackendHandler>,
+        uploads: State<Uploads>,
+        data: &DataDir,
+        name: String,
+        uuid: String,
+        digest: Digest,
     ) -> Result<UuidAcceptResponse, Error> {
-        not_implemented!()
+        let backend = handler.backend();
+        let mut upload = backend::Layer::new();
+        upload.set_name(name.clone());
+        upload.set_digest(uuid.clone());
+        if !backend.uuid_exists(&upload)?.get_success() {
+            return Err(errors::Client::BLOB_UPLOAD_UNKNOWN.into());
+        }
+
+        let hash = uploads
+            .finish(&uuid, digest.algorithm())
+            .map_err(|_| errors::Client::BLOB_UPLOAD_UNKNOWN)?;
+        if hash != digest {
+            warn!("Digest mismatch for {}: {} != {}", uuid, hash, digest);
+            return Err(errors::Client::DIGEST_INVALID.into());
+        }
+
+        let size = state::uuid::save_layer(data, &uuid, &digest)?;
+        data.link_blob(&name, &digest)?;
+
+        let mut req = backend::CommittedBlob::new();
+        req.set_name(name.clone());
+        req.set_uuid(uuid.clone());
+        req.set_digest(digest.to_string());
+        req.set_size(size);
+        let response = backend.commit_upload(&req)?;
+        if !response.get_success() {
+            warn!("Commit of {}: {}", uuid, response.get_text());
+        }
+
+        Ok(UuidAcceptResponse::UuidAccept { uuid, digest, name })
     }
 
     pub fn delete_upload(
@@ -45,10 +81,9 @@ impl UuidAcceptResponse {
         let backend = handler.backend();
         let mut req = backend::Layer::new();
         req.set_name(layer.name.to_owned());
-        req.set_repo(layer.repo.to_owned());
         req.set_digest(layer.digest.to_owned());
 
-        let response = backend.cancel_upload(req)?;
+        let response = backend.cancel_upload(&req)?;
 
         debug!("Return: {:?}", response);
         match response.get_success() {
@@ -65,25 +100,19 @@ impl<'r> Responder<'r> for UuidAcceptResponse {
         match self {
             UuidAccept {
                 name,
-                repo,
                 digest,
                 uuid: _,
             } => {
-                let location = format!("{}/v2/{}/{}/blobs/{}", BASE_URL, name, repo, digest);
+                let location = format!("{}/v2/{}/blobs/{}", BASE_URL, name, digest);
                 let location = Header::new("Location", location);
-                let digest = Header::new("Docker-Content-Digest", digest);
+                let digest = Header::new("Docker-Content-Digest", digest.to_string());
                 Response::build()
                     .status(Status::Created)
                     .header(location)
                     .header(digest)
                     .ok()
             }
-            DigestMismatch => {
-                debug!("Digest mismatched");
-                Response::build().status(Status::NotFound).ok()
-            }
             UuidDelete => Response::build().status(Status::NoContent).ok(),
-            UnknownError => Response::build().status(Status::NotFound).ok(),
         }
     }
 }
@@ -98,8 +127,7 @@ mod test {
         UuidResponse::Uuid {
             // TODO: keep this as a real Uuid!
             uuid: String::from("whatever"),
-            name: String::from("moredhel"),
-            repo: String::from("test"),
+            name: String::from("moredhel/test"),
             left: 0,
             right: 0,
         }
@@ -1,26 +1,53 @@
 use std::string::ToString;
 
+use failure;
+use grpcio;
 use rocket;
 
+use auth;
 use errors;
 use config;
+use controller::logging::LogLevelParams;
+use controller::referrers::ReferrersParams;
+use controller::repository::{BlobPath, ManifestPath, ReferrersPath, TagsPath, UploadPath,
+                             UploadsPath};
+use controller::token::TokenParams;
 use controller::uuid as cuuid;
+use datadir::DataDir;
 use response::admin::Admin;
+use response::backends::Backends;
+use response::blob::{Blob, DeletedBlob};
 use response::{MaybeResponse, MaybeResponse2, RegistryResponse};
 use response::empty::Empty;
+use response::health::{Alive, Readiness};
 use response::layers::LayerExists;
+use response::logging::LogLevels;
+use response::manifest::ManifestResponse;
+use response::metrics::Metrics;
+use response::referrers::Referrers;
+use response::replication::{Backfill, ReplicationJobs};
+use response::scrub::ScrubReport;
+use response::tags::Tags;
 use response::uuid::UuidResponse;
 use response::uuidaccept::UuidAcceptResponse;
 use response::catalog::Catalog;
 use response::html::HTML;
+use response::token::{Challenge, Token};
 
+use manifest::Accepted;
+use notifications::{self, Notify};
+use proxy::Proxy;
+use replication::Replicator;
+use scrub::Scrubber;
 use state;
+use state::uploads::Uploads;
 use types::Layer;
 
 pub fn routes() -> Vec<rocket::Route> {
     routes![
         get_v2root,
         get_homepage,
+        get_token,
         get_manifest,
         check_image_manifest,
         get_blob,
@@ -30,19 +57,39 @@ pub fn routes() -> Vec<rocket::Route> {
         put_blob,
         patch_blob,
         delete_upload,
+        post_blob_mount,
         post_blob_upload,
         delete_blob,
         put_image_manifest,
         get_catalog,
         get_image_tags,
         delete_image_manifest,
+        get_referrers,
+        get_referrers_filtered,
+        get_metrics,
+        get_healthz,
+        get_readyz,
         // admin routes
         admin_get_uuids,
+        admin_get_log_levels,
+        admin_put_log_level,
+        admin_get_replication,
+        admin_backfill,
+        admin_backfill_rule,
+        admin_get_scrub,
+        admin_get_backends,
+        draining,
     ]
 }
 
 pub fn errors() -> Vec<rocket::Catcher> {
-    errors![err_400, err_404,]
+    errors![err_400, err_401, err_403, err_404,]
+}
+
+/// Requests arriving after SIGTERM end up here, see `shutdown`.
+#[get("/draining")]
+fn draining() -> MaybeResponse<errors::Client> {
+    MaybeResponse::build(errors::Client::UNAVAILABLE)
 }
 
 #[error(400)]
@@ -50,11 +97,51 @@ fn err_400() -> MaybeResponse<Empty> {
     MaybeResponse::err(Empty)
 }
 
+#[error(401)]
+fn err_401(req: &rocket::Request) -> MaybeResponse<Challenge> {
+    let scope = auth::requested_scope(req);
+    let challenge = req.guard::<rocket::State<auth::Authenticator>>()
+        .succeeded()
+        .and_then(|auth| auth.challenge(scope.as_ref()));
+    MaybeResponse::build(Challenge(challenge))
+}
+
+#[error(403)]
+fn err_403() -> MaybeResponse<errors::Client> {
+    MaybeResponse::build(errors::Client::DENIED)
+}
+
 #[error(404)]
 fn err_404() -> MaybeResponse<Empty> {
     MaybeResponse::err(Empty)
 }
 
+/// Errors meant for the client are returned as is, anything else is
+/// logged and reported as an internal error, or unavailable if a
+/// backend call failed.
+fn client_error(context: &str, e: failure::Error) -> errors::Client {
+    match e.downcast::<errors::Client>() {
+        Ok(e) => e,
+        Err(e) => {
+            warn!("{}: {}", context, e);
+            match e.downcast_ref::<grpcio::Error>() {
+                Some(_) => errors::Client::UNAVAILABLE,
+                None => errors::Client::UNKNOWN,
+            }
+        }
+    }
+}
+
+/// Logs _e_, reporting failed backend calls as unavailable and
+/// answering any other error with _fallback_.
+fn unavailable<T>(context: &str, e: failure::Error, fallback: T) -> Result<T, errors::Client> {
+    warn!("{}: {}", context, e);
+    match e.downcast_ref::<grpcio::Error>() {
+        Some(_) => Err(errors::Client::UNAVAILABLE),
+        None => Ok(fallback),
+    }
+}
+
 /// Routes of a 2.0 Registry
 ///
 /// Version Check of the registry
@@ -67,8 +154,34 @@ fn err_404() -> MaybeResponse<Empty> {
 /// # Headers
 /// Docker-Distribution-API-Version: registry/2.0
 #[get("/v2")]
-fn get_v2root() -> MaybeResponse<Empty> {
-    MaybeResponse::build(Empty)
+fn get_v2root(
+    auth: rocket::State<auth::Authenticator>,
+    token: Option<auth::AccessToken>,
+) -> MaybeResponse<Result<Empty, Challenge>> {
+    match (auth.enabled(), token) {
+        (true, None) => MaybeResponse::build(Err(Challenge::new(&auth, None))),
+        _ => MaybeResponse::build(Ok(Empty)),
+    }
+}
+
+/// Token Server
+/// GET /token?service=<service>&scope=<scope>
+///
+/// Authenticates the user with HTTP Basic auth against the configured
+/// htpasswd file and issues a signed JWT for the requested scopes.
+///
+/// # Returns
+/// 200 - token issued
+/// 401 - invalid credentials
+#[get("/token?<params>")]
+fn get_token(
+    auth: rocket::State<auth::Authenticator>,
+    credentials: Option<auth::BasicAuth>,
+    params: TokenParams,
+) -> MaybeResponse2<Token> {
+    let response =
+        Token::handle(auth, credentials, params).map_err(|e| client_error("Token", e));
+    MaybeResponse::build(response)
 }
 
 const ROOT_RESPONSE: &'static str = "<!DOCTYPE html><html><body>
@@ -100,13 +213,37 @@ Accept: manifest-version
 200 - return the manifest
 404 - manifest not known to the registry
  */
-#[get("/v2/<_name>/<_repo>/manifests/<reference>")]
-fn get_manifest(_name: String, _repo: String, reference: String) -> MaybeResponse<Empty> {
+#[get("/v2/<path..>", rank = 1)]
+fn get_manifest(
+    auth: auth::Authorized,
+    notify: Notify,
+    proxy: rocket::State<Proxy>,
+    accepted: Accepted,
+    path: ManifestPath,
+) -> MaybeResponse2<ManifestResponse> {
     info!("Getting Manifest");
-    match reference.as_str() {
-        "good" => MaybeResponse::ok(Empty),
-        _ => MaybeResponse::err(Empty),
-    }
+    let repository = repository!(path);
+    let reference = path.reference;
+    let response = ManifestResponse::get(proxy, &accepted, &repository, &reference)
+        .map(|response| {
+            if let ManifestResponse::Manifest(ref manifest) = response {
+                let tag = match state::manifests::is_digest(&reference) {
+                    true => None,
+                    false => Some(reference.as_str()),
+                };
+                let target = notifications::Target::manifest(
+                    &repository,
+                    &manifest.media_type,
+                    &manifest.digest,
+                    manifest.content.len() as u64,
+                    tag,
+                );
+                notify.emit(notifications::Action::Pull, target, auth.user);
+            }
+            response
+        })
+        .map_err(|e| client_error("Get manifest", e));
+    MaybeResponse::build(response)
 }
 /*
 
@@ -126,9 +263,17 @@ Content-Length: size of manifest
 200 - manifest exists
 404 - manifest does not exist
  */
-#[head("/v2/<_name>/<_repo>/manifests/<_reference>")]
-fn check_image_manifest(_name: String, _repo: String, _reference: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+#[head("/v2/<path..>", rank = 1)]
+fn check_image_manifest(
+    _auth: auth::Authorized,
+    proxy: rocket::State<Proxy>,
+    accepted: Accepted,
+    path: ManifestPath,
+) -> MaybeResponse2<ManifestResponse> {
+    let repository = repository!(path);
+    let response = ManifestResponse::get(proxy, &accepted, &repository, &path.reference)
+        .map_err(|e| client_error("Check manifest", e));
+    MaybeResponse::build(response)
 }
 
 /*
@@ -142,13 +287,26 @@ digest - unique identifier for the blob to be downoaded
 200 - blob is downloaded
 307 - redirect to another service for downloading[1]
  */
-#[get("/v2/<_name>/<_repo>/blobs/<digest>")]
-fn get_blob(_name: String, _repo: String, digest: String) -> MaybeResponse<Empty> {
+#[get("/v2/<path..>", rank = 2)]
+fn get_blob(
+    auth: auth::Authorized,
+    notify: Notify,
+    proxy: rocket::State<Proxy>,
+    data: rocket::State<DataDir>,
+    path: BlobPath,
+) -> MaybeResponse2<Blob> {
     info!("Getting Blob");
-    match digest.as_str() {
-        "good" => MaybeResponse::ok(Empty),
-        _ => MaybeResponse::err(Empty),
-    }
+    let repository = repository!(path);
+    let digest = digest!(path.digest);
+    let response = Blob::handle(proxy, &data, &repository, &digest)
+        .map(|blob| {
+            let size = state::uuid::blob_size(&data, &digest);
+            let target = notifications::Target::blob(&repository, &digest.to_string(), size);
+            notify.emit(notifications::Action::Pull, target, auth.user);
+            blob
+        })
+        .map_err(|e| client_error("Get blob", e));
+    MaybeResponse::build(response)
 }
 
 /// Pushing a Layer
@@ -162,10 +320,11 @@ fn get_blob(_name: String, _repo: String, digest: String) -> MaybeResponse<Empty
 /// Docker-Upload-UUID: <uuid>
 ///
 /// # Returns
-/// 202 - accepted
-#[post("/v2/<_name>/<_repo>/blobs/uploads/<_uuid>")]
-fn post_blob_uuid(_name: String, _repo: String, _uuid: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+/// 405 - unsupported, uploads are started by `post_blob_upload`
+#[post("/v2/<path..>", rank = 2)]
+fn post_blob_uuid(_auth: auth::Authorized, path: UploadPath) -> MaybeResponse2<Empty> {
+    let _repository = repository!(path);
+    MaybeResponse::build(Err(errors::Client::UNSUPPORTED))
 }
 
 /*
@@ -184,21 +343,18 @@ Docker-Content-Digest: <digest>
 404 - does not exist
  */
 
-#[head("/v2/<name>/<repo>/blobs/<digest>")]
+#[head("/v2/<path..>", rank = 2)]
 fn check_existing_layer(
+    _auth: auth::Authorized,
     backend: rocket::State<config::BackendHandler>,
-    name: String,
-    repo: String,
-    digest: String,
-) -> MaybeResponse<LayerExists> {
+    path: BlobPath,
+) -> MaybeResponse2<LayerExists> {
     debug!("Handling LayerExists route");
-    LayerExists::handle(backend, Layer { name, repo, digest })
-        .map(|response| MaybeResponse::build(response))
-        .map_err(|e| {
-            warn!("{}", e);
-            errors::Client::BLOB_UNKNOWN
-        })
-        .unwrap_or(MaybeResponse::build(LayerExists::False))
+    let name = repository!(path);
+    let digest = digest!(path.digest);
+    let response = LayerExists::handle(backend, Layer::new(name, digest.to_string()))
+        .or_else(|e| unavailable("Check layer", e, LayerExists::False));
+    MaybeResponse::build(response)
 }
 
 /*
@@ -217,11 +373,12 @@ Range: bytes=0-<offset>
 Docker-Upload-UUID: <uuid>
 
 # Returns
-204
+405 - unsupported, upload progress isn't tracked
  */
-#[get("/v2/<_name>/<_repo>/blobs/uploads/<_uuid>")]
-fn get_upload_progress(_name: String, _repo: String, _uuid: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+#[get("/v2/<path..>", rank = 3)]
+fn get_upload_progress(_auth: auth::Authorized, path: UploadPath) -> MaybeResponse2<Empty> {
+    let _repository = repository!(path);
+    MaybeResponse::build(Err(errors::Client::UNSUPPORTED))
 }
 /*
 
@@ -243,53 +400,67 @@ Content-Type: application/octet-stream
 <Layer Chunk Binary Data>
  */
 
-#[put("/v2/<name>/<repo>/blobs/uploads/<uuid>?<digest>")] // capture digest query string
+#[put("/v2/<path..>?<digest>", rank = 1)] // capture digest query string
 fn put_blob(
+    auth: auth::Authorized,
+    notify: Notify,
     config: rocket::State<config::BackendHandler>,
-    name: String,
-    repo: String,
-    uuid: String,
+    uploads: rocket::State<Uploads>,
+    data: rocket::State<DataDir>,
+    path: UploadPath,
     digest: cuuid::DigestStruct,
-) -> MaybeResponse<UuidAcceptResponse> {
-    UuidAcceptResponse::handle(config, name, repo, uuid, digest)
-        .map(|response| MaybeResponse::build(response))
-        .or_else(|e| Err(e))
-        .unwrap_or(MaybeResponse::build(UuidAcceptResponse::UnknownError))
+) -> MaybeResponse2<UuidAcceptResponse> {
+    let name = repository!(path);
+    let digest = digest!(digest.digest);
+    let uuid = upload!(path.uuid);
+    let response = UuidAcceptResponse::handle(config, uploads, &data, name, uuid, digest)
+        .map(|response| {
+            if let UuidAcceptResponse::UuidAccept {
+                ref name,
+                ref digest,
+                ..
+            } = response
+            {
+                let size = state::uuid::blob_size(&data, digest);
+                let target = notifications::Target::blob(name, &digest.to_string(), size);
+                notify.emit(notifications::Action::Push, target, auth.user);
+            }
+            response
+        })
+        .map_err(|e| client_error("Put blob", e));
+    MaybeResponse::build(response)
 }
 
-#[patch("/v2/<name>/<repo>/blobs/uploads/<uuid>", data = "<chunk>")]
+#[patch("/v2/<path..>", data = "<chunk>", rank = 1)]
 fn patch_blob(
+    _auth: auth::Authorized,
     handler: rocket::State<config::BackendHandler>,
-    name: String,
-    repo: String,
-    uuid: String,
+    uploads: rocket::State<Uploads>,
+    path: UploadPath,
     chunk: rocket::data::Data,
-) -> MaybeResponse<UuidResponse> {
+) -> MaybeResponse2<UuidResponse> {
+    let name = repository!(path);
+    let uuid = upload!(path.uuid);
     debug!("Checking if uuid is valid!");
-    let exists = UuidResponse::uuid_exists(handler, &uuid);
-    if let Ok(_) = exists {
-        let absolute_file = state::uuid::scratch_path(&uuid);
-        debug!("Streaming out to {}", absolute_file);
-        let file = chunk.stream_to_file(absolute_file);
-
-        match file {
-            Ok(_) => {
-                let right = match file.map(|x| x.to_string()) {
-                    Ok(x) => x.parse::<u32>().unwrap(),
-                    Err(_) => 0,
-                };
-                MaybeResponse::build(UuidResponse::Uuid {
-                    uuid,
-                    name,
-                    repo,
-                    left: 0,
-                    right,
-                })
+    if let Err(e) = UuidResponse::uuid_exists(handler, &name, &uuid) {
+        return MaybeResponse::build(Err(client_error("Check upload", e)));
+    }
+    debug!("Streaming out upload {}", uuid);
+    match uploads.append(&uuid, chunk.open()) {
+        Ok(right) => MaybeResponse::build(Ok(UuidResponse::Uuid {
+            uuid,
+            name,
+            left: 0,
+            right,
+        })),
+        // e.g. RANGE_INVALID while another chunk is still being received
+        Err(e) => match e.downcast::<errors::Client>() {
+            Ok(e) => MaybeResponse::build(Err(e)),
+            Err(e) => {
+                warn!("Appending to upload {}: {}", uuid, e);
+                MaybeResponse::build(Err(errors::Client::BLOB_UPLOAD_INVALID))
             }
-            Err(_) => MaybeResponse::build(UuidResponse::Empty),
-        }
-    } else {
-        MaybeResponse::build(UuidResponse::Empty)
+        },
     }
 }
 
@@ -303,42 +474,80 @@ DELETE /v2/<name>/blobs/uploads/<uuid>
  */
 
 /// This route assumes that no more data will be uploaded to the specified uuid.
-#[delete("/v2/<name>/<repo>/blobs/uploads/<uuid>")]
+#[delete("/v2/<path..>", rank = 1)]
 fn delete_upload(
+    _auth: auth::Authorized,
     handler: rocket::State<config::BackendHandler>,
-    name: String,
-    repo: String,
-    uuid: String,
+    uploads: rocket::State<Uploads>,
+    path: UploadPath,
 ) -> MaybeResponse2<UuidAcceptResponse> {
-    let response = UuidAcceptResponse::delete_upload(handler, &Layer::new(name, repo, uuid))
-    .map_err(|e| {
-        match e.downcast::<errors::Client>() {
-            Ok(e) => e,
-            Err(_) => errors::Client::UNSUPPORTED,
-        }
-    }
-    );
+    let name = repository!(path);
+    let uuid = upload!(path.uuid);
+    uploads.cancel(&uuid);
+    let response = UuidAcceptResponse::delete_upload(handler, &Layer::new(name, uuid))
+        .map_err(|e| client_error("Delete upload", e));
     MaybeResponse::build(response)
 }
 /*
 ---
-Cross repo blob mounting (validate how regularly this is used)
+Cross repository blob mount
 POST /v2/<name>/blobs/uploads/?mount=<digest>&from=<repository name>
 
+Links the blob of _from_ into _name_ without uploading it again, if the
+caller may pull from _from_. Otherwise an upload is started, as without
+the query.
+
+# Returns
+201 - the blob was mounted
+202 - an upload was started instead
  */
+#[post("/v2/<path..>?<params>", rank = 1)]
+fn post_blob_mount(
+    auth: auth::Authorized,
+    notify: Notify,
+    authenticator: rocket::State<auth::Authenticator>,
+    handler: rocket::State<config::BackendHandler>,
+    data: rocket::State<DataDir>,
+    path: UploadsPath,
+    params: cuuid::MountParams,
+) -> MaybeResponse2<UuidResponse> {
+    let name = repository!(path);
+    let digest = digest!(params.mount);
+    let from = match ::controller::repository::validate(&params.from) {
+        Ok(from) => match auth.allows(&authenticator, &from, auth::acl::Action::Pull) {
+            true => Some(from),
+            false => None,
+        },
+        Err(_) => None,
+    };
+    let response = UuidResponse::mount(handler, &data, name, digest, from)
+        .map(|response| {
+            if let UuidResponse::Mounted {
+                ref name,
+                ref digest,
+                ref from,
+            } = response
+            {
+                let size = state::uuid::blob_size(&data, digest);
+                let target = notifications::Target::mount(name, &digest.to_string(), size, from);
+                notify.emit(notifications::Action::Mount, target, auth.user);
+            }
+            response
+        })
+        .or_else(|e| unavailable("Blob mount", e, UuidResponse::Empty));
+    MaybeResponse::build(response)
+}
 
-#[post("/v2/<name>/<repo>/blobs/uploads")]
+#[post("/v2/<path..>", rank = 3)]
 fn post_blob_upload(
+    _auth: auth::Authorized,
     handler: rocket::State<config::BackendHandler>,
-    name: String,
-    repo: String,
-) -> MaybeResponse<UuidResponse> {
-    UuidResponse::handle(handler, name, repo)
-        .map_err(|e| {
-            warn!("Uuid Generate: {}", e);
-        })
-        .map(|response| MaybeResponse::build(response))
-        .unwrap_or(MaybeResponse::build(UuidResponse::Empty))
+    path: UploadsPath,
+) -> MaybeResponse2<UuidResponse> {
+    let name = repository!(path);
+    let response = UuidResponse::handle(handler, name)
+        .or_else(|e| unavailable("Uuid Generate", e, UuidResponse::Empty));
+    MaybeResponse::build(response)
 }
 /*
 
@@ -346,10 +555,28 @@ fn post_blob_upload(
 Delete a layer
 DELETE /v2/<name>/blobs/<digest>
 
+Layers are stored once for every repository, so this only unlinks the
+layer from _name_. `lycaon gc` removes the layers no manifest refers to
+any more.
  */
-#[delete("/v2/<_name>/<_repo>/blobs/<_digest>")]
-fn delete_blob(_name: String, _repo: String, _digest: String) -> MaybeResponse<Empty> {
-    MaybeResponse::build(Empty)
+#[delete("/v2/<path..>", rank = 2)]
+fn delete_blob(
+    auth: auth::Authorized,
+    notify: Notify,
+    data: rocket::State<DataDir>,
+    path: BlobPath,
+) -> MaybeResponse2<DeletedBlob> {
+    let repository = repository!(path);
+    let digest = digest!(path.digest);
+    let size = state::uuid::blob_size(&data, &digest);
+    let response = DeletedBlob::handle(&data, &repository, &digest)
+        .map(|response| {
+            let target = notifications::Target::blob(&repository, &digest.to_string(), size);
+            notify.emit(notifications::Action::Delete, target, auth.user);
+            response
+        })
+        .map_err(|e| client_error("Delete blob", e));
+    MaybeResponse::build(response)
 }
 /*
 
@@ -359,9 +586,42 @@ PUT /v2/<name>/manifests/<reference>
 Content-Type: <manifest media type>
 
  */
-#[put("/v2/<_name>/<_repo>/manifests/<_reference>")]
-fn put_image_manifest(_name: String, _repo: String, _reference: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+#[put("/v2/<path..>", data = "<manifest>", rank = 2)]
+fn put_image_manifest(
+    auth: auth::Authorized,
+    notify: Notify,
+    handler: rocket::State<config::BackendHandler>,
+    data: rocket::State<DataDir>,
+    replicator: rocket::State<Replicator>,
+    content_type: Option<&rocket::http::ContentType>,
+    path: ManifestPath,
+    manifest: rocket::data::Data,
+) -> MaybeResponse2<ManifestResponse> {
+    let repository = repository!(path);
+    let reference = path.reference;
+    let content_type = content_type.cloned();
+    let response =
+        ManifestResponse::put(handler, &data, &repository, &reference, content_type, manifest)
+        .map(|response| {
+            if let ManifestResponse::Created { ref digest, .. } = response {
+                let tag = match state::manifests::is_digest(&reference) {
+                    true => None,
+                    false => Some(reference.as_str()),
+                };
+                let manifest = state::manifests::get(&data, &repository, digest);
+                let media_type = manifest.as_ref().map(|m| m.media_type.clone()).unwrap_or_default();
+                let size = manifest.map(|m| m.content.len() as u64).unwrap_or(0);
+                let target =
+                    notifications::Target::manifest(&repository, &media_type, digest, size, tag);
+                notify.emit(notifications::Action::Push, target, auth.user);
+                if let Some(tag) = tag {
+                    replicator.tag_pushed(&repository, tag);
+                }
+            }
+            response
+        })
+        .map_err(|e| client_error("Put manifest", e));
+    MaybeResponse::build(response)
 }
 /*
 ---
@@ -370,7 +630,7 @@ GET /v2/_catalog
 
  */
 #[get("/v2/_catalog")]
-fn get_catalog() -> MaybeResponse<Catalog> {
+fn get_catalog(_auth: auth::Authorized) -> MaybeResponse<Catalog> {
     MaybeResponse::build(Catalog)
 }
 /*
@@ -379,9 +639,14 @@ Listing Image Tags
 GET /v2/<name>/tags/list
 
  */
-#[delete("/v2/<_name>/<_repo>/tags/list")]
-fn get_image_tags(_name: String, _repo: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+#[get("/v2/<path..>", rank = 6)]
+fn get_image_tags(
+    _auth: auth::Authorized,
+    data: rocket::State<DataDir>,
+    path: TagsPath,
+) -> MaybeResponse2<Tags> {
+    let name = repository!(path);
+    MaybeResponse::build(Tags::handle(&data, name))
 }
 /*
 ---
@@ -389,24 +654,292 @@ Deleting an Image
 DELETE /v2/<name>/manifests/<reference>
 
  */
-#[delete("/v2/<_name>/<_repo>/manifests/<_reference>")]
-fn delete_image_manifest(_name: String, _repo: String, _reference: String) -> MaybeResponse<Empty> {
-    MaybeResponse::err(Empty)
+#[delete("/v2/<path..>", rank = 3)]
+fn delete_image_manifest(
+    auth: auth::Authorized,
+    notify: Notify,
+    handler: rocket::State<config::BackendHandler>,
+    data: rocket::State<DataDir>,
+    path: ManifestPath,
+) -> MaybeResponse2<ManifestResponse> {
+    let repository = repository!(path);
+    let reference = path.reference;
+    // read first, the event describes what was deleted
+    let deleted = state::manifests::get(&data, &repository, &reference);
+    let response = ManifestResponse::delete(handler, &data, &repository, &reference)
+        .map(|response| {
+            if let Some(manifest) = deleted {
+                let target = notifications::Target::manifest(
+                    &repository,
+                    &manifest.media_type,
+                    &manifest.digest,
+                    manifest.content.len() as u64,
+                    None,
+                );
+                notify.emit(notifications::Action::Delete, target, auth.user);
+            }
+            response
+        })
+        .map_err(|e| client_error("Delete manifest", e));
+    MaybeResponse::build(response)
+}
+
+/// Listing referrers
+/// GET /v2/<name>/referrers/<digest>?artifactType=<type>
+///
+/// The manifests pushed with _digest_ as their subject, e.g.
+/// signatures and SBOMs, as an OCI image index.
+#[get("/v2/<path..>", rank = 5)]
+fn get_referrers(
+    _auth: auth::Authorized,
+    handler: rocket::State<config::BackendHandler>,
+    path: ReferrersPath,
+) -> MaybeResponse2<Referrers> {
+    let repository = repository!(path);
+    let subject = digest!(path.digest);
+    let response = Referrers::handle(handler, &repository, &subject, None)
+        .map_err(|e| client_error("Referrers", e));
+    MaybeResponse::build(response)
+}
+
+#[get("/v2/<path..>?<params>", rank = 4)]
+fn get_referrers_filtered(
+    _auth: auth::Authorized,
+    handler: rocket::State<config::BackendHandler>,
+    path: ReferrersPath,
+    params: ReferrersParams,
+) -> MaybeResponse2<Referrers> {
+    let repository = repository!(path);
+    let subject = digest!(path.digest);
+    let response = Referrers::handle(handler, &repository, &subject, params.artifact_type)
+        .map_err(|e| client_error("Referrers", e));
+    MaybeResponse::build(response)
+}
+
+/// Prometheus metrics
+/// GET /metrics
+///
+/// Request counts and latencies per route, transfer volume, upload
+/// sessions, gRPC latencies, peers, replication lag and storage usage.
+#[get("/metrics")]
+fn get_metrics(data: rocket::State<DataDir>) -> MaybeResponse2<Metrics> {
+    let response = Metrics::handle(&data).map_err(|e| client_error("Metrics", e));
+    MaybeResponse::build(response)
 }
 
 #[get("/admin/uuids")]
-fn admin_get_uuids(handler: rocket::State<config::BackendHandler>) -> MaybeResponse<Admin> {
+fn admin_get_uuids(
+    _auth: auth::Authorized,
+    handler: rocket::State<config::BackendHandler>,
+) -> MaybeResponse2<Admin> {
     MaybeResponse::build(
-        Admin::get_uuids(handler)
-            .map(|uuids| {
-                // oMaybeResponse::build(uuids)
-                uuids
-            })
-            .unwrap_or(Admin::Uuids(vec![])),
+        Admin::get_uuids(handler).or_else(|e| unavailable("Admin uuids", e, Admin::Uuids(vec![]))),
     )
 }
 
+/// Current log levels
+/// GET /admin/log
+#[get("/admin/log")]
+fn admin_get_log_levels(_auth: auth::Authorized) -> MaybeResponse<LogLevels> {
+    MaybeResponse::build(LogLevels::get())
+}
+
+/// Change a log level without restarting
+/// PUT /admin/log?module=<module>&level=<level>
+///
+/// Without a module the default level is changed.
+#[put("/admin/log?<params>")]
+fn admin_put_log_level(
+    _auth: auth::Authorized,
+    params: LogLevelParams,
+) -> MaybeResponse2<LogLevels> {
+    let response = LogLevels::handle(params).map_err(|e| client_error("Log level", e));
+    MaybeResponse::build(response)
+}
+
+/// Replication jobs
+/// GET /admin/replication
+#[get("/admin/replication")]
+fn admin_get_replication(
+    _auth: auth::Authorized,
+    replicator: rocket::State<Replicator>,
+) -> MaybeResponse<ReplicationJobs> {
+    MaybeResponse::build(ReplicationJobs::get(replicator))
+}
+
+/// Queue replication of every existing tag matching any rule
+/// POST /admin/replication/backfill
+#[post("/admin/replication/backfill")]
+fn admin_backfill(
+    _auth: auth::Authorized,
+    replicator: rocket::State<Replicator>,
+) -> MaybeResponse2<Backfill> {
+    let response = Backfill::handle(replicator, None).map_err(|e| client_error("Backfill", e));
+    MaybeResponse::build(response)
+}
+
+/// Queue replication of every existing tag matching one rule
+/// POST /admin/replication/<rule>/backfill
+#[post("/admin/replication/<rule>/backfill")]
+fn admin_backfill_rule(
+    _auth: auth::Authorized,
+    replicator: rocket::State<Replicator>,
+    rule: String,
+) -> MaybeResponse2<Backfill> {
+    let response =
+        Backfill::handle(replicator, Some(&rule)).map_err(|e| client_error("Backfill", e));
+    MaybeResponse::build(response)
+}
+
+/// Blob integrity, corrupt blobs found by the scrubber
+/// GET /admin/scrub
+#[get("/admin/scrub")]
+fn admin_get_scrub(
+    _auth: auth::Authorized,
+    scrubber: rocket::State<Scrubber>,
+) -> MaybeResponse<ScrubReport> {
+    MaybeResponse::build(ScrubReport::get(scrubber))
+}
+
+/// Liveness of the process, answered even while shutting down
+/// GET /healthz
+#[get("/healthz")]
+fn get_healthz() -> MaybeResponse<Alive> {
+    MaybeResponse::build(Alive::get())
+}
+
+/// Readiness for requests: a backend is ready and storage is writable
+/// GET /readyz
+#[get("/readyz")]
+fn get_readyz(
+    handler: rocket::State<config::BackendHandler>,
+    data: rocket::State<DataDir>,
+) -> MaybeResponse<Readiness> {
+    MaybeResponse::build(Readiness::check(handler, &data))
+}
+
+/// The backends this frontend calls and whether they're healthy
+/// GET /admin/backends
+#[get("/admin/backends")]
+fn admin_get_backends(
+    _auth: auth::Authorized,
+    handler: rocket::State<config::BackendHandler>,
+) -> MaybeResponse<Backends> {
+    MaybeResponse::build(Backends::get(handler))
+}
+
 /*
 ---
 [1]: Could possibly be used to redirect a client to a local cache
  */
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use backend::Node;
+    use clap;
+    use rocket::http::{ContentType, Status};
+    use rocket::local::Client;
+    use uuid::Uuid;
+
+    use config;
+    use state::manifests;
+
+    /// A registry calling the embedded backend of a standalone node,
+    /// both storing in a new temporary directory.
+    fn registry() -> (Client, PathBuf) {
+        let dir = env::temp_dir().join(format!("lycaon-routes-{}", Uuid::new_v4()));
+        let args = clap::App::new("lycaon").get_matches_from(vec!["lycaon"]);
+        let embedded = Node::standalone(&dir).backend();
+        let rocket = config::rocket(&args, Some(embedded), &dir).unwrap();
+        (Client::new(rocket).unwrap(), dir)
+    }
+
+    /// Pushes _content_ to _repository_ in one chunk, returns its digest.
+    fn push_blob(client: &Client, repository: &str, content: &[u8]) -> String {
+        let started = client
+            .post(format!("/v2/{}/blobs/uploads/", repository))
+            .dispatch();
+        assert_eq!(started.status(), Status::Accepted);
+        let uuid = started.headers().get_one("Docker-Upload-UUID").unwrap().to_owned();
+
+        let upload = format!("/v2/{}/blobs/uploads/{}", repository, uuid);
+        let patched = client.patch(upload.clone()).body(content).dispatch();
+        assert_eq!(patched.status(), Status::Accepted);
+        let range = format!("0-{}", content.len() - 1);
+        assert_eq!(patched.headers().get_one("Range"), Some(range.as_str()));
+
+        let digest = manifests::digest_of(content).to_string();
+        let put = client
+            .put(format!("{}?digest={}", upload, digest))
+            .dispatch();
+        assert_eq!(put.status(), Status::Created);
+        digest
+    }
+
+    #[test]
+    fn blobs_are_pushed_and_pulled() {
+        let (client, dir) = registry();
+        let digest = push_blob(&client, "library/alpine", b"layer");
+
+        let mut pulled = client
+            .get(format!("/v2/library/alpine/blobs/{}", digest))
+            .dispatch();
+        assert_eq!(pulled.status(), Status::Ok);
+        assert_eq!(pulled.body_bytes(), Some(b"layer".to_vec()));
+
+        // stored once, but only linked into the repository it was pushed to
+        let elsewhere = client
+            .get(format!("/v2/library/debian/blobs/{}", digest))
+            .dispatch();
+        assert_eq!(elsewhere.status(), Status::NotFound);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cancelled_uploads_are_gone() {
+        let (client, dir) = registry();
+        let started = client.post("/v2/library/alpine/blobs/uploads/").dispatch();
+        let uuid = started.headers().get_one("Docker-Upload-UUID").unwrap().to_owned();
+        let upload = format!("/v2/library/alpine/blobs/uploads/{}", uuid);
+
+        assert_eq!(client.delete(upload.clone()).dispatch().status(), Status::NoContent);
+        let patched = client.patch(upload).body(&b"late"[..]).dispatch();
+        assert_eq!(patched.status(), Status::NotFound);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn manifests_are_tagged() {
+        let (client, dir) = registry();
+        let config = push_blob(&client, "library/alpine", b"{}");
+        let manifest = format!(
+            r#"{{
+            "schemaVersion": 2,
+            "config": {{"digest": "{}", "size": 2}},
+            "layers": []
+        }}"#,
+            config
+        );
+
+        let oci = ContentType::new("application", "vnd.oci.image.manifest.v1+json");
+        let put = client
+            .put("/v2/library/alpine/manifests/3.7")
+            .header(oci)
+            .body(manifest.clone())
+            .dispatch();
+        assert_eq!(put.status(), Status::Created);
+
+        let mut tags = client.get("/v2/library/alpine/tags/list").dispatch();
+        assert_eq!(tags.status(), Status::Ok);
+        assert!(tags.body_string().unwrap().contains("3.7"));
+
+        let mut pulled = client.get("/v2/library/alpine/manifests/3.7").dispatch();
+        assert_eq!(pulled.status(), Status::Ok);
+        assert_eq!(pulled.body_string(), Some(manifest));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
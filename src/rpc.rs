@@ -0,0 +1,415 @@
+//! The calls the frontend makes to the backend.
+//!
+//! Usually the backend is remote, and calls go over gRPC to one of the
+//! backends in the pool. A single node can instead embed the backend
+//! with `embedded = true`, calling its `BackendService` directly and
+//! skipping the loopback round trip. Both fail with a `grpcio::Error`,
+//! so handlers can't tell them apart.
+//!
+//! Remote calls have a deadline, and the ones safe to repeat are
+//! retried with backoff when the backend couldn't be reached, as long
+//! as the call so far and the next backoff fit in that deadline. After
+//! too many failures in a row a circuit breaker opens and calls fail
+//! straight away, rather than every request waiting out the deadline
+//! while the backends are down.
+
+use std::cmp;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use grpc::backend::{BlobChunk, CommittedBlob, Empty, Event, GenUuidResult, Layer,
+                    LayerExistsResult, Referrer, ReferrerList, ReferrersRequest, UuidList};
+use grpc::backend::Result as BackendResult;
+use grpc::backend_grpc::BackendClient;
+use grpcio::{self, CallOption, RpcStatus, RpcStatusCode};
+use futures::{Future, Stream};
+
+use backend::BackendService;
+use backend::auth::ClusterAuth;
+use balancer::Pool;
+use config::BackendsConfig;
+use metrics;
+
+pub type Result<T> = grpcio::Result<T>;
+
+/// Chunks of a blob fetched from a peer
+pub type BlobChunks = Box<Stream<Item = BlobChunk, Error = grpcio::Error>>;
+
+pub trait Backend: Send + Sync {
+    fn layer_exists(&self, req: &Layer) -> Result<LayerExistsResult>;
+    fn gen_uuid(&self, req: &Layer) -> Result<GenUuidResult>;
+    fn uuid_exists(&self, req: &Layer) -> Result<BackendResult>;
+    fn cancel_upload(&self, req: &Layer) -> Result<BackendResult>;
+    fn commit_upload(&self, req: &CommittedBlob) -> Result<BackendResult>;
+    fn add_referrer(&self, req: &Referrer) -> Result<BackendResult>;
+    fn list_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList>;
+    fn remove_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList>;
+    fn publish(&self, req: &Event) -> Result<BackendResult>;
+    fn get_uuids(&self, req: &Empty) -> Result<UuidList>;
+    fn fetch_blob(&self, req: &Layer) -> Result<BlobChunks>;
+
+    /// The backends behind this one and whether they're healthy.
+    fn status(&self) -> Vec<(String, bool)>;
+}
+
+/// Whether _e_ means the backend couldn't be reached or didn't answer
+/// in time, rather than answering with an error.
+pub fn unavailable(e: &grpcio::Error) -> bool {
+    match *e {
+        grpcio::Error::RpcFailure(ref status) => match status.status {
+            RpcStatusCode::Unavailable | RpcStatusCode::DeadlineExceeded => true,
+            _ => false,
+        },
+        grpcio::Error::Codec(_) => false,
+        _ => true,
+    }
+}
+
+/// Fails calls fast while the backends are down.
+///
+/// Opens after _threshold_ unavailable calls in a row, 0 never opens.
+/// Once _cooldown_ passed a single call is let through to try the
+/// backends again, closing the breaker if it gets an answer.
+struct Breaker {
+    threshold: usize,
+    cooldown: Duration,
+    failures: AtomicUsize,
+    opened: Mutex<Option<Instant>>,
+}
+
+impl Breaker {
+    fn new(threshold: u32, cooldown: Duration) -> Breaker {
+        Breaker {
+            threshold: threshold as usize,
+            cooldown,
+            failures: AtomicUsize::new(0),
+            opened: Mutex::new(None),
+        }
+    }
+
+    /// Whether a call may go out now.
+    fn allow(&self) -> bool {
+        let mut opened = self.opened.lock().unwrap();
+        match *opened {
+            Some(at) if at.elapsed() < self.cooldown => false,
+            Some(_) => {
+                // the one trying, everyone else waits another cooldown
+                *opened = Some(Instant::now());
+                true
+            }
+            None => true,
+        }
+    }
+
+    /// The backend answered, even if with an error.
+    fn answered(&self) {
+        self.failures.store(0, Ordering::Relaxed);
+        let mut opened = self.opened.lock().unwrap();
+        if opened.take().is_some() {
+            info!("Backend answering again, closing the circuit");
+            metrics::BACKEND_CIRCUIT_OPEN.set(0.0);
+        }
+    }
+
+    fn failed(&self) {
+        let failures = self.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.threshold == 0 || failures < self.threshold {
+            return;
+        }
+        let mut opened = self.opened.lock().unwrap();
+        if opened.is_none() {
+            warn!("{} backend calls failed in a row, opening the circuit", failures);
+            metrics::BACKEND_CIRCUIT_OPEN.set(1.0);
+        }
+        *opened = Some(Instant::now());
+    }
+}
+
+/// Backends reached over gRPC.
+pub struct Remote {
+    pool: Pool,
+    auth: ClusterAuth,
+    timeout: Duration,
+    max_retries: u32,
+    backoff: Duration,
+    breaker: Breaker,
+}
+
+impl Remote {
+    pub fn new(pool: Pool, auth: ClusterAuth, config: &BackendsConfig) -> Remote {
+        Remote {
+            pool,
+            auth,
+            timeout: Duration::from_secs(config.timeout()),
+            max_retries: config.max_retries(),
+            backoff: Duration::from_millis(config.backoff()),
+            breaker: Breaker::new(
+                config.breaker_threshold(),
+                Duration::from_secs(config.breaker_cooldown()),
+            ),
+        }
+    }
+
+    /// Call options authenticating this frontend with the backend.
+    fn call_option(&self) -> CallOption {
+        self.auth.call_option().timeout(self.timeout)
+    }
+
+    /// Makes a call with _call_ to one of the backends, retrying it on
+    /// the next one while they're unavailable if it's _idempotent_.
+    ///
+    /// Calls with a _key_, a repository or a blob digest, go to its
+    /// backend, see `balancer`.
+    fn call<T, F>(&self, idempotent: bool, key: Option<&str>, call: F) -> Result<T>
+    where
+        F: Fn(&BackendClient) -> Result<T>,
+    {
+        let started = Instant::now();
+        let mut attempt = 0;
+        loop {
+            if !self.breaker.allow() {
+                return Err(grpcio::Error::RpcFailure(RpcStatus::new(
+                    RpcStatusCode::Unavailable,
+                    Some(String::from("backend circuit is open")),
+                )));
+            }
+            let client = match key {
+                Some(key) => self.pool.pick_for(key),
+                None => self.pool.pick(),
+            };
+            let e = match call(client) {
+                Ok(reply) => {
+                    self.breaker.answered();
+                    return Ok(reply);
+                }
+                Err(e) => e,
+            };
+            if !unavailable(&e) {
+                self.breaker.answered();
+                return Err(e);
+            }
+            self.breaker.failed();
+            if !idempotent || attempt >= self.max_retries {
+                return Err(e);
+            }
+            let delay = self.backoff * 2u32.pow(cmp::min(attempt, 16));
+            // the request waiting on this has its own deadline
+            if started.elapsed() + delay >= self.timeout {
+                return Err(e);
+            }
+            debug!("Backend call failed, retrying in {:?}: {}", delay, e);
+            metrics::BACKEND_RETRIES.inc();
+            thread::sleep(delay);
+            attempt += 1;
+        }
+    }
+}
+
+impl Backend for Remote {
+    fn layer_exists(&self, req: &Layer) -> Result<LayerExistsResult> {
+        self.call(true, Some(req.get_digest()), |client| client.layer_exists_opt(req, self.call_option()))
+    }
+
+    fn gen_uuid(&self, req: &Layer) -> Result<GenUuidResult> {
+        self.call(false, Some(req.get_name()), |client| client.gen_uuid_opt(req, self.call_option()))
+    }
+
+    fn uuid_exists(&self, req: &Layer) -> Result<BackendResult> {
+        self.call(true, Some(req.get_name()), |client| client.uuid_exists_opt(req, self.call_option()))
+    }
+
+    /// Not retried, a retry after a lost reply would find the upload
+    /// gone and fail a cancel that worked.
+    fn cancel_upload(&self, req: &Layer) -> Result<BackendResult> {
+        self.call(false, Some(req.get_name()), |client| client.cancel_upload_opt(req, self.call_option()))
+    }
+
+    fn commit_upload(&self, req: &CommittedBlob) -> Result<BackendResult> {
+        self.call(false, Some(req.get_name()), |client| client.commit_upload_opt(req, self.call_option()))
+    }
+
+    fn add_referrer(&self, req: &Referrer) -> Result<BackendResult> {
+        self.call(false, Some(req.get_repository()), |client| client.add_referrer_opt(req, self.call_option()))
+    }
+
+    fn list_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList> {
+        self.call(true, Some(req.get_repository()), |client| client.list_referrers_opt(req, self.call_option()))
+    }
+
+    fn remove_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList> {
+        self.call(false, Some(req.get_repository()), |client| client.remove_referrers_opt(req, self.call_option()))
+    }
+
+    fn publish(&self, req: &Event) -> Result<BackendResult> {
+        self.call(false, None, |client| client.publish_opt(req, self.call_option()))
+    }
+
+    /// The uploads of every backend, as each keeps its own.
+    fn get_uuids(&self, req: &Empty) -> Result<UuidList> {
+        let mut uuids = UuidList::new();
+        let mut failure = None;
+        for client in self.pool.all() {
+            match client.get_uuids_opt(req, self.call_option()) {
+                Ok(mut list) => uuids.mut_uuids().extend(list.take_uuids().into_iter()),
+                Err(e) => failure = Some(e),
+            }
+        }
+        match failure {
+            Some(e) if uuids.get_uuids().is_empty() => Err(e),
+            _ => Ok(uuids),
+        }
+    }
+
+    fn fetch_blob(&self, req: &Layer) -> Result<BlobChunks> {
+        // no deadline, streaming a large blob can take a while
+        let chunks = self.call(true, Some(req.get_digest()), |client| {
+            client.fetch_blob_opt(req, self.auth.call_option())
+        })?;
+        Ok(Box::new(chunks))
+    }
+
+    fn status(&self) -> Vec<(String, bool)> {
+        self.pool.status()
+    }
+}
+
+/// The backend of this process.
+pub struct Embedded {
+    service: BackendService,
+}
+
+impl Embedded {
+    pub fn new(service: BackendService) -> Embedded {
+        Embedded { service }
+    }
+}
+
+/// Fails like a remote call answered with _status_.
+fn failed<T>(result: ::std::result::Result<T, grpcio::RpcStatus>) -> Result<T> {
+    result.map_err(grpcio::Error::RpcFailure)
+}
+
+impl Backend for Embedded {
+    fn layer_exists(&self, req: &Layer) -> Result<LayerExistsResult> {
+        failed(self.service.layer_exists(req))
+    }
+
+    fn gen_uuid(&self, req: &Layer) -> Result<GenUuidResult> {
+        failed(self.service.gen_uuid(req))
+    }
+
+    fn uuid_exists(&self, req: &Layer) -> Result<BackendResult> {
+        failed(self.service.uuid_exists(req))
+    }
+
+    fn cancel_upload(&self, req: &Layer) -> Result<BackendResult> {
+        failed(self.service.cancel_upload(req))
+    }
+
+    fn commit_upload(&self, req: &CommittedBlob) -> Result<BackendResult> {
+        failed(self.service.commit_upload(req))
+    }
+
+    fn add_referrer(&self, req: &Referrer) -> Result<BackendResult> {
+        failed(self.service.add_referrer(req))
+    }
+
+    fn list_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList> {
+        failed(self.service.list_referrers(req))
+    }
+
+    fn remove_referrers(&self, req: &ReferrersRequest) -> Result<ReferrerList> {
+        failed(self.service.remove_referrers(req))
+    }
+
+    fn publish(&self, req: &Event) -> Result<BackendResult> {
+        failed(self.service.publish(req))
+    }
+
+    fn get_uuids(&self, req: &Empty) -> Result<UuidList> {
+        failed(self.service.get_uuids(req))
+    }
+
+    fn fetch_blob(&self, req: &Layer) -> Result<BlobChunks> {
+        // peers are reached through their own clients, not this thread
+        let chunks = failed(self.service.fetch_blob(req).wait())?;
+        Ok(chunks as BlobChunks)
+    }
+
+    fn status(&self) -> Vec<(String, bool)> {
+        let ready = self.service.health().problem().is_none();
+        vec![(String::from("embedded"), ready)]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+
+    use backend::Node;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn embedded_upload_session() {
+        let dir = env::temp_dir().join(format!("lycaon-rpc-{}", Uuid::new_v4()));
+        let backend = Embedded::new(Node::standalone(&dir).backend());
+        let mut layer = Layer::new();
+        layer.set_name(String::from("library/alpine"));
+        let uuid = backend.gen_uuid(&layer).unwrap();
+
+        layer.set_digest(uuid.get_uuid().to_owned());
+        assert!(backend.uuid_exists(&layer).unwrap().get_success());
+        let uuids = backend.get_uuids(&Empty::new()).unwrap();
+        assert!(uuids.get_uuids().iter().any(|u| u.get_uuid() == uuid.get_uuid()));
+
+        assert!(backend.cancel_upload(&layer).unwrap().get_success());
+        assert!(!backend.uuid_exists(&layer).unwrap().get_success());
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn cancelled_uploads_leave_no_scratch_file() {
+        let dir = env::temp_dir().join(format!("lycaon-rpc-{}", Uuid::new_v4()));
+        let backend = Embedded::new(Node::standalone(&dir).backend());
+        let mut layer = Layer::new();
+        layer.set_name(String::from("library/alpine"));
+        let uuid = backend.gen_uuid(&layer).unwrap();
+        layer.set_digest(uuid.get_uuid().to_owned());
+
+        let scratch = dir.join("scratch").join(uuid.get_uuid());
+        fs::create_dir_all(scratch.parent().unwrap()).unwrap();
+        fs::File::create(&scratch).unwrap();
+        assert!(backend.cancel_upload(&layer).unwrap().get_success());
+        assert!(!scratch.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn breaker_opens_and_closes() {
+        let breaker = Breaker::new(2, Duration::from_millis(50));
+        breaker.failed();
+        assert!(breaker.allow());
+        breaker.failed();
+        assert!(!breaker.allow());
+
+        thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+        breaker.answered();
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn answers_are_not_unavailable() {
+        let status = |code| grpcio::Error::RpcFailure(RpcStatus::new(code, None));
+        assert!(unavailable(&status(RpcStatusCode::Unavailable)));
+        assert!(unavailable(&status(RpcStatusCode::DeadlineExceeded)));
+        assert!(!unavailable(&status(RpcStatusCode::NotFound)));
+        assert!(!unavailable(&status(RpcStatusCode::Unauthenticated)));
+    }
+}
@@ -0,0 +1,292 @@
+//! Background integrity checks of the layer store.
+//!
+//! With a `[scrub]` section configured, every blob in the layer store
+//! is hashed again periodically, reading no faster than the configured
+//! rate. A blob that doesn't match its digest is moved to the
+//! `quarantine` directory so it's no longer served, and fetched again
+//! through the backend if a peer has a healthy copy. The progress of
+//! the running pass, or the outcome of the last one, is kept for the
+//! admin endpoint.
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use failure::Error;
+use futures::Stream;
+use uuid::Uuid;
+
+use config::{BackendHandler, ScrubConfig};
+use datadir::DataDir;
+use errors;
+use grpc::backend;
+use metrics;
+use state::uuid;
+use types::Digest;
+
+/// A blob which didn't match its digest.
+#[derive(Clone, Debug, Serialize)]
+pub struct Corruption {
+    pub digest: String,
+    pub actual: String,
+    /// Where the corrupt content was moved to
+    pub quarantined: String,
+    /// Whether a healthy copy was fetched from a peer
+    pub repaired: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub detected: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Report {
+    pub running: bool,
+    pub started: Option<String>,
+    pub finished: Option<String>,
+    /// Blobs checked so far
+    pub scanned: u64,
+    pub bytes: u64,
+    pub corrupted: Vec<Corruption>,
+}
+
+/// Managed state holding the scrub report.
+pub struct Scrubber {
+    enabled: bool,
+    report: Arc<Mutex<Report>>,
+}
+
+impl Scrubber {
+    /// Starts the thread scrubbing the layer store of _data_, repairing
+    /// blobs through _handler_.
+    pub fn start(
+        config: ScrubConfig,
+        handler: BackendHandler,
+        data: DataDir,
+    ) -> Result<Scrubber, Error> {
+        let report = Arc::new(Mutex::new(Report::default()));
+        let shared = report.clone();
+        thread::Builder::new()
+            .name(String::from("scrub"))
+            .spawn(move || loop {
+                scrub(&handler, &data, config.rate(), &shared);
+                thread::sleep(Duration::from_secs(config.interval()));
+            })?;
+        info!(
+            "Scrubbing blobs every {}s at up to {} bytes/s",
+            config.interval(),
+            config.rate()
+        );
+
+        Ok(Scrubber {
+            enabled: true,
+            report,
+        })
+    }
+
+    pub fn disabled() -> Scrubber {
+        Scrubber {
+            enabled: false,
+            report: Arc::new(Mutex::new(Report::default())),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn report(&self) -> Report {
+        self.report.lock().unwrap().clone()
+    }
+}
+
+/// Keeps reads under _rate_ bytes per second by sleeping whenever they
+/// get ahead of it.
+struct Throttle {
+    rate: u64,
+    start: Instant,
+    bytes: u64,
+}
+
+impl Throttle {
+    fn new(rate: u64) -> Throttle {
+        Throttle {
+            rate,
+            start: Instant::now(),
+            bytes: 0,
+        }
+    }
+
+    fn consumed(&mut self, bytes: usize) {
+        if self.rate == 0 {
+            return;
+        }
+        self.bytes += bytes as u64;
+        let due = Duration::from_millis(self.bytes * 1000 / self.rate);
+        let elapsed = self.start.elapsed();
+        if due > elapsed {
+            thread::sleep(due - elapsed);
+        }
+    }
+}
+
+/// A single pass over every blob.
+fn scrub(handler: &BackendHandler, data: &DataDir, rate: u64, report: &Mutex<Report>) {
+    *report.lock().unwrap() = Report {
+        running: true,
+        started: Some(Utc::now().to_rfc3339()),
+        ..Report::default()
+    };
+
+    let entries = match fs::read_dir(data.layers()) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("Can't scrub {:?}: {}", data.layers(), e);
+            report.lock().unwrap().running = false;
+            return;
+        }
+    };
+    let mut throttle = Throttle::new(rate);
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        // anything not named by its digest isn't a blob
+        let digest = match entry.file_name().to_str().map(|name| name.parse::<Digest>()) {
+            Some(Ok(digest)) => digest,
+            _ => continue,
+        };
+        let (size, actual) = match hash(&entry.path(), &digest, &mut throttle) {
+            Ok(hashed) => hashed,
+            // deleted since the directory was read
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => {
+                warn!("Scrubbing {}: {}", digest, e);
+                continue;
+            }
+        };
+        metrics::SCRUBBED_BLOBS.inc();
+        metrics::SCRUBBED_BYTES.inc_by(size as f64);
+        {
+            let mut report = report.lock().unwrap();
+            report.scanned += 1;
+            report.bytes += size;
+        }
+
+        if actual != digest {
+            let corruption = repair(handler, data, &entry.path(), &digest, &actual);
+            report.lock().unwrap().corrupted.push(corruption);
+        }
+    }
+
+    let mut report = report.lock().unwrap();
+    report.running = false;
+    report.finished = Some(Utc::now().to_rfc3339());
+    metrics::SCRUB_COMPLETED.set(Utc::now().timestamp() as f64);
+    info!(
+        "Scrubbed {} blobs, {} corrupt",
+        report.scanned,
+        report.corrupted.len()
+    );
+}
+
+/// The size and actual digest of the blob at _path_.
+fn hash(path: &Path, digest: &Digest, throttle: &mut Throttle) -> io::Result<(u64, Digest)> {
+    let mut file = File::open(path)?;
+    let mut hasher = digest.hasher();
+    let mut buffer = [0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        size += read as u64;
+        throttle.consumed(read);
+    }
+    Ok((size, hasher.finish()))
+}
+
+/// Quarantines the corrupt blob at _path_ and replaces it with a copy
+/// from a peer, if there is one.
+fn repair(
+    handler: &BackendHandler,
+    data: &DataDir,
+    path: &Path,
+    digest: &Digest,
+    actual: &Digest,
+) -> Corruption {
+    warn!("Blob {} is corrupt, its content hashes to {}", digest, actual);
+    metrics::CORRUPT_BLOBS.inc();
+    let mut corruption = Corruption {
+        digest: digest.to_string(),
+        actual: actual.to_string(),
+        quarantined: String::new(),
+        repaired: false,
+        error: None,
+        detected: Utc::now().to_rfc3339(),
+    };
+
+    // timestamped, the same blob may rot more than once
+    let quarantined = data
+        .quarantine()
+        .join(format!("{}.{}", digest, Utc::now().timestamp()));
+    let moved = fs::create_dir_all(data.quarantine()).and_then(|_| fs::rename(path, &quarantined));
+    if let Err(e) = moved {
+        corruption.error = Some(format!("quarantine failed: {}", e));
+        return corruption;
+    }
+    corruption.quarantined = quarantined.to_string_lossy().into_owned();
+
+    match fetch(handler, data, digest) {
+        Ok(()) => {
+            info!("Repaired {} from a peer", digest);
+            metrics::REPAIRED_BLOBS.inc();
+            corruption.repaired = true;
+        }
+        Err(e) => {
+            warn!("Can't repair {}: {}", digest, e);
+            corruption.error = Some(e.to_string());
+        }
+    }
+    corruption
+}
+
+/// Fetches _digest_ from a peer through the backend, verifying it
+/// before it's stored.
+fn fetch(handler: &BackendHandler, data: &DataDir, digest: &Digest) -> Result<(), Error> {
+    let mut req = backend::Layer::new();
+    req.set_digest(digest.to_string());
+    let chunks = handler
+        .backend()
+        .fetch_blob(&req)?;
+
+    let upload = format!("scrub-{}", Uuid::new_v4());
+    let scratch = data.upload(&upload);
+    let fetched = File::create(&scratch)
+        .map_err(Error::from)
+        .and_then(|mut file| {
+            let mut hasher = digest.hasher();
+            for chunk in chunks.wait() {
+                let chunk = chunk?;
+                hasher.update(chunk.get_data());
+                file.write_all(chunk.get_data())?;
+            }
+            Ok(hasher.finish())
+        });
+    match fetched {
+        Ok(ref actual) if actual == digest => {
+            uuid::save_layer(data, &upload, digest)?;
+            Ok(())
+        }
+        Ok(_) => {
+            let _ = fs::remove_file(&scratch);
+            Err(errors::Server::DigestMismatch.into())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&scratch);
+            Err(e)
+        }
+    }
+}
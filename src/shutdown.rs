@@ -0,0 +1,144 @@
+//! Graceful shutdown on SIGTERM.
+//!
+//! Once the signal arrives new requests are turned away with 503 while
+//! the ones in flight, such as uploads streaming their data, get up to
+//! the grace period to finish. The backend server of this process then
+//! sends its peers the CRDT deltas they haven't taken yet and saves its
+//! upload sessions, to pick them up again on the next start, before it
+//! shuts down. The size and hash of an upload aren't saved, they're
+//! rebuilt from its scratch file, see `state::uploads`.
+//!
+//! A request counts as finished once its response is handed to Rocket,
+//! so a body still streaming out, such as a large blob being pulled,
+//! may be cut off when the process exits.
+
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use ctrlc;
+use failure::Error;
+use rocket::{Data, Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+
+use backend;
+
+/// Where requests arriving while draining are sent
+pub const DRAINING_PATH: &'static str = "/draining";
+
+/// Still answered while draining, so the process isn't killed early
+const LIVENESS_PATH: &'static str = "/healthz";
+
+/// Milliseconds between checks for requests still in flight
+const POLL_INTERVAL: u64 = 100;
+
+/// Counts the requests in flight and turns new ones away once draining.
+#[derive(Clone)]
+pub struct Drain {
+    draining: Arc<AtomicBool>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drain {
+    pub fn new() -> Drain {
+        Drain {
+            draining: Arc::new(AtomicBool::new(false)),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Stops taking requests and waits up to _grace_ for the ones in
+    /// flight, returns whether they all finished.
+    fn drain(&self, grace: Duration) -> bool {
+        self.draining.store(true, Ordering::SeqCst);
+        let started = Instant::now();
+        while self.in_flight.load(Ordering::SeqCst) > 0 {
+            if started.elapsed() >= grace {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(POLL_INTERVAL));
+        }
+        true
+    }
+}
+
+impl Fairing for Drain {
+    fn info(&self) -> Info {
+        Info {
+            name: "Graceful shutdown",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request, _: &Data) {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        if self.draining() && request.uri().path() != LIVENESS_PATH {
+            request.set_method(Method::Get);
+            request.set_uri(DRAINING_PATH);
+        }
+    }
+
+    // Rocket writes the body out after this, see the module docs
+    fn on_response(&self, _: &Request, response: &mut Response) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        if self.draining() {
+            response.set_raw_header("Connection", "close");
+        }
+    }
+}
+
+/// The backend server of this process and the thread running it.
+pub type Server = (backend::Stop, JoinHandle<()>);
+
+/// Attaches the SIGTERM handler: _drain_ gets _grace_ to finish the
+/// requests in flight, then _server_ is shut down and the process exits.
+pub fn attach_sigterm(
+    grace: Duration,
+    drain: Option<Drain>,
+    server: Option<Server>,
+) -> Result<(), Error> {
+    let server = Mutex::new(server);
+    ctrlc::set_handler(move || {
+        info!("SIGTERM caught, shutting down...");
+        if let Some(ref drain) = drain {
+            if !drain.drain(grace) {
+                warn!("Requests still in flight after {:?}, shutting down anyway", grace);
+            }
+        }
+        if let Some((stop, thread)) = server.lock().unwrap().take() {
+            stop.stop();
+            if thread.join().is_err() {
+                warn!("Backend server failed while shutting down");
+            }
+        }
+        process::exit(0);
+    }).map_err(|e| e.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn drain_waits_for_requests_in_flight() {
+        let drain = Drain::new();
+        drain.in_flight.fetch_add(1, Ordering::SeqCst);
+        assert!(!drain.drain(Duration::from_millis(10)));
+        assert!(drain.draining());
+
+        let finishing = drain.clone();
+        let request = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            finishing.in_flight.fetch_sub(1, Ordering::SeqCst);
+        });
+        assert!(drain.drain(Duration::from_secs(5)));
+        request.join().unwrap();
+    }
+}
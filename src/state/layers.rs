@@ -28,14 +28,12 @@ mod tests {
         {
             let digest_len = g.gen_range(1, 256);
             let name_len = g.gen_range(1, 256);
-            let repo_len = g.gen_range(1, 256);
 
             let digest: String = g.gen_ascii_chars().take(digest_len).collect();
             let digest: String = format!("sha256:{}", digest);
             let name: String = g.gen_ascii_chars().take(name_len).collect();
-            let repo: String = g.gen_ascii_chars().take(repo_len).collect();
 
-            Layer::new( digest, name, repo )
+            Layer::new( name, digest )
         }
     }
 
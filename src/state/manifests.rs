@@ -0,0 +1,207 @@
+//! Manifest storage.
+//!
+//! Manifests are kept per repository in the data directory, similar to
+//! the layout of docker distribution:
+//!
+//! ```text
+//! repositories/<repository>/_manifests/revisions/<digest>
+//! repositories/<repository>/_manifests/revisions/<digest>.type
+//! repositories/<repository>/_manifests/tags/<tag>
+//! repositories/<repository>/_layers/<digest>
+//! ```
+//!
+//! A tag file holds the digest of the manifest it points to, the
+//! `.type` file the media type the manifest was pushed with. Blobs are
+//! stored once in `layers`, an empty `_layers` file links one into a
+//! repository.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use failure::Error;
+
+use datadir::DataDir;
+use types::{Algorithm, Digest};
+
+#[derive(Debug, Clone)]
+pub struct Manifest {
+    pub media_type: String,
+    pub digest: String,
+    pub content: Vec<u8>,
+}
+
+/// Whether _reference_ is meant as a digest rather than a tag, tags
+/// can't contain a colon. It may still be a malformed one.
+pub fn is_digest(reference: &str) -> bool {
+    reference.contains(':')
+}
+
+/// The `sha256:<hex>` digest of _content_.
+pub fn digest_of(content: &[u8]) -> Digest {
+    Digest::of(Algorithm::Sha256, content)
+}
+
+fn revision_path(data: &DataDir, repository: &str, digest: &str) -> PathBuf {
+    data.manifests(repository).join("revisions").join(digest)
+}
+
+fn tag_path(data: &DataDir, repository: &str, tag: &str) -> PathBuf {
+    data.manifests(repository).join("tags").join(tag)
+}
+
+fn read(path: &PathBuf) -> Option<Vec<u8>> {
+    let mut contents = Vec::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut contents))
+        .ok()
+        .map(|_| contents)
+}
+
+/// Writes via a temporary file so readers never see partial content.
+fn write(path: &PathBuf, contents: &[u8]) -> Result<(), Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    // tags may contain dots, so don't use with_extension here
+    let tmp = path.with_file_name(format!(".{}.tmp", name));
+    File::create(&tmp)?.write_all(contents)?;
+    fs::rename(tmp, path).map_err(|e| e.into())
+}
+
+/// The digest _reference_ points to in _repository_.
+pub fn resolve(data: &DataDir, repository: &str, reference: &str) -> Option<String> {
+    if is_digest(reference) {
+        return reference
+            .parse::<Digest>()
+            .ok()
+            .map(|digest| digest.to_string());
+    }
+    read(&tag_path(data, repository, reference))
+        .and_then(|digest| String::from_utf8(digest).ok())
+        .map(|digest| digest.trim().to_owned())
+}
+
+pub fn get(data: &DataDir, repository: &str, reference: &str) -> Option<Manifest> {
+    let digest = resolve(data, repository, reference)?;
+    let path = revision_path(data, repository, &digest);
+    let content = read(&path)?;
+    let media_type = read(&path.with_extension("type"))
+        .and_then(|media_type| String::from_utf8(media_type).ok())
+        .unwrap_or_default();
+
+    Some(Manifest {
+        media_type,
+        digest,
+        content,
+    })
+}
+
+/// Stores _content_ and, if _reference_ is a tag, points the tag at it.
+pub fn put(
+    data: &DataDir,
+    repository: &str,
+    reference: &str,
+    media_type: &str,
+    content: Vec<u8>,
+) -> Result<Manifest, Error> {
+    // a manifest pushed by digest is stored under that digest's algorithm
+    let digest = match reference.parse::<Digest>() {
+        Ok(expected) => Digest::of(expected.algorithm(), &content),
+        Err(_) => digest_of(&content),
+    };
+    store(data, repository, reference, media_type, &digest, content)
+}
+
+/// Stores _content_ under _digest_, which has to be its digest, and
+/// points the tag _reference_ at it unless that's a digest itself.
+pub fn store(
+    data: &DataDir,
+    repository: &str,
+    reference: &str,
+    media_type: &str,
+    digest: &Digest,
+    content: Vec<u8>,
+) -> Result<Manifest, Error> {
+    let digest = digest.to_string();
+    let path = revision_path(data, repository, &digest);
+    write(&path, &content)?;
+    write(&path.with_extension("type"), media_type.as_bytes())?;
+    if !is_digest(reference) {
+        write(&tag_path(data, repository, reference), digest.as_bytes())?;
+    }
+    debug!("Stored manifest {}@{}", repository, digest);
+
+    Ok(Manifest {
+        media_type: media_type.to_owned(),
+        digest,
+        content,
+    })
+}
+
+/// Time since _tag_ was last written.
+pub fn tag_age(data: &DataDir, repository: &str, tag: &str) -> Option<Duration> {
+    fs::metadata(tag_path(data, repository, tag))
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+}
+
+/// Marks _tag_ as up to date without changing it.
+pub fn touch_tag(data: &DataDir, repository: &str, tag: &str) -> Result<(), Error> {
+    let path = tag_path(data, repository, tag);
+    let digest = read(&path).unwrap_or_default();
+    write(&path, &digest)
+}
+
+/// Deletes the manifest with _digest_ and every tag pointing to it.
+/// Returns the removed tags, or `None` if the manifest didn't exist.
+pub fn delete(data: &DataDir, repository: &str, digest: &str) -> Result<Option<Vec<String>>, Error> {
+    let path = revision_path(data, repository, digest);
+    if fs::metadata(&path).is_err() {
+        return Ok(None);
+    }
+
+    let mut removed = Vec::new();
+    for tag in tags(data, repository) {
+        if resolve(data, repository, &tag).map(|d| d == digest).unwrap_or(false) {
+            fs::remove_file(tag_path(data, repository, &tag))?;
+            removed.push(tag);
+        }
+    }
+    fs::remove_file(&path)?;
+    let _ = fs::remove_file(path.with_extension("type"));
+    Ok(Some(removed))
+}
+
+/// All tags of _repository_, sorted.
+pub fn tags(data: &DataDir, repository: &str) -> Vec<String> {
+    let mut tags: Vec<String> = fs::read_dir(data.manifests(repository).join("tags"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .filter(|name| !name.starts_with('.'))
+                .collect()
+        })
+        .unwrap_or_default();
+    tags.sort();
+    tags
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn digest_format() {
+        assert_eq!(
+            digest_of(b"").to_string(),
+            "sha256:e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert!(is_digest("sha256:abc"));
+        assert!(!is_digest("latest"));
+    }
+}
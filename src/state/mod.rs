@@ -2,6 +2,8 @@ use rocket;
 
 /// Export Module layers
 mod layers;
+pub(crate) mod manifests;
+pub(crate) mod uploads;
 pub(crate) mod uuid;
 
 // TODO: merge this into the Config struct in config.rs
@@ -0,0 +1,229 @@
+//! Upload sessions of this frontend.
+//!
+//! Chunks are hashed while they are streamed to the scratch file, so
+//! finishing an upload never has to read it back. A session is only
+//! kept in memory: an upload this frontend doesn't know about, e.g.
+//! after a restart, is hashed from its scratch file once and then
+//! continued as usual. So nothing is saved on shutdown, the scratch
+//! file has everything the size and hash are rebuilt from.
+//!
+//! Each session has its own lock, held while a chunk is streamed. A
+//! chunk arriving while another one of the same upload is still being
+//! received is rejected with `RANGE_INVALID`, the two would interleave.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, TryLockError};
+
+use failure::Error;
+
+use datadir::DataDir;
+use errors;
+use metrics;
+use state::uuid;
+use types::{Algorithm, Digest, Hasher};
+
+/// Clients use sha256 in practice, anything else is hashed from the
+/// scratch file when the upload finishes
+const STREAMED_ALGORITHM: Algorithm = Algorithm::Sha256;
+
+struct Session {
+    size: u64,
+    hasher: Hasher,
+}
+
+impl Session {
+    /// Picks up an upload from what's already in its scratch file.
+    fn resume(path: &Path) -> Result<Session, Error> {
+        let mut hasher = Hasher::new(STREAMED_ALGORITHM);
+        let size = match File::open(path) {
+            Ok(mut file) => io::copy(&mut file, &mut hasher)?,
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Session { size, hasher })
+    }
+
+    /// Streams _chunk_ to the end of the scratch file at _path_.
+    fn append<R: Read>(&mut self, path: &Path, mut chunk: R) -> Result<(), Error> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            let read = match chunk.read(&mut buffer) {
+                Ok(0) => return Ok(()),
+                Ok(read) => read,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            file.write_all(&buffer[..read])?;
+            self.hasher.update(&buffer[..read]);
+            self.size += read as u64;
+            metrics::BYTES_UPLOADED.inc_by(read as f64);
+        }
+    }
+}
+
+/// A session, `None` until it's picked up from its scratch file.
+type Shared = Arc<Mutex<Option<Session>>>;
+
+pub struct Uploads {
+    data: DataDir,
+    sessions: Mutex<HashMap<String, Shared>>,
+}
+
+impl Uploads {
+    /// Upload sessions streaming to the scratch files of _data_.
+    pub fn new(data: DataDir) -> Uploads {
+        Uploads {
+            data,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Appends _chunk_ to the upload _uuid_, returns how much of the
+    /// upload has been received so far.
+    pub fn append<R: Read>(&self, uuid: &String, chunk: R) -> Result<u64, Error> {
+        let path = self.data.upload(uuid);
+        let shared = self.sessions
+            .lock()
+            .unwrap()
+            .entry(uuid.to_owned())
+            .or_insert_with(|| Arc::new(Mutex::new(None)))
+            .clone();
+        let mut session = lock(&shared)?;
+        if session.is_none() {
+            *session = Some(Session::resume(&path)?);
+        }
+
+        let appended = match *session {
+            Some(ref mut session) => session.append(&path, chunk).map(|()| session.size),
+            None => unreachable!(),
+        };
+        // whatever made it into the scratch file is hashed again from there
+        if appended.is_err() {
+            *session = None;
+        }
+        appended
+    }
+
+    /// Ends the upload _uuid_, returning the _algorithm_ digest of
+    /// everything it received.
+    pub fn finish(&self, uuid: &String, algorithm: Algorithm) -> Result<Digest, Error> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = match sessions.get(uuid) {
+            Some(shared) => lock(shared)?.take(),
+            None => None,
+        };
+        sessions.remove(uuid);
+        drop(sessions);
+
+        match session {
+            Some(session) if algorithm == STREAMED_ALGORITHM => Ok(session.hasher.finish()),
+            _ => uuid::hash_file(&self.data.upload(uuid), algorithm),
+        }
+    }
+
+    /// Forgets the upload _uuid_, its scratch file is left alone.
+    pub fn cancel(&self, uuid: &String) {
+        self.sessions.lock().unwrap().remove(uuid);
+    }
+}
+
+/// Takes the lock of a session, unless a chunk is being appended to it.
+fn lock(shared: &Shared) -> Result<MutexGuard<Option<Session>>, Error> {
+    match shared.try_lock() {
+        Ok(session) => Ok(session),
+        Err(TryLockError::WouldBlock) => Err(errors::Client::RANGE_INVALID.into()),
+        // a chunk failed halfway, the session is picked up from the file
+        Err(TryLockError::Poisoned(poisoned)) => {
+            let mut session = poisoned.into_inner();
+            *session = None;
+            Ok(session)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+    use std::fs;
+    use std::sync::mpsc;
+    use std::thread;
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn uploads_survive_a_restart() {
+        let root = env::temp_dir().join(format!("lycaon-uploads-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("layers")).unwrap();
+        fs::create_dir_all(root.join("scratch")).unwrap();
+        let upload = Uuid::new_v4().to_string();
+
+        let before = Uploads::new(DataDir::open(&root).unwrap());
+        assert_eq!(before.append(&upload, &b"first "[..]).unwrap(), 6);
+
+        // a new frontend only has the scratch file
+        let after = Uploads::new(DataDir::open(&root).unwrap());
+        assert_eq!(after.append(&upload, &b"second"[..]).unwrap(), 12);
+        assert_eq!(
+            after.finish(&upload, Algorithm::Sha256).unwrap(),
+            Digest::of(Algorithm::Sha256, b"first second")
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    /// A chunk that doesn't end until _release_ is dropped or sent to.
+    struct Stalled(mpsc::Receiver<()>);
+
+    impl Read for Stalled {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            let _ = self.0.recv();
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn overlapping_chunks_are_rejected() {
+        let root = env::temp_dir().join(format!("lycaon-uploads-{}", Uuid::new_v4()));
+        fs::create_dir_all(root.join("layers")).unwrap();
+        fs::create_dir_all(root.join("scratch")).unwrap();
+        let upload = Uuid::new_v4().to_string();
+        let uploads = Arc::new(Uploads::new(DataDir::open(&root).unwrap()));
+        assert_eq!(uploads.append(&upload, &b"first "[..]).unwrap(), 6);
+
+        let (release, stalled) = mpsc::channel();
+        let (started, start) = mpsc::channel();
+        let appending = {
+            let (uploads, upload) = (uploads.clone(), upload.clone());
+            thread::spawn(move || {
+                let shared = uploads.sessions.lock().unwrap()[&upload].clone();
+                let _ = started.send(());
+                // wait until the other chunk holds the session
+                while shared.try_lock().is_ok() {
+                    thread::yield_now();
+                }
+                let result = uploads.append(&upload, &b"second"[..]);
+                drop(release);
+                result
+            })
+        };
+        start.recv().unwrap();
+        assert_eq!(uploads.append(&upload, Stalled(stalled)).unwrap(), 6);
+
+        let error = appending.join().unwrap().unwrap_err();
+        match error.downcast::<errors::Client>() {
+            Ok(errors::Client::RANGE_INVALID) => {}
+            other => panic!("expected RANGE_INVALID, got {:?}", other),
+        }
+        assert_eq!(uploads.append(&upload, &b"second"[..]).unwrap(), 12);
+        assert_eq!(
+            uploads.finish(&upload, Algorithm::Sha256).unwrap(),
+            Digest::of(Algorithm::Sha256, b"first second")
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
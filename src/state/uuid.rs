@@ -2,10 +2,12 @@ use std::collections::HashSet;
 use std::fs;
 use std::fs::File;
 use std::io;
-use std::io::Read;
+use std::path::Path;
 
 use failure::Error;
-use ring::digest;
+
+use datadir::DataDir;
+use types::{Algorithm, Digest, Hasher};
 
 pub struct UuidImpl {
     digests: HashSet<String>,
@@ -16,49 +18,68 @@ impl UuidImpl {
     }
 }
 
-pub fn scratch_path(uuid: &String) -> String {
-    warn!("Deprecated, please use the recommended function");
-    format!("data/scratch/{}", uuid)
+/// Size of a stored layer, 0 if it doesn't exist.
+pub fn blob_size(data: &DataDir, digest: &Digest) -> u64 {
+    fs::metadata(data.blob(digest))
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
 }
 
-pub fn layer_path(fname: &String) -> String {
-    warn!("Deprecated, please use the recommended function");
-    format!("data/layers/{}", fname)
+/// Whether a layer with _digest_ is stored.
+pub fn blob_exists(data: &DataDir, digest: &Digest) -> bool {
+    fs::metadata(data.blob(digest)).is_ok()
 }
 
-/// given a _uuid_ and a _hash_, will copy the layer to the _layers_
-/// directory from the _scratch_ directory.
-pub fn save_layer(uuid: &String, digest: &String) -> io::Result<u64> {
-    let from = scratch_path(uuid);
-    let to = layer_path(digest);
+/// given a _uuid_ and a _hash_, will move the layer to the _layers_
+/// directory from the _scratch_ directory. Returns the layer's size.
+///
+/// The scratch file is synced before it's linked in under its digest,
+/// so a crash never leaves a truncated layer behind. Layers are named
+/// by their content, so if the digest is already stored, possibly by a
+/// concurrent upload, the existing layer is kept and the scratch file
+/// dropped.
+pub fn save_layer(data: &DataDir, uuid: &str, digest: &Digest) -> io::Result<u64> {
+    let from = data.upload(uuid);
+    let to = data.blob(digest);
+
+    File::open(&from)?.sync_all()?;
+    // unlike a rename, linking never replaces an existing layer
+    match fs::hard_link(&from, &to) {
+        Ok(()) => debug!("Committed {:?} -> {:?}", from, to),
+        Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            debug!("{} is already stored, dropping {:?}", digest, from)
+        }
+        Err(e) => {
+            // e.g. file systems without hard links
+            debug!("Linking {:?} failed ({}), renaming instead", from, e);
+            fs::rename(&from, &to)?;
+        }
+    }
+    if let Err(e) = fs::remove_file(&from) {
+        if e.kind() != io::ErrorKind::NotFound {
+            warn!("Removing {:?}: {}", from, e);
+        }
+    }
+    // make the new directory entry durable too
+    File::open(data.layers())?.sync_all()?;
 
-    // TODO: check if layer already exists.
-    debug!("Copying {} -> {}", from, to);
-    fs::copy(from, to)
+    fs::metadata(to).map(|metadata| metadata.len())
 }
 
 /// Marks the given uuid for deletion.
 /// The current implementation simply deletes the file, future
 /// implementations may want to propogate the message to neighbouring
 /// registry instances.
-pub fn mark_delete(uuid: &String) -> io::Result<()> {
-    let file = scratch_path(uuid);
-    fs::remove_file(file)
+pub fn mark_delete(data: &DataDir, uuid: &str) -> io::Result<()> {
+    fs::remove_file(data.upload(uuid))
 }
 
 
-// TODO change this to return a type-safe thing rather than just 'String'
-pub fn hash_file(absolute_directory: String) -> Result<String, Error> {
-    debug!("Hashing file: {}", absolute_directory);
-    match File::open(&absolute_directory) {
-        Ok(mut file) => {
-            let mut vec_file = &mut Vec::new();
-            let _ = file.read_to_end(&mut vec_file);
-            let sha = digest::digest(&digest::SHA256, &vec_file);
-
-            // HACK: needs a fix of some description
-            Ok(format!("{:?}", sha).to_lowercase())
-        }
-        Err(e) => Err(e.into()),
-    }
+/// The _algorithm_ digest of the file at _path_, read in a single
+/// streaming pass.
+pub fn hash_file(path: &Path, algorithm: Algorithm) -> Result<Digest, Error> {
+    debug!("Hashing file: {:?}", path);
+    let mut hasher = Hasher::new(algorithm);
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher.finish())
 }
@@ -0,0 +1,57 @@
+//! Request IDs for HTTP requests.
+//!
+//! The `RequestId` fairing opens a server span for every request,
+//! reusing the client's `X-Request-ID` and `traceparent` headers when
+//! present, and echoes the request ID back in the response. The span
+//! is current for the rest of the request, so backend calls made
+//! through `rpc::Remote` carry it along.
+
+use std::cell::RefCell;
+
+use rocket::{Data, Request, Response};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+
+use backend::trace::Span;
+
+thread_local! {
+    static REQUEST_SPAN: RefCell<Option<Span>> = RefCell::new(None);
+}
+
+pub struct RequestId;
+
+impl Fairing for RequestId {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request IDs",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request, _: &Data) {
+        // finish a span left over from a request that never got a response
+        REQUEST_SPAN.with(|current| current.borrow_mut().take());
+
+        let request_id = match request.headers().get_one("X-Request-ID") {
+            Some(id) if !id.is_empty() && id.len() <= 128 => Some(id.to_owned()),
+            _ => None,
+        };
+        let traceparent = request.headers().get_one("traceparent");
+
+        let name = format!("{} {}", request.method(), request.uri().path());
+        let span = Span::server(&name, request_id, traceparent);
+        REQUEST_SPAN.with(|current| *current.borrow_mut() = Some(span));
+    }
+
+    fn on_response(&self, request: &Request, response: &mut Response) {
+        let span = REQUEST_SPAN.with(|current| current.borrow_mut().take());
+        if let Some(mut span) = span {
+            if let Some(route) = request.route() {
+                span.set_attribute("http.route", route.uri.path());
+            }
+            span.set_attribute("http.method", request.method().as_str());
+            span.set_attribute("http.status_code", &response.status().code.to_string());
+            response.set_header(Header::new("X-Request-ID", span.context().request_id.clone()));
+        }
+    }
+}
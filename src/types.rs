@@ -1,17 +1,19 @@
-pub type Digest = String;
+pub use backend::digest::{Algorithm, Digest, Hasher};
 
+/// _name_ is the full repository name, of any depth
+/// _digest_ is the layer's digest, or the upload's uuid while it's in
+/// the scratch directory
 #[derive(Debug, Clone)]
 pub struct Layer {
-    pub digest: Digest,
+    pub digest: String,
     pub name: String,
-    pub repo: String,
 }
 impl Layer {
-    pub fn new(name: String, repo: String, digest: Digest) -> Layer {
-        Layer { digest, name, repo }
+    pub fn new(name: String, digest: String) -> Layer {
+        Layer { digest, name }
     }
 
-    pub fn digest(&self) -> Digest {
+    pub fn digest(&self) -> String {
         self.digest.clone()
     }
 }
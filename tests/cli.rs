@@ -1,8 +1,42 @@
 extern crate assert_cli;
+extern crate ring;
+extern crate uuid;
 
 #[cfg(test)]
 mod cli {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+
     use assert_cli;
+    use ring::digest;
+    use uuid::Uuid;
+
+    /// An empty data directory for offline commands, unique to a test
+    /// run so tests running in parallel don't share one.
+    fn data_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("lycaon-cli-{}-{}", name, Uuid::new_v4()));
+        fs::create_dir_all(dir.join("layers")).unwrap();
+        dir
+    }
+
+    fn write(path: &Path, content: &[u8]) {
+        File::create(path).unwrap().write_all(content).unwrap();
+    }
+
+    /// Stores _content_ in the OCI layout at _layout_, returns its digest.
+    fn layout_blob(layout: &Path, content: &[u8]) -> String {
+        let hex: String = digest::digest(&digest::SHA256, content)
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        let dir = layout.join("blobs").join("sha256");
+        fs::create_dir_all(&dir).unwrap();
+        write(&dir.join(&hex), content);
+        format!("sha256:{}", hex)
+    }
 
     #[test]
     fn invalid_argument() {
@@ -26,4 +60,130 @@ mod cli {
             .contains("Lycaon")
             .unwrap();
     }
+
+    #[test]
+    fn serve_one_side_only() {
+        assert_cli::Assert::main_binary()
+            .with_args(&["serve", "--frontend-only", "--backend-only"])
+            .fails()
+            .and()
+            .stderr()
+            .contains("cannot be used with")
+            .unwrap();
+    }
+
+    #[test]
+    fn gc_empty_data_dir() {
+        let dir = data_dir("gc");
+        assert_cli::Assert::main_binary()
+            .with_args(&["gc", "--dry-run", "--data-dir", dir.to_str().unwrap()])
+            .succeeds()
+            .and()
+            .stdout()
+            .contains("would remove 0 manifests and 0 blobs")
+            .unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn fsck_empty_data_dir() {
+        let dir = data_dir("fsck");
+        assert_cli::Assert::main_binary()
+            .with_args(&["fsck", "--data-dir", dir.to_str().unwrap()])
+            .succeeds()
+            .and()
+            .stdout()
+            .contains("0 problems")
+            .unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn import_needs_a_layout() {
+        let dir = data_dir("import");
+        assert_cli::Assert::main_binary()
+            .with_args(&["import", "--data-dir", dir.to_str().unwrap(), "/nonexistent", "alpine"])
+            .fails_with(2)
+            .and()
+            .stderr()
+            .contains("is not an OCI layout")
+            .unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn export_needs_tags() {
+        let dir = data_dir("export");
+        assert_cli::Assert::main_binary()
+            .with_args(&["export", "--data-dir", dir.to_str().unwrap(), "-o", "/tmp", "alpine"])
+            .fails_with(2)
+            .and()
+            .stderr()
+            .contains("alpine has no tags")
+            .unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn import_then_export() {
+        let dir = data_dir("roundtrip");
+        let layout = dir.join("layout");
+        let config = layout_blob(&layout, b"{}");
+        let manifest = format!(
+            r#"{{"schemaVersion": 2, "config": {{"digest": "{}", "size": 2}}, "layers": []}}"#,
+            config
+        );
+        let manifest = layout_blob(&layout, manifest.as_bytes());
+        let index = format!(
+            r#"{{"schemaVersion": 2, "manifests": [{{
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": "{}",
+                "annotations": {{"org.opencontainers.image.ref.name": "3.7"}}
+            }}]}}"#,
+            manifest
+        );
+        write(&layout.join("index.json"), index.as_bytes());
+        write(&layout.join("oci-layout"), br#"{"imageLayoutVersion": "1.0.0"}"#);
+
+        let data = dir.to_str().unwrap();
+        assert_cli::Assert::main_binary()
+            .with_args(&["import", "--data-dir", data, layout.to_str().unwrap(), "library/alpine"])
+            .succeeds()
+            .and()
+            .stdout()
+            .contains("imported 1 manifests and 1 blobs")
+            .unwrap();
+        let output = dir.join("export");
+        assert_cli::Assert::main_binary()
+            .with_args(&["export", "--data-dir", data, "-o", output.to_str().unwrap(), "library/alpine"])
+            .succeeds()
+            .and()
+            .stdout()
+            .contains("tagged 3.7")
+            .unwrap();
+        assert!(output.join("index.json").is_file());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn peers_timeout_is_checked() {
+        assert_cli::Assert::main_binary()
+            .with_args(&["peers", "--timeout", "soon"])
+            .fails_with(2)
+            .and()
+            .stderr()
+            .contains("timeout must be a number of seconds")
+            .unwrap();
+    }
+
+    #[test]
+    fn fsck_needs_a_data_dir() {
+        assert_cli::Assert::main_binary()
+            .with_args(&["fsck", "--data-dir", "/nonexistent/lycaon"])
+            .fails_with(2)
+            .and()
+            .stderr()
+            .contains("is not a data directory")
+            .unwrap();
+    }
 }